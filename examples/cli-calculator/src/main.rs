@@ -4,9 +4,30 @@ use rustyline::DefaultEditor;
 use calculator::*;
 
 /// A simple demo application for demonstrating the calculator lib.
+///
+/// With `--programmer` values are fixed-width integers like `u8(300)`,
+/// which are printed in hex and binary as well.
+///
+/// Results are formatted with the options `--fixed <decimals>`, `--digits <significant digits>`,
+/// `--sci`, `--eng`, `--si`, `--separator <char>` and `--radix <2|8|10|16>`.
 fn main() {
 	println!("Simple calculator in Rust");
 
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let format = parse_format(&args);
+
+	if args.iter().any(|arg| arg == "--programmer") {
+		let mut calculator = Calculator::<Word>::default();
+		calculator.init_std();
+		repl(calculator, |result| match result {
+			Value::Number(word) => {
+				format!("{} ({}, {:#x}, {:#b})", word, word.word_type(), word, word)
+			}
+			_ => result.to_string(),
+		});
+		return;
+	}
+
 	let mut calculator = Calculator::new();
 
 	// initialize std lib
@@ -16,8 +37,8 @@ fn main() {
 	calculator.add_var("foo", 40.0);
 
 	// initialize predefined functions
-	calculator.add_fn("double", |arg: f32| arg * 2.0);
-	fn div(a: f32, b: f32) -> Result<f32, Error> {
+	calculator.add_fn("double", |arg: f64| arg * 2.0);
+	fn div(a: f64, b: f64) -> Result<f64, Error> {
 		if b == 0.0 {
 			Err(Error::Fatal("Division by zero!"))
 		} else {
@@ -26,6 +47,47 @@ fn main() {
 	}
 	calculator.add_fn("div", div);
 
+	repl(calculator, move |result| {
+		// results which can't be formatted like non-integers in hex are displayed as they are
+		result
+			.format(&format)
+			.unwrap_or_else(|_| result.to_string())
+	});
+}
+
+/// Reads the format options from the command line arguments, invalid options are ignored.
+fn parse_format(args: &[String]) -> NumberFormat {
+	let mut format = NumberFormat::default();
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--sci" => format.notation = Notation::Scientific,
+			"--eng" => format.notation = Notation::Engineering,
+			"--si" => format.notation = Notation::Si,
+			"--fixed" => {
+				if let Some(decimals) = args.next().and_then(|arg| arg.parse().ok()) {
+					format.precision = Precision::Fixed(decimals);
+				}
+			}
+			"--digits" => {
+				if let Some(digits) = args.next().and_then(|arg| arg.parse().ok()) {
+					format.precision = Precision::Significant(digits);
+				}
+			}
+			"--separator" => format.separator = args.next().and_then(|arg| arg.chars().next()),
+			"--radix" => {
+				if let Some(radix) = args.next().and_then(|arg| arg.parse().ok()) {
+					format.radix = radix;
+				}
+			}
+			_ => (),
+		}
+	}
+	format
+}
+
+/// Reads and evaluates expressions until an empty line is entered.
+fn repl<N: Number>(mut calculator: Calculator<N>, display: impl Fn(&Value<N>) -> String) {
 	// read expressions
 	let mut rl = DefaultEditor::new().expect("Failed to create new editor!");
 	while let Ok(input) = rl.readline("> ") {
@@ -38,20 +100,36 @@ fn main() {
 
 		// evaluate line
 		match calculator.calculate(&input) {
-			Ok(result) => println!("= {}", result),
+			Ok(result) => println!("= {}", display(&result)),
 			Err(e) => {
 				eprintln!("{}: {}", "ERROR".red(), e);
 				match e {
-					Error::Fatal(_) | Error::Runtime(_) | Error::UnexpectedEndOfInput => (),
+					Error::Fatal(_)
+					| Error::Runtime(_)
+					| Error::UnexpectedEndOfInput
+					| Error::IntervalContainsZero { .. }
+					| Error::NoConvergence { .. } => (),
 					Error::InvalidCharacter(_, pos) => {
 						print_error_position(&input, pos, pos);
 					}
 					Error::UnexpectedToken { start, end, .. } => {
 						print_error_position(&input, start, end);
 					}
+					Error::InvalidNumber { start, end, .. } => {
+						print_error_position(&input, start, end);
+					}
 					Error::VariableNotFound { start, end, .. } => {
 						print_error_position(&input, start, end);
 					}
+					Error::DimensionMismatch { start, end, .. } => {
+						print_error_position(&input, start, end);
+					}
+					Error::UnitNotFound { start, end, .. } => {
+						print_error_position(&input, start, end);
+					}
+					Error::ShapeMismatch { start, end, .. } => {
+						print_error_position(&input, start, end);
+					}
 				}
 			}
 		}
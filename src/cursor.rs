@@ -1,20 +1,30 @@
-use crate::{AddOperator, Error, ExpOperator, MulOperator, Token, TokenValue};
+use crate::{
+	AddOperator, BitOperator, CmpOperator, Error, ExpOperator, LogicOperator, MulOperator, Number,
+	Token, TokenValue,
+};
 
-pub struct Cursor {
-	tokens: Vec<Token>,
+pub struct Cursor<N> {
+	tokens: Vec<Token<N>>,
 	pointer: usize,
 }
 
-impl Cursor {
-	pub fn new(tokens: Vec<Token>) -> Cursor {
+impl<N: Number> Cursor<N> {
+	pub fn new(tokens: Vec<Token<N>>) -> Cursor<N> {
 		Cursor { tokens, pointer: 0 }
 	}
 
-	pub fn current(&self) -> Option<Token> {
+	/// Creates a cursor for a part of the input, e.g. the body of a function.
+	pub fn with_eof(mut tokens: Vec<Token<N>>) -> Cursor<N> {
+		let end = tokens.last().map_or(0, |token| token.end + 1);
+		tokens.push(Token::new(TokenValue::Eof, "EOF".to_owned(), end, end));
+		Self::new(tokens)
+	}
+
+	pub fn current(&self) -> Option<Token<N>> {
 		self.tokens.get(self.pointer).cloned()
 	}
 
-	pub fn consume(&mut self) -> Option<Token> {
+	pub fn consume(&mut self) -> Option<Token<N>> {
 		if let Some(token) = self.tokens.get(self.pointer) {
 			self.pointer += 1;
 			Some(token.clone())
@@ -23,11 +33,29 @@ impl Cursor {
 		}
 	}
 
-	pub fn next(&self) -> Option<Token> {
+	pub fn previous(&self) -> Option<Token<N>> {
+		self.tokens.get(self.pointer.checked_sub(1)?).cloned()
+	}
+
+	pub fn next(&self) -> Option<Token<N>> {
 		self.tokens.get(self.pointer + 1).cloned()
 	}
 
-	pub fn expect(&mut self, expected: &TokenValue) -> Result<Token, Error> {
+	/// Returns the token at the given offset from the current token.
+	pub fn peek(&self, offset: usize) -> Option<Token<N>> {
+		self.tokens.get(self.pointer + offset).cloned()
+	}
+
+	pub fn position(&self) -> usize {
+		self.pointer
+	}
+
+	/// Returns the consumed tokens since the given position.
+	pub fn slice(&self, start: usize) -> Vec<Token<N>> {
+		self.tokens[start..self.pointer].to_vec()
+	}
+
+	pub fn expect(&mut self, expected: &TokenValue<N>) -> Result<Token<N>, Error> {
 		let token = self.consume().ok_or(Error::UnexpectedEndOfInput)?;
 		match &token.value {
 			value if value == expected => Ok(token),
@@ -62,6 +90,39 @@ impl Cursor {
 		}
 	}
 
+	pub fn get_cmp_op(&mut self) -> Result<Option<CmpOperator>, Error> {
+		if let TokenValue::CmpOperator(op) =
+			self.consume().ok_or(Error::UnexpectedEndOfInput)?.value
+		{
+			Ok(Some(op))
+		} else {
+			self.pointer -= 1;
+			Ok(None)
+		}
+	}
+
+	/// Consumes the next token if it is the given logical operator.
+	pub fn get_logic_op(&mut self, op: LogicOperator) -> Result<bool, Error> {
+		let token = self.current().ok_or(Error::UnexpectedEndOfInput)?;
+		if token.value == TokenValue::LogicOperator(op) {
+			self.pointer += 1;
+			Ok(true)
+		} else {
+			Ok(false)
+		}
+	}
+
+	pub fn get_bit_op(&mut self) -> Result<Option<BitOperator>, Error> {
+		if let TokenValue::BitOperator(op) =
+			self.consume().ok_or(Error::UnexpectedEndOfInput)?.value
+		{
+			Ok(Some(op))
+		} else {
+			self.pointer -= 1;
+			Ok(None)
+		}
+	}
+
 	pub fn get_exp_op(&mut self) -> Result<Option<ExpOperator>, Error> {
 		if let TokenValue::ExpOperator(op) =
 			self.consume().ok_or(Error::UnexpectedEndOfInput)?.value
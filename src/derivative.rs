@@ -0,0 +1,79 @@
+use crate::{
+	Cursor, Dual, Environment, Error, Number, Parser, Quantity, Token, Unit, Value, Variable,
+};
+
+/// Expression or function which is differentiated by `diff`.
+pub enum Derivative<N> {
+	/// Derivative of an expression for a variable at its current value, e.g. `diff(x**2, x)`.
+	Expression { body: Vec<Token<N>>, var: String },
+	/// Derivative of a function at the given point, e.g. `diff(f, 2)`.
+	Function { name: String, at: Value<N> },
+}
+
+/// Implementation of `diff`, see [`differentiate`].
+pub type Differentiate<N> = fn(&Environment<N>, Derivative<N>) -> Result<Value<N>, Error>;
+
+/// Calculates the derivative with forward-mode automatic differentiation.
+///
+/// The expression is evaluated by a parser for dual numbers, where the variable has the derivative `1`
+/// and everything else is constant. Derivatives for quantities are divided by the unit of the variable.
+pub fn differentiate<N: Number>(
+	env: &Environment<N>,
+	derivative: Derivative<N>,
+) -> Result<Value<N>, Error> {
+	let mut dual_env = env.map_numbers(&Dual::constant)?;
+	let (result, unit) = match derivative {
+		Derivative::Expression { body, var } => {
+			let (at, unit) = match env.get(&var) {
+				Some(Variable::Var(value)) => seed(value.clone())?,
+				_ => return Err(Error::Runtime("Expected a variable!")),
+			};
+			dual_env.assign_var(var, at);
+			let tokens = body
+				.into_iter()
+				.map(|token| token.map(Dual::constant))
+				.collect::<Result<_, _>>()?;
+			let result =
+				Parser::new(Cursor::with_eof(tokens), &mut dual_env).evaluate_expression()?;
+			(result, unit)
+		}
+		Derivative::Function { name, at } => {
+			let (at, unit) = seed(at)?;
+			let result =
+				Parser::new(Cursor::new(Vec::new()), &mut dual_env).call(&name, vec![at])?;
+			(result, unit)
+		}
+	};
+
+	let result = match result {
+		Value::Number(_) | Value::Quantity(_) | Value::List(_) | Value::Matrix(_) => {
+			result.map_numbers(&|dual| Ok(dual.derivative), &|dual| Ok(dual.value))?
+		}
+		_ => return Err(Error::Runtime("Only numbers can be differentiated!")),
+	};
+	if unit.is_empty() {
+		return Ok(result);
+	}
+	let one = N::parse("1", env.context())?;
+	result.div(Value::Quantity(Quantity { value: one, unit }))
+}
+
+/// Creates the variable of the derivative, which has the derivative `1`.
+fn seed<N: Number>(value: Value<N>) -> Result<(Value<Dual<N>>, Unit<N>), Error> {
+	match value {
+		Value::Number(number) => Ok((
+			Value::Number(Dual::variable(number)?),
+			Unit::dimensionless(),
+		)),
+		Value::Quantity(quantity) => {
+			let value = Value::Quantity(Quantity {
+				value: Dual::variable(quantity.value)?,
+				unit: quantity.unit.clone().map_numbers(&Dual::constant)?,
+			});
+			Ok((value, quantity.unit))
+		}
+		_ => Err(Error::Runtime(
+			"Derivatives are only defined for numbers and quantities!",
+		)),
+	}
+}
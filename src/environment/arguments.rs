@@ -1,6 +1,39 @@
-use crate::Error;
+use crate::{Error, Value};
 
 /// Used for getting arguments for function calls.
-pub trait Arguments {
-	fn get_next_arg(&mut self) -> Result<f32, Error>;
+pub trait Arguments<N> {
+	fn get_next_arg(&mut self) -> Result<Value<N>, Error>;
+
+	/// Returns all remaining arguments for variadic functions,
+	/// without brackets (e.g. `mean v`) this is only the next argument.
+	fn get_remaining_args(&mut self) -> Result<Vec<Value<N>>, Error> {
+		Ok(vec![self.get_next_arg()?])
+	}
+}
+
+/// Arguments which are already evaluated, e.g. the bracketed arguments of `f(1, 2)`.
+pub struct ArgumentList<N>(std::vec::IntoIter<Value<N>>);
+
+impl<N> ArgumentList<N> {
+	pub fn new(args: Vec<Value<N>>) -> Self {
+		Self(args.into_iter())
+	}
+
+	/// Checks that the called function used all arguments.
+	pub fn finish(mut self) -> Result<(), Error> {
+		match self.0.next() {
+			Some(_) => Err(Error::Runtime("Too many arguments!")),
+			None => Ok(()),
+		}
+	}
+}
+
+impl<N> Arguments<N> for ArgumentList<N> {
+	fn get_next_arg(&mut self) -> Result<Value<N>, Error> {
+		self.0.next().ok_or(Error::Runtime("Too few arguments!"))
+	}
+
+	fn get_remaining_args(&mut self) -> Result<Vec<Value<N>>, Error> {
+		Ok(self.0.by_ref().collect())
+	}
 }
@@ -1,11 +1,24 @@
-use crate::{Arguments, Error};
+use crate::{Arguments, Error, Number, Value};
 
-pub trait FromArguments: Sized {
-	fn from_args(args: &mut dyn Arguments) -> Result<Self, Error>;
+pub trait FromArguments<N>: Sized {
+	fn from_args(args: &mut dyn Arguments<N>) -> Result<Self, Error>;
 }
 
-impl FromArguments for f32 {
-	fn from_args(args: &mut dyn Arguments) -> Result<Self, Error> {
+impl<N: Number> FromArguments<N> for N {
+	fn from_args(args: &mut dyn Arguments<N>) -> Result<Self, Error> {
+		args.get_next_arg()?.into_number()
+	}
+}
+
+impl<N: Number> FromArguments<N> for Value<N> {
+	fn from_args(args: &mut dyn Arguments<N>) -> Result<Self, Error> {
 		args.get_next_arg()
 	}
 }
+
+/// Collects all remaining arguments, so it must be the last parameter of a function.
+impl<N: Number> FromArguments<N> for Vec<Value<N>> {
+	fn from_args(args: &mut dyn Arguments<N>) -> Result<Self, Error> {
+		args.get_remaining_args()
+	}
+}
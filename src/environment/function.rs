@@ -1,6 +1,6 @@
-use crate::{Arguments, Error};
+use crate::{Arguments, Error, Value};
 
-pub trait Function {
-	fn clone_box(&self) -> Box<dyn Function>;
-	fn call_with_args(&self, args: &mut dyn Arguments) -> Result<f32, Error>;
+pub trait Function<N> {
+	fn clone_box(&self) -> Box<dyn Function<N>>;
+	fn call_with_args(&self, args: &mut dyn Arguments<N>) -> Result<Value<N>, Error>;
 }
@@ -1,24 +1,31 @@
-use crate::Error;
+use crate::{Error, Number, Value};
 
 use super::{Arguments, FromArguments, IntoResult};
 
-pub trait Handler<T> {
-	fn call(&self, args: &mut dyn Arguments) -> Result<f32, Error>;
+pub trait Handler<N, T> {
+	fn call(&self, args: &mut dyn Arguments<N>) -> Result<Value<N>, Error>;
 }
 
+impl_handler!();
 impl_handler!(T1);
 impl_handler!(T1, T2);
 impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
 
 macro_rules! impl_handler {
 	($($ty:ident),*) => {
-		impl<F, $($ty,)* R> Handler<($($ty,)*)> for F
+		impl<F, N, $($ty,)* R> Handler<N, ($($ty,)*)> for F
 		where
 			F: Fn($($ty,)*) -> R,
-			$($ty: FromArguments,)*
-			R: IntoResult
+			N: Number,
+			$($ty: FromArguments<N>,)*
+			R: IntoResult<N>
 		{
-			fn call(&self, args: &mut dyn Arguments) -> Result<f32, Error> {
+			// functions without arguments don't use them
+			#[allow(unused_variables)]
+			fn call(&self, args: &mut dyn Arguments<N>) -> Result<Value<N>, Error> {
 				(self)($($ty::from_args(args)?,)*).into_result()
 			}
 		}
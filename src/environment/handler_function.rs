@@ -1,13 +1,15 @@
-use crate::Error;
+use crate::{Error, Number, Value};
 
 use super::{Arguments, Function};
 
-pub struct HandlerFunction<H: Clone> {
+type Call<H, N> = fn(&H, &mut dyn Arguments<N>) -> Result<Value<N>, Error>;
+
+pub struct HandlerFunction<H: Clone, N> {
 	pub handler: H,
-	pub call: fn(&H, &mut dyn Arguments) -> Result<f32, Error>,
+	pub call: Call<H, N>,
 }
 
-impl<H: Clone> Clone for HandlerFunction<H> {
+impl<H: Clone, N> Clone for HandlerFunction<H, N> {
 	fn clone(&self) -> Self {
 		Self {
 			handler: self.handler.clone(),
@@ -16,15 +18,16 @@ impl<H: Clone> Clone for HandlerFunction<H> {
 	}
 }
 
-impl<H> Function for HandlerFunction<H>
+impl<H, N> Function<N> for HandlerFunction<H, N>
 where
 	H: Clone + 'static,
+	N: Number,
 {
-	fn clone_box(&self) -> Box<dyn Function> {
+	fn clone_box(&self) -> Box<dyn Function<N>> {
 		Box::new(self.clone())
 	}
 
-	fn call_with_args(&self, args: &mut dyn Arguments) -> Result<f32, Error> {
+	fn call_with_args(&self, args: &mut dyn Arguments<N>) -> Result<Value<N>, Error> {
 		(self.call)(&self.handler, args)
 	}
 }
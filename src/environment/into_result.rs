@@ -1,17 +1,29 @@
-use crate::Error;
+use crate::{Error, Number, Value};
 
-pub trait IntoResult {
-	fn into_result(self) -> Result<f32, Error>;
+pub trait IntoResult<N> {
+	fn into_result(self) -> Result<Value<N>, Error>;
 }
 
-impl IntoResult for f32 {
-	fn into_result(self) -> Result<f32, Error> {
+impl<N: Number> IntoResult<N> for N {
+	fn into_result(self) -> Result<Value<N>, Error> {
+		Ok(Value::Number(self))
+	}
+}
+
+impl<N: Number> IntoResult<N> for Result<N, Error> {
+	fn into_result(self) -> Result<Value<N>, Error> {
+		self.map(Value::Number)
+	}
+}
+
+impl<N: Number> IntoResult<N> for Value<N> {
+	fn into_result(self) -> Result<Value<N>, Error> {
 		Ok(self)
 	}
 }
 
-impl IntoResult for Result<f32, Error> {
-	fn into_result(self) -> Result<f32, Error> {
+impl<N: Number> IntoResult<N> for Result<Value<N>, Error> {
+	fn into_result(self) -> Result<Value<N>, Error> {
 		self
 	}
 }
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
-use std::f32::consts::{E, PI};
+
+use crate::{
+	det, dot, identity, inverse, len, max, mean, median, min, mode, norm, now, parse_factor,
+	percentile, product, stdev, sum, today, transpose, variance, Differentiate, Dimension, Error,
+	Number, Unit, UnitDef, Value, WordType, PREFIXES, STD_TEMPERATURE_UNITS, STD_UNITS,
+};
 
 pub mod arguments;
 pub use arguments::*;
@@ -16,32 +21,57 @@ use handler_function::*;
 mod into_result;
 use into_result::*;
 
-pub struct Environment {
-	variables: HashMap<String, Variable>,
-	last_result: Option<f32>,
+/// Default tolerance of numerical methods like `integrate`.
+pub const DEFAULT_TOLERANCE: f64 = 1e-10;
+
+pub struct Environment<N: Number> {
+	variables: HashMap<String, Variable<N>>,
+	units: HashMap<String, UnitDef<N>>,
+	last_result: Option<Value<N>>,
+	context: N::Context,
+	differentiate: Option<Differentiate<N>>,
+	tolerance: f64,
 }
 
-impl Default for Environment {
+impl<N: Number> Default for Environment<N> {
 	fn default() -> Self {
 		Self::new()
 	}
 }
 
-impl Environment {
-	pub fn new() -> Environment {
+impl<N: Number> Environment<N> {
+	pub fn new() -> Environment<N> {
+		Self::with_context(N::Context::default())
+	}
+
+	pub fn with_context(context: N::Context) -> Environment<N> {
 		Environment {
 			variables: HashMap::new(),
+			units: HashMap::new(),
 			last_result: None,
+			context,
+			differentiate: None,
+			tolerance: DEFAULT_TOLERANCE,
 		}
 	}
 
-	pub fn assign_var(&mut self, key: impl Into<String>, value: f32) {
-		self.variables.insert(key.into(), Variable::Var(value));
+	pub fn context(&self) -> &N::Context {
+		&self.context
+	}
+
+	pub fn assign_var(&mut self, key: impl Into<String>, value: impl Into<Value<N>>) {
+		self.variables
+			.insert(key.into(), Variable::Var(value.into()));
+	}
+
+	/// Units are stored separately from variables, so `m` can be used as variable and unit.
+	pub fn assign_unit(&mut self, symbol: impl Into<String>, def: UnitDef<N>) {
+		self.units.insert(symbol.into(), def);
 	}
 
 	pub fn assign_fn<H, T>(&mut self, id: impl Into<String>, fun: H)
 	where
-		H: Handler<T> + Clone + 'static,
+		H: Handler<N, T> + Clone + 'static,
 		T: 'static,
 	{
 		let hf = HandlerFunction {
@@ -51,34 +81,217 @@ impl Environment {
 		self.variables.insert(id.into(), Variable::Fn(Box::new(hf)));
 	}
 
-	pub fn get(&self, key: &str) -> Option<&Variable> {
-		return self.variables.get(key);
+	pub fn get(&self, key: &str) -> Option<&Variable<N>> {
+		self.variables.get(key)
+	}
+
+	/// Replaces a variable and returns the previous one, `None` removes the variable.
+	pub fn replace(&mut self, key: &str, var: Option<Variable<N>>) -> Option<Variable<N>> {
+		match var {
+			Some(var) => self.variables.insert(key.to_owned(), var),
+			None => self.variables.remove(key),
+		}
 	}
 
-	pub fn get_last_result(&self) -> Option<f32> {
-		self.last_result
+	/// Looks up a unit, SI prefixes are applied on prefixable units, e.g. `km`.
+	pub fn get_unit(&self, symbol: &str) -> Option<Unit<N>> {
+		if let Some(def) = self.units.get(symbol) {
+			return Some(Unit::new(symbol, def.clone()));
+		}
+		PREFIXES.iter().find_map(|(prefix, factor)| {
+			let def = self.units.get(symbol.strip_prefix(prefix)?)?;
+			if !def.prefixable {
+				return None;
+			}
+			let factor = N::parse(factor, &self.context)
+				.and_then(|factor| factor.mul(def.factor.clone()))
+				.ok()?;
+			Some(Unit::new(
+				symbol,
+				UnitDef {
+					factor,
+					..def.clone()
+				},
+			))
+		})
 	}
 
-	pub fn set_last_result(&mut self, value: f32) -> f32 {
-		self.last_result = Some(value);
+	pub fn get_last_result(&self) -> Option<Value<N>> {
+		self.last_result.clone()
+	}
+
+	pub fn set_last_result(&mut self, value: Value<N>) -> Value<N> {
+		self.last_result = Some(value.clone());
 		value
 	}
 
+	/// Sets the implementation of `diff`, which isn't part of the generic environment
+	/// as it evaluates expressions with dual numbers of the number type.
+	pub fn set_differentiate(&mut self, differentiate: Differentiate<N>) {
+		self.differentiate = Some(differentiate);
+	}
+
+	pub fn differentiate(&self) -> Option<Differentiate<N>> {
+		self.differentiate
+	}
+
+	/// Sets the tolerance of numerical methods like `integrate`.
+	pub fn set_tolerance(&mut self, tolerance: f64) {
+		self.tolerance = tolerance;
+	}
+
+	pub fn tolerance(&self) -> f64 {
+		self.tolerance
+	}
+
+	/// Creates an environment of another number type with the converted variables and units.
+	///
+	/// The std lib functions are defined again, other native functions are only placeholders
+	/// resulting in an error, because they can't be converted.
+	pub fn map_numbers<M>(
+		&self,
+		f: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<Environment<M>, Error>
+	where
+		M: Number<Context = N::Context>,
+	{
+		let mut env = Environment::with_context(self.context.clone());
+		env.tolerance = self.tolerance;
+		env.init_std_functions();
+		for (id, var) in &self.variables {
+			match var {
+				Variable::Var(value) => {
+					env.assign_var(id.clone(), value.clone().map_numbers(f, f)?)
+				}
+				Variable::Fn(_) if env.get(id).is_none() => env.assign_fn(id.clone(), || {
+					Err::<Value<M>, _>(Error::Runtime("Native functions can't be converted!"))
+				}),
+				Variable::Fn(_) => (),
+			}
+		}
+		for (symbol, def) in &self.units {
+			let def = UnitDef {
+				factor: f(def.factor.clone())?,
+				dimension: def.dimension,
+				prefixable: def.prefixable,
+				offset: def.offset.clone().map(f).transpose()?,
+			};
+			env.assign_unit(symbol.clone(), def);
+		}
+		env.last_result = match &self.last_result {
+			Some(value) => Some(value.clone().map_numbers(f, f)?),
+			None => None,
+		};
+		Ok(env)
+	}
+
 	pub fn init_std(&mut self) {
-		self.assign_var("pi", PI);
-		self.assign_var("e", E);
-
-		self.assign_fn("sin", f32::sin);
-		self.assign_fn("asin", f32::asin);
-		self.assign_fn("cos", f32::cos);
-		self.assign_fn("acos", f32::acos);
-		self.assign_fn("tan", f32::tan);
-		self.assign_fn("atan", f32::atan);
-		self.assign_fn("r2d", f32::to_degrees);
-		self.assign_fn("d2r", f32::to_radians);
+		// constants which cannot be represented by the number type are not defined
+		if let Ok(pi) = N::pi(&self.context) {
+			self.assign_var("pi", pi);
+		}
+		if let Ok(e) = N::e(&self.context) {
+			self.assign_var("e", e);
+		}
+		if let Ok(i) = N::imaginary_unit(&self.context) {
+			self.assign_var("i", i.clone());
+			self.assign_var("j", i);
+		}
+
+		// units which cannot be represented by the number type are not defined as well
+		for (symbol, factor, dimension, prefixable) in STD_UNITS {
+			if let Ok(factor) = parse_factor(factor, &self.context) {
+				let dimension = Dimension(dimension);
+				self.assign_unit(
+					symbol,
+					UnitDef {
+						factor,
+						dimension,
+						prefixable,
+						offset: None,
+					},
+				);
+			}
+		}
+		for (symbol, factor, offset) in STD_TEMPERATURE_UNITS {
+			let factor = parse_factor(factor, &self.context);
+			let offset = parse_factor(offset, &self.context);
+			if let (Ok(factor), Ok(offset)) = (factor, offset) {
+				self.assign_unit(
+					symbol,
+					UnitDef {
+						factor,
+						dimension: Dimension([0, 0, 0, 0, 1, 0, 0]),
+						prefixable: false,
+						offset: Some(offset),
+					},
+				);
+			}
+		}
+		let degree = N::pi(&self.context).and_then(|pi| pi.div(N::parse("180", &self.context)?));
+		if let Ok(factor) = degree {
+			self.assign_unit(
+				"deg",
+				UnitDef {
+					factor,
+					dimension: Dimension::default(),
+					prefixable: false,
+					offset: None,
+				},
+			);
+		}
+
+		self.init_std_functions();
+	}
+
+	pub(crate) fn init_std_functions(&mut self) {
+		self.assign_fn("sin", N::sin);
+		self.assign_fn("asin", N::asin);
+		self.assign_fn("cos", N::cos);
+		self.assign_fn("acos", N::acos);
+		self.assign_fn("tan", N::tan);
+		self.assign_fn("atan", N::atan);
+		self.assign_fn("r2d", N::to_degrees);
+		self.assign_fn("d2r", N::to_radians);
+		self.assign_fn("fact", N::factorial);
+		self.assign_fn("exp", N::exp);
+		self.assign_fn("ln", N::ln);
+		self.assign_fn("sqrt", N::sqrt);
+		self.assign_fn("abs", N::abs);
+		self.assign_fn("arg", N::arg);
+		self.assign_fn("re", N::re);
+		self.assign_fn("im", N::im);
+		self.assign_fn("conj", N::conj);
+		self.assign_fn("polar", N::from_polar);
+		self.assign_fn("interval", N::interval);
+		for ty in WordType::ALL {
+			self.assign_fn(ty.name(), move |x: N| x.cast(ty));
+		}
+		self.assign_fn("sum", sum);
+		self.assign_fn("product", product);
+		self.assign_fn("mean", mean);
+		self.assign_fn("median", median);
+		self.assign_fn("mode", mode);
+		self.assign_fn("variance", variance);
+		self.assign_fn("stdev", stdev);
+		self.assign_fn("percentile", percentile);
+		self.assign_fn("min", min);
+		self.assign_fn("max", max);
+		self.assign_fn("len", len);
+		self.assign_fn("dot", dot);
+		self.assign_fn("norm", norm);
+		self.assign_fn("transpose", transpose);
+		self.assign_fn("det", det);
+		self.assign_fn("inverse", inverse);
+		self.assign_fn("identity", identity);
+		self.assign_fn("today", today);
+		self.assign_fn("now", now);
 
 		#[cfg(test)]
-		self.assign_fn("test", |a: f32| a / 2.0);
+		self.assign_fn("test", |a: N| {
+			let two = N::from_f64(2.0, &a.context())?;
+			a.div(two)
+		});
 	}
 }
 
@@ -88,7 +301,7 @@ mod tests {
 
 	#[test]
 	fn test_01_assignment() {
-		let mut env = Environment::new();
+		let mut env = Environment::<f64>::new();
 		env.assign_var("var1", 34.5);
 		match env.get("var1") {
 			Some(Variable::Var(val)) => assert_eq!(*val, 34.5),
@@ -98,7 +311,7 @@ mod tests {
 
 	#[test]
 	fn test_02_get_undefined() {
-		let env = Environment::new();
+		let env = Environment::<f64>::new();
 		if env.get("xyz").is_some() {
 			panic!();
 		}
@@ -106,10 +319,10 @@ mod tests {
 
 	#[test]
 	fn test_03_init() {
-		let mut env = Environment::new();
+		let mut env = Environment::<f64>::new();
 		env.init_std();
 		match env.get("pi") {
-			Some(Variable::Var(val)) => assert_eq!(*val, PI),
+			Some(Variable::Var(val)) => assert_eq!(*val, std::f64::consts::PI),
 			_ => panic!(),
 		}
 
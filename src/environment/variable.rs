@@ -1,6 +1,16 @@
 use super::Function;
+use crate::Value;
 
-pub enum Variable {
-	Var(f32),
-	Fn(Box<dyn Function>),
+pub enum Variable<N> {
+	Var(Value<N>),
+	Fn(Box<dyn Function<N>>),
+}
+
+impl<N: Clone> Clone for Variable<N> {
+	fn clone(&self) -> Self {
+		match self {
+			Self::Var(value) => Self::Var(value.clone()),
+			Self::Fn(fun) => Self::Fn(fun.clone_box()),
+		}
+	}
 }
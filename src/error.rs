@@ -8,6 +8,11 @@ pub enum Error {
 		start: usize,
 		end: usize,
 	},
+	InvalidNumber {
+		literal: String,
+		start: usize,
+		end: usize,
+	},
 	UnexpectedEndOfInput,
 	Runtime(/* message: */ &'static str),
 	VariableNotFound {
@@ -15,6 +20,56 @@ pub enum Error {
 		start: usize,
 		end: usize,
 	},
+	IntervalContainsZero {
+		lo: f64,
+		hi: f64,
+	},
+	DimensionMismatch {
+		left: String,
+		right: String,
+		start: usize,
+		end: usize,
+	},
+	UnitNotFound {
+		unit: String,
+		start: usize,
+		end: usize,
+	},
+	ShapeMismatch {
+		/// Length of a list or rows and columns of a matrix.
+		left: Vec<usize>,
+		right: Vec<usize>,
+		start: usize,
+		end: usize,
+	},
+	NoConvergence {
+		iterations: usize,
+		/// Last approximation of the numerical method, e.g. the root for `solve`.
+		x: Vec<f64>,
+		/// Remaining error of the approximation, e.g. the function value for `solve`.
+		residual: f64,
+	},
+}
+
+impl Error {
+	/// Sets the position of errors which are raised by operators or function calls.
+	pub(crate) fn with_span(self, start: usize, end: usize) -> Self {
+		match self {
+			Self::DimensionMismatch { left, right, .. } => Self::DimensionMismatch {
+				left,
+				right,
+				start,
+				end,
+			},
+			Self::ShapeMismatch { left, right, .. } => Self::ShapeMismatch {
+				left,
+				right,
+				start,
+				end,
+			},
+			error => error,
+		}
+	}
 }
 
 impl std::fmt::Display for Error {
@@ -25,9 +80,50 @@ impl std::fmt::Display for Error {
 			Self::UnexpectedToken { token, .. } => {
 				write!(f, "Unexpected token `{}` found!", token)
 			}
+			Self::InvalidNumber { literal, .. } => {
+				write!(f, "Invalid number literal `{}` found!", literal)
+			}
 			Self::Runtime(msg) => write!(f, "{}", msg),
 			Self::VariableNotFound { var, .. } => write!(f, "Variable `{}` not found!", var),
 			Self::UnexpectedEndOfInput => write!(f, "Unexpected end of input!"),
+			Self::IntervalContainsZero { lo, hi } => {
+				write!(f, "Division by interval [{}, {}] containing 0!", lo, hi)
+			}
+			Self::DimensionMismatch { left, right, .. } => {
+				write!(f, "Dimension mismatch between `{}` and `{}`!", left, right)
+			}
+			Self::UnitNotFound { unit, .. } => write!(f, "Unit `{}` not found!", unit),
+			Self::ShapeMismatch { left, right, .. } => {
+				let shape = |shape: &[usize]| {
+					shape
+						.iter()
+						.map(|n| n.to_string())
+						.collect::<Vec<_>>()
+						.join("x")
+				};
+				write!(
+					f,
+					"Shape mismatch between `{}` and `{}`!",
+					shape(left),
+					shape(right)
+				)
+			}
+			Self::NoConvergence {
+				iterations,
+				x,
+				residual,
+			} => {
+				let x = x.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+				let x = match x.as_slice() {
+					[x] => x.clone(),
+					x => format!("[{}]", x.join(", ")),
+				};
+				write!(
+					f,
+					"No convergence after {} iterations, last approximation {} with residual {}!",
+					iterations, x, residual
+				)
+			}
 		}
 	}
 }
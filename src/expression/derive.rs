@@ -0,0 +1,105 @@
+use crate::{Error, Expr, Number};
+
+impl<N: Number> Expr<N> {
+	/// Calculates the derivative for the variable, other variables are constants.
+	///
+	/// The result isn't simplified, e.g. `x**2` results in `2*x**(2 - 1)*1`.
+	pub(crate) fn derive(&self, var: &str, ctx: &N::Context) -> Result<Self, Error> {
+		let derive = |expr: &Self| expr.derive(var, ctx);
+		Ok(match self {
+			Self::Number(_) => Self::number("0", ctx)?,
+			Self::Variable(id) if id == var => Self::number("1", ctx)?,
+			Self::Variable(_) => Self::number("0", ctx)?,
+			Self::Neg(a) => derive(a)?.neg(),
+			Self::Add(a, b) => derive(a)?.add(derive(b)?),
+			Self::Sub(a, b) => derive(a)?.sub(derive(b)?),
+			Self::Mul(a, b) => derive(a)?
+				.mul(*b.clone())
+				.add(a.as_ref().clone().mul(derive(b)?)),
+			Self::Div(a, b) if !b.contains(var) => derive(a)?.div(*b.clone()),
+			// (a / b)' = (a' * b - a * b') / b ** 2
+			Self::Div(a, b) => derive(a)?
+				.mul(*b.clone())
+				.sub(a.as_ref().clone().mul(derive(b)?))
+				.div(b.as_ref().clone().pow(Self::number("2", ctx)?)),
+			// (a ** b)' = b * a ** (b - 1) * a'
+			Self::Pow(a, b) if !b.contains(var) => {
+				let exponent = b.as_ref().clone().sub(Self::number("1", ctx)?);
+				b.as_ref()
+					.clone()
+					.mul(a.as_ref().clone().pow(exponent))
+					.mul(derive(a)?)
+			}
+			// (a ** b)' = a ** b * ln(a) * b'
+			Self::Pow(a, b) if !a.contains(var) => self
+				.clone()
+				.mul(Self::call("ln", *a.clone()))
+				.mul(derive(b)?),
+			// (a ** b)' = a ** b * (b' * ln(a) + b * a' / a)
+			Self::Pow(a, b) => {
+				let ln = derive(b)?.mul(Self::call("ln", *a.clone()));
+				let quotient = b.as_ref().clone().mul(derive(a)?).div(*a.clone());
+				self.clone().mul(ln.add(quotient))
+			}
+			Self::Rem(..) => return Err(Error::Runtime("Remainders can't be differentiated!")),
+			Self::Call(name, args) => match args.as_slice() {
+				[arg] => chain(name, arg, ctx)?.mul(derive(arg)?),
+				_ => return Err(Error::Runtime("Function can't be differentiated!")),
+			},
+		})
+	}
+}
+
+/// Returns the derivative of a std function `f'(u)` for the chain rule.
+fn chain<N: Number>(name: &str, u: &Expr<N>, ctx: &N::Context) -> Result<Expr<N>, Error> {
+	let one = || Expr::number("1", ctx);
+	let square = || Ok::<_, Error>(u.clone().pow(Expr::number("2", ctx)?));
+	Ok(match name {
+		"sin" => Expr::call("cos", u.clone()),
+		"cos" => Expr::call("sin", u.clone()).neg(),
+		"tan" => one()?.div(Expr::call("cos", u.clone()).pow(Expr::number("2", ctx)?)),
+		"asin" => one()?.div(Expr::call("sqrt", one()?.sub(square()?))),
+		"acos" => one()?.div(Expr::call("sqrt", one()?.sub(square()?))).neg(),
+		"atan" => one()?.div(one()?.add(square()?)),
+		"exp" => Expr::call("exp", u.clone()),
+		"ln" => one()?.div(u.clone()),
+		"sqrt" => one()?.div(Expr::number("2", ctx)?.mul(Expr::call("sqrt", u.clone()))),
+		"r2d" | "d2r" => Expr::call(name, one()?),
+		_ => return Err(Error::Runtime("Function can't be differentiated!")),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{parse, Environment, Error};
+
+	fn derive(input: &str) -> Result<String, Error> {
+		let mut env = Environment::<f64>::new();
+		env.init_std();
+		let expr = parse(input, &env)?.into_number()?;
+		Ok(expr.derive("x", &())?.simplify(&())?.to_string())
+	}
+
+	#[test]
+	fn test_01_rules() {
+		assert_eq!(derive("3*x + y").unwrap(), "3");
+		assert_eq!(derive("x**4").unwrap(), "4*x**3");
+		assert_eq!(derive("2**x").unwrap(), "2**x*ln 2");
+		assert_eq!(derive("e**x").unwrap(), "e**x");
+		assert_eq!(derive("x/(x + 1)").unwrap(), "1/(x + 1)**2");
+		assert_eq!(derive("-(x**2)").unwrap(), "-2*x");
+	}
+
+	#[test]
+	fn test_02_chain_rule() {
+		assert_eq!(derive("cos(3*x)").unwrap(), "-3*sin(3*x)");
+		assert_eq!(derive("ln(x**2 + 1)").unwrap(), "2*x/(x**2 + 1)");
+		assert_eq!(derive("sqrt x").unwrap(), "1/(2*sqrt x)");
+		assert_eq!(derive("tan x").unwrap(), "1/(cos x)**2");
+		assert_eq!(derive("asin x").unwrap(), "1/sqrt(1 - x**2)");
+		assert_eq!(
+			derive("fact x"),
+			Err(Error::Runtime("Function can't be differentiated!"))
+		);
+	}
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+use crate::{Error, Number};
+
+mod derive;
+mod number;
+mod parse;
+pub(crate) use parse::*;
+mod simplify;
+
+/// Expression tree used for symbolic manipulations like derivatives.
+///
+/// The tree is built by evaluating the input with expressions as numbers,
+/// so it supports arithmetic on numbers, variables and function calls.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr<N> {
+	Number(N),
+	Variable(String),
+	Neg(Box<Expr<N>>),
+	Add(Box<Expr<N>>, Box<Expr<N>>),
+	Sub(Box<Expr<N>>, Box<Expr<N>>),
+	Mul(Box<Expr<N>>, Box<Expr<N>>),
+	Div(Box<Expr<N>>, Box<Expr<N>>),
+	Rem(Box<Expr<N>>, Box<Expr<N>>),
+	Pow(Box<Expr<N>>, Box<Expr<N>>),
+	Call(String, Vec<Expr<N>>),
+}
+
+impl<N: Number> Expr<N> {
+	fn number(value: &str, ctx: &N::Context) -> Result<Self, Error> {
+		Ok(Self::Number(N::parse(value, ctx)?))
+	}
+
+	fn neg(self) -> Self {
+		Self::Neg(Box::new(self))
+	}
+
+	fn add(self, rhs: Self) -> Self {
+		Self::Add(Box::new(self), Box::new(rhs))
+	}
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::Sub(Box::new(self), Box::new(rhs))
+	}
+
+	fn mul(self, rhs: Self) -> Self {
+		Self::Mul(Box::new(self), Box::new(rhs))
+	}
+
+	fn div(self, rhs: Self) -> Self {
+		Self::Div(Box::new(self), Box::new(rhs))
+	}
+
+	fn pow(self, rhs: Self) -> Self {
+		Self::Pow(Box::new(self), Box::new(rhs))
+	}
+
+	fn call(name: &str, arg: Self) -> Self {
+		Self::Call(name.to_owned(), vec![arg])
+	}
+
+	/// Checks if the expression depends on the variable.
+	fn contains(&self, var: &str) -> bool {
+		match self {
+			Self::Number(_) => false,
+			Self::Variable(id) => id == var,
+			Self::Neg(a) => a.contains(var),
+			Self::Add(a, b)
+			| Self::Sub(a, b)
+			| Self::Mul(a, b)
+			| Self::Div(a, b)
+			| Self::Rem(a, b)
+			| Self::Pow(a, b) => a.contains(var) || b.contains(var),
+			Self::Call(_, args) => args.iter().any(|arg| arg.contains(var)),
+		}
+	}
+
+	fn first_number(&self) -> Option<&N> {
+		match self {
+			Self::Number(number) => Some(number),
+			Self::Variable(_) => None,
+			Self::Neg(a) => a.first_number(),
+			Self::Add(a, b)
+			| Self::Sub(a, b)
+			| Self::Mul(a, b)
+			| Self::Div(a, b)
+			| Self::Rem(a, b)
+			| Self::Pow(a, b) => a.first_number().or_else(|| b.first_number()),
+			Self::Call(_, args) => args.iter().find_map(Self::first_number),
+		}
+	}
+
+	/// Binding strength of the expression, used for placing brackets when formatting it.
+	fn precedence(&self) -> u8 {
+		match self {
+			Self::Add(..) | Self::Sub(..) => 1,
+			Self::Mul(..) | Self::Div(..) | Self::Rem(..) => 2,
+			Self::Pow(..) => 3,
+			Self::Neg(_) | Self::Call(..) => 4,
+			Self::Number(number) => {
+				let number = number.to_string();
+				let unsigned = number.strip_prefix('-').unwrap_or(&number);
+				if unsigned.contains(['+', '-']) {
+					1
+				} else if unsigned.contains('/') {
+					2
+				} else if unsigned.len() < number.len() {
+					4
+				} else {
+					5
+				}
+			}
+			Self::Variable(_) => 5,
+		}
+	}
+
+	/// Formats the expression, which is put in brackets if it binds weaker than required.
+	fn fmt_operand(&self, f: &mut fmt::Formatter, precedence: u8) -> fmt::Result {
+		if self.precedence() < precedence {
+			write!(f, "({})", self)
+		} else {
+			write!(f, "{}", self)
+		}
+	}
+}
+
+/// Formats the expression in the syntax of the calculator, e.g. `2*x*sin x + x**2*cos x`.
+///
+/// Signs bind stronger than powers, so `-(x**2)` and `(-x)**2` keep their brackets.
+impl<N: Number> fmt::Display for Expr<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Number(number) => write!(f, "{}", number),
+			Self::Variable(id) => write!(f, "{}", id),
+			Self::Neg(a) => {
+				write!(f, "-")?;
+				a.fmt_operand(f, 4)
+			}
+			Self::Add(a, b) => {
+				a.fmt_operand(f, 1)?;
+				write!(f, " + ")?;
+				b.fmt_operand(f, 1)
+			}
+			Self::Sub(a, b) => {
+				a.fmt_operand(f, 1)?;
+				write!(f, " - ")?;
+				b.fmt_operand(f, 2)
+			}
+			Self::Mul(a, b) => {
+				a.fmt_operand(f, 2)?;
+				write!(f, "*")?;
+				b.fmt_operand(f, 2)
+			}
+			Self::Div(a, b) => {
+				a.fmt_operand(f, 2)?;
+				write!(f, "/")?;
+				b.fmt_operand(f, 3)
+			}
+			Self::Rem(a, b) => {
+				a.fmt_operand(f, 2)?;
+				write!(f, " % ")?;
+				b.fmt_operand(f, 3)
+			}
+			Self::Pow(a, b) => {
+				a.fmt_operand(f, 5)?;
+				write!(f, "**")?;
+				b.fmt_operand(f, 4)
+			}
+			Self::Call(name, args) => match args.as_slice() {
+				[arg] if arg.precedence() == 5 => write!(f, "{} {}", name, arg),
+				_ => {
+					write!(f, "{}(", name)?;
+					for (i, arg) in args.iter().enumerate() {
+						if i > 0 {
+							write!(f, ", ")?;
+						}
+						write!(f, "{}", arg)?;
+					}
+					write!(f, ")")
+				}
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Environment;
+
+	fn parse_f64(input: &str) -> Expr<f64> {
+		let mut env = Environment::<f64>::new();
+		env.init_std();
+		parse(input, &env).unwrap().into_number().unwrap()
+	}
+
+	#[test]
+	fn test_01_display() {
+		for input in [
+			"x + 2*y",
+			"(x + 1)*(x - 1)",
+			"a - (b + c)",
+			"a/(b*c)",
+			"(x**2)**3",
+			"(-x)**2",
+			"-(x**2)",
+			"(sin x)**2",
+			"sin(2*x)",
+			"x**-1",
+			"-sin x",
+		] {
+			assert_eq!(parse_f64(input).to_string(), input);
+		}
+		assert_eq!(parse_f64("sin(x)").to_string(), "sin x");
+		assert_eq!(parse_f64("((x))*(2)").to_string(), "x*2");
+		assert_eq!(parse_f64("(a + b) + (c - d)").to_string(), "a + b + c - d");
+	}
+}
@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+
+use crate::{Error, Expr, Number, NumberFormat, WordType};
+
+/// Expressions are numbers for the parser, so operators and functions build the tree
+/// with the same grammar as the evaluation, e.g. `x + 1` results in `Add(x, 1)`.
+impl<N: Number> Number for Expr<N> {
+	type Context = N::Context;
+
+	fn parse(src: &str, ctx: &Self::Context) -> Result<Self, Error> {
+		Ok(Self::Number(N::parse(src, ctx)?))
+	}
+
+	fn from_f64(value: f64, ctx: &Self::Context) -> Result<Self, Error> {
+		Ok(Self::Number(N::from_f64(value, ctx)?))
+	}
+
+	fn to_f64(&self) -> f64 {
+		match self {
+			Self::Number(number) => number.to_f64(),
+			_ => f64::NAN,
+		}
+	}
+
+	/// Expressions don't store the context, so it is taken from their first number.
+	fn context(&self) -> Self::Context {
+		self.first_number()
+			.map_or_else(N::Context::default, N::context)
+	}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Expr::add(self, rhs))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Expr::sub(self, rhs))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Expr::mul(self, rhs))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Expr::div(self, rhs))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::Rem(Box::new(self), Box::new(rhs)))
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Expr::pow(self, rhs))
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		let one = Self::number("1", &self.context())?;
+		Ok(Expr::pow(self, Expr::div(one, rhs)))
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(Expr::neg(self))
+	}
+
+	fn compare(&self, other: &Self) -> Result<Ordering, Error> {
+		match (self, other) {
+			(Self::Number(a), Self::Number(b)) => a.compare(b),
+			_ => Err(Error::Runtime("Expressions can't be compared!")),
+		}
+	}
+
+	fn to_integer(&self) -> Option<BigInt> {
+		match self {
+			Self::Number(number) => number.to_integer(),
+			_ => None,
+		}
+	}
+
+	fn from_integer(value: BigInt, ctx: &Self::Context) -> Result<Self, Error> {
+		Ok(Self::Number(N::from_integer(value, ctx)?))
+	}
+
+	fn cast(self, ty: WordType) -> Result<Self, Error> {
+		Ok(Self::call(ty.name(), self))
+	}
+
+	fn pi(_: &Self::Context) -> Result<Self, Error> {
+		Ok(Self::Variable("pi".to_owned()))
+	}
+
+	fn e(_: &Self::Context) -> Result<Self, Error> {
+		Ok(Self::Variable("e".to_owned()))
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		Ok(Self::call("sin", self))
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		Ok(Self::call("asin", self))
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		Ok(Self::call("cos", self))
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		Ok(Self::call("acos", self))
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		Ok(Self::call("tan", self))
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		Ok(Self::call("atan", self))
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		Ok(Self::call("r2d", self))
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		Ok(Self::call("d2r", self))
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		Ok(Self::call("exp", self))
+	}
+
+	fn ln(self) -> Result<Self, Error> {
+		Ok(Self::call("ln", self))
+	}
+
+	fn sqrt(self) -> Result<Self, Error> {
+		Ok(Self::call("sqrt", self))
+	}
+
+	fn abs(self) -> Result<Self, Error> {
+		Ok(Self::call("abs", self))
+	}
+
+	fn arg(self) -> Result<Self, Error> {
+		Ok(Self::call("arg", self))
+	}
+
+	fn re(self) -> Result<Self, Error> {
+		Ok(Self::call("re", self))
+	}
+
+	fn im(self) -> Result<Self, Error> {
+		Ok(Self::call("im", self))
+	}
+
+	fn conj(self) -> Result<Self, Error> {
+		Ok(Self::call("conj", self))
+	}
+
+	fn from_polar(r: Self, phi: Self) -> Result<Self, Error> {
+		Ok(Self::Call("polar".to_owned(), vec![r, phi]))
+	}
+
+	fn interval(lo: Self, hi: Self) -> Result<Self, Error> {
+		Ok(Self::Call("interval".to_owned(), vec![lo, hi]))
+	}
+
+	fn format(&self, _: &NumberFormat) -> Result<String, Error> {
+		Ok(self.to_string())
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		Ok(Self::call("fact", self))
+	}
+}
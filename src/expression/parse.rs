@@ -0,0 +1,106 @@
+use crate::{lexer, Cursor, Environment, Error, Expr, Number, Parser, TokenValue, Value, Variable};
+
+/// Parses the input into expression trees by evaluating it with expressions as numbers,
+/// so the grammar is the same as for the evaluation, e.g. `6x**2` or `2 m + x cm`.
+///
+/// Identifiers which aren't functions are variables, so defined variables stay symbolic.
+/// User functions are inlined, native functions which aren't part of the std lib are kept as calls.
+pub(crate) fn parse<N: Number>(input: &str, env: &Environment<N>) -> Result<Value<Expr<N>>, Error> {
+	let tokens = lexer::tokenize::<Expr<N>>(input, env.context())?;
+	let mut expr_env = env.map_numbers(&|number| Ok(Expr::Number(number)))?;
+	let mut std_env = Environment::<N>::with_context(env.context().clone());
+	std_env.init_std_functions();
+
+	for token in &tokens {
+		let TokenValue::Identifier(id) = &token.value else {
+			continue;
+		};
+		match env.get(id) {
+			Some(Variable::Var(Value::Function(_) | Value::Polynomial(_))) => (),
+			Some(Variable::Fn(_)) if std_env.get(id).is_some() => (),
+			Some(Variable::Fn(_)) => {
+				let name = id.clone();
+				expr_env.assign_fn(id.clone(), move |args: Vec<Value<Expr<N>>>| {
+					let args = args
+						.into_iter()
+						.map(Value::into_number)
+						.collect::<Result<_, _>>()?;
+					Ok::<_, Error>(Expr::Call(name.clone(), args))
+				});
+			}
+			_ => expr_env.assign_var(id.clone(), Expr::Variable(id.clone())),
+		}
+	}
+
+	match Parser::new(Cursor::new(tokens), &mut expr_env).evaluate_term()? {
+		value @ (Value::Number(_) | Value::Quantity(_) | Value::List(_) | Value::Matrix(_)) => {
+			Ok(value)
+		}
+		_ => Err(Error::Runtime("Expected a numerical expression!")),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse_f64(input: &str) -> Result<Value<Expr<f64>>, Error> {
+		let mut env = Environment::<f64>::new();
+		env.init_std();
+		env.assign_var("a", 2.0);
+		parse(input, &env)
+	}
+
+	fn var(id: &str) -> Expr<f64> {
+		Expr::Variable(id.to_owned())
+	}
+
+	#[test]
+	fn test_01_precedence() {
+		assert_eq!(
+			parse_f64("x + 2 * y").unwrap(),
+			Value::Number(var("x").add(Expr::Number(2.0).mul(var("y"))))
+		);
+		// signs and function arguments are atomic like in the evaluation
+		assert_eq!(
+			parse_f64("-x**2").unwrap(),
+			Value::Number(var("x").neg().pow(Expr::Number(2.0)))
+		);
+		assert_eq!(
+			parse_f64("sin x**2").unwrap(),
+			Value::Number(Expr::call("sin", var("x")).pow(Expr::Number(2.0)))
+		);
+		assert_eq!(
+			parse_f64("sin(x + 1)").unwrap(),
+			Value::Number(Expr::call("sin", var("x").add(Expr::Number(1.0))))
+		);
+		assert_eq!(
+			parse_f64("x // 2").unwrap(),
+			Value::Number(var("x").pow(Expr::Number(1.0).div(Expr::Number(2.0))))
+		);
+	}
+
+	#[test]
+	fn test_02_grammar() {
+		assert_eq!(parse_f64("6x**2").unwrap().to_string(), "6*x**2");
+		assert_eq!(
+			parse_f64("x % 2 + 2 \\ a").unwrap().to_string(),
+			"x % 2 + a/2"
+		);
+		assert_eq!(parse_f64("[x, 3 ^ 5]").unwrap().to_string(), "[x, 6]");
+		assert_eq!(parse_f64("x * 3 km").unwrap().to_string(), "x*3 km");
+		assert_eq!(
+			parse_f64("x < 2"),
+			Err(Error::Runtime("Expressions can't be compared!"))
+		);
+		assert_eq!(
+			parse_f64("x = 2"),
+			Err(Error::UnexpectedToken {
+				token: "=".to_owned(),
+				start: 2,
+				end: 2
+			})
+		);
+		assert_eq!(parse_f64("(x + 2"), Err(Error::UnexpectedEndOfInput));
+	}
+}
@@ -0,0 +1,438 @@
+use std::mem;
+
+use crate::{Error, Expr, Number};
+
+impl<N: Number> Expr<N> {
+	/// Simplifies the expression bottom up, e.g. `x*1 + 0 + 2*x` results in `3*x`.
+	///
+	/// Constants are folded, like terms are collected and factors with the same base are combined into powers.
+	pub(crate) fn simplify(self, ctx: &N::Context) -> Result<Self, Error> {
+		Ok(match self {
+			Self::Neg(a) => a.simplify(ctx)?.negate(ctx)?,
+			Self::Add(a, b) => {
+				let mut sum = Sum::new(ctx)?;
+				sum.push(a.simplify(ctx)?, false)?;
+				sum.push(b.simplify(ctx)?, false)?;
+				sum.finish()?
+			}
+			Self::Sub(a, b) => {
+				let mut sum = Sum::new(ctx)?;
+				sum.push(a.simplify(ctx)?, false)?;
+				sum.push(b.simplify(ctx)?, true)?;
+				sum.finish()?
+			}
+			Self::Mul(a, b) => {
+				let mut product = Product::new(ctx)?;
+				product.push(a.simplify(ctx)?, false)?;
+				product.push(b.simplify(ctx)?, false)?;
+				product.finish()?
+			}
+			Self::Div(a, b) => {
+				let mut product = Product::new(ctx)?;
+				product.push(a.simplify(ctx)?, false)?;
+				product.push(b.simplify(ctx)?, true)?;
+				product.finish()?
+			}
+			Self::Pow(a, b) => match (a.simplify(ctx)?, b.simplify(ctx)?) {
+				(Self::Number(a), Self::Number(b)) if is_integer(&b) && !is_negative(&b) => {
+					Self::Number(a.pow(b)?)
+				}
+				(_, b) if b.is("0") => Self::number("1", ctx)?,
+				(a, b) if b.is("1") => a,
+				(a, _) if a.is("1") => Self::number("1", ctx)?,
+				// (a ** m) ** n = a ** (m * n) only holds for integer exponents n, e.g. not for `(x**2)**(1/2)`
+				(Self::Pow(a, m), Self::Number(n)) if is_integer(&n) => {
+					let exponent = m.mul(Self::Number(n)).simplify(ctx)?;
+					a.pow(exponent).simplify(ctx)?
+				}
+				(a, b) => a.pow(b),
+			},
+			Self::Rem(a, b) => match (a.simplify(ctx)?, b.simplify(ctx)?) {
+				(Self::Number(a), Self::Number(b)) => Self::Number(a.rem(b)?),
+				(a, b) => Self::Rem(Box::new(a), Box::new(b)),
+			},
+			Self::Call(name, args) => {
+				let args = args
+					.into_iter()
+					.map(|arg| arg.simplify(ctx))
+					.collect::<Result<Vec<_>, _>>()?;
+				match (name.as_str(), args.as_slice()) {
+					("ln", [Self::Variable(id)]) if id == "e" => Self::number("1", ctx)?,
+					_ => Self::Call(name, args),
+				}
+			}
+			expr => expr,
+		})
+	}
+
+	/// Checks if the expression is the given number.
+	fn is(&self, value: &str) -> bool {
+		matches!(self, Self::Number(number) if N::parse(value, &number.context()).is_ok_and(|value| *number == value))
+	}
+
+	/// Negates a simplified expression, signs are moved into numbers, e.g. `-(2*x)` results in `-2*x`.
+	fn negate(self, ctx: &N::Context) -> Result<Self, Error> {
+		Ok(match self {
+			Self::Number(a) => Self::Number(a.neg()?),
+			Self::Neg(a) => *a,
+			expr @ (Self::Mul(..) | Self::Div(..)) => {
+				let mut product = Product::new(ctx)?;
+				product.push(expr, false)?;
+				product.coefficient = product.coefficient.neg()?;
+				product.finish()?
+			}
+			expr => expr.neg(),
+		})
+	}
+
+	/// Checks if simplified products are equal up to the order of their factors, e.g. `x*y` and `y*x`.
+	fn is_permutation(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Div(a, b), Self::Div(c, d)) => a.is_permutation(c) && b.is_permutation(d),
+			(Self::Mul(..), Self::Mul(..)) => {
+				let mut others = other.factors();
+				self.factors().into_iter().all(|factor| {
+					match others.iter().position(|other| *other == factor) {
+						Some(i) => {
+							others.swap_remove(i);
+							true
+						}
+						None => false,
+					}
+				}) && others.is_empty()
+			}
+			_ => self == other,
+		}
+	}
+
+	fn factors(&self) -> Vec<&Self> {
+		match self {
+			Self::Mul(a, b) => {
+				let mut factors = a.factors();
+				factors.extend(b.factors());
+				factors
+			}
+			expr => vec![expr],
+		}
+	}
+
+	/// Checks if the first factor of a product has a minus.
+	fn has_minus(&self) -> bool {
+		match self {
+			Self::Number(a) => is_negative(a),
+			Self::Neg(_) => true,
+			Self::Mul(a, _) | Self::Div(a, _) => a.has_minus(),
+			_ => false,
+		}
+	}
+}
+
+/// Terms of a simplified sum, terms only differing by their coefficient are collected.
+struct Sum<'c, N: Number> {
+	ctx: &'c N::Context,
+	/// Coefficients, divisors and the products without them, e.g. `3`, `2` and `x` for `3*x/2`.
+	/// Constants are collected in the term without product, so the order of the terms is kept.
+	terms: Vec<(N, N, Option<Expr<N>>)>,
+}
+
+impl<'c, N: Number> Sum<'c, N> {
+	fn new(ctx: &'c N::Context) -> Result<Self, Error> {
+		Ok(Self {
+			ctx,
+			terms: Vec::new(),
+		})
+	}
+
+	/// Adds the terms of the expression, which are subtracted if it is negative.
+	fn push(&mut self, expr: Expr<N>, negative: bool) -> Result<(), Error> {
+		match expr {
+			Expr::Neg(a) => self.push(*a, !negative)?,
+			Expr::Add(a, b) => {
+				self.push(*a, negative)?;
+				self.push(*b, negative)?;
+			}
+			Expr::Sub(a, b) => {
+				self.push(*a, negative)?;
+				self.push(*b, !negative)?;
+			}
+			expr => {
+				let mut product = Product::new(self.ctx)?;
+				product.push(expr, false)?;
+				let mut coefficient =
+					mem::replace(&mut product.coefficient, N::parse("1", self.ctx)?);
+				let divisor = mem::replace(&mut product.divisor, N::parse("1", self.ctx)?);
+				if negative {
+					coefficient = coefficient.neg()?;
+				}
+				let term = if product.numerator.is_empty() && product.denominator.is_empty() {
+					None
+				} else {
+					Some(product.finish()?)
+				};
+				self.add(coefficient, divisor, term)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn add(&mut self, coefficient: N, divisor: N, term: Option<Expr<N>>) -> Result<(), Error> {
+		let find = |other: &Option<Expr<N>>| match (other, &term) {
+			(Some(other), Some(term)) => other.is_permutation(term),
+			(other, term) => other.is_none() && term.is_none(),
+		};
+		match self.terms.iter_mut().find(|(.., other)| find(other)) {
+			Some((sum, sum_divisor, _)) if *sum_divisor == divisor => {
+				*sum = sum.clone().add(coefficient)?;
+			}
+			// a/b + c/d = (a*d + c*b) / (b*d)
+			Some((sum, sum_divisor, _)) => {
+				*sum = sum
+					.clone()
+					.mul(divisor.clone())?
+					.add(coefficient.mul(sum_divisor.clone())?)?;
+				*sum_divisor = sum_divisor.clone().mul(divisor)?;
+			}
+			None => self.terms.push((coefficient, divisor, term)),
+		}
+		Ok(())
+	}
+
+	fn finish(self) -> Result<Expr<N>, Error> {
+		let mut result: Option<Expr<N>> = None;
+		for (coefficient, divisor, term) in self.terms {
+			let mut product = Product::new(self.ctx)?;
+			if let Some(term) = term {
+				product.push(term, false)?;
+			}
+			product.coefficient = coefficient;
+			product.divisor = divisor;
+			let term = product.finish()?;
+			result = Some(match result {
+				_ if term.is("0") => continue,
+				None => term,
+				Some(result) if term.has_minus() => result.sub(term.negate(self.ctx)?),
+				Some(result) => result.add(term),
+			});
+		}
+		match result {
+			Some(result) => Ok(result),
+			None => Expr::number("0", self.ctx),
+		}
+	}
+}
+
+/// Factors of a simplified product, e.g. `2*x**2/y` has the coefficient 2 and the factors `x**2` and `y`.
+struct Product<'c, N: Number> {
+	ctx: &'c N::Context,
+	coefficient: N,
+	divisor: N,
+	/// Bases and exponents of the factors, equal bases are combined.
+	numerator: Vec<(Expr<N>, Expr<N>)>,
+	denominator: Vec<(Expr<N>, Expr<N>)>,
+}
+
+impl<'c, N: Number> Product<'c, N> {
+	fn new(ctx: &'c N::Context) -> Result<Self, Error> {
+		Ok(Self {
+			ctx,
+			coefficient: N::parse("1", ctx)?,
+			divisor: N::parse("1", ctx)?,
+			numerator: Vec::new(),
+			denominator: Vec::new(),
+		})
+	}
+
+	/// Adds the factors of the expression, which divides the product if it is inverse.
+	fn push(&mut self, expr: Expr<N>, inverse: bool) -> Result<(), Error> {
+		match expr {
+			Expr::Number(number) if inverse => self.divisor = self.divisor.clone().mul(number)?,
+			Expr::Number(number) => self.coefficient = self.coefficient.clone().mul(number)?,
+			Expr::Neg(a) => {
+				self.coefficient = self.coefficient.clone().neg()?;
+				self.push(*a, inverse)?;
+			}
+			Expr::Mul(a, b) => {
+				self.push(*a, inverse)?;
+				self.push(*b, inverse)?;
+			}
+			Expr::Div(a, b) => {
+				self.push(*a, inverse)?;
+				self.push(*b, !inverse)?;
+			}
+			expr => {
+				let (base, exponent) = match expr {
+					Expr::Pow(base, exponent) => (*base, *exponent),
+					expr => (expr, Expr::number("1", self.ctx)?),
+				};
+				let factors = if inverse {
+					&mut self.denominator
+				} else {
+					&mut self.numerator
+				};
+				match factors.iter_mut().find(|(other, _)| *other == base) {
+					Some((_, sum)) => *sum = sum.clone().add(exponent).simplify(self.ctx)?,
+					None => factors.push((base, exponent)),
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn finish(mut self) -> Result<Expr<N>, Error> {
+		let one = N::parse("1", self.ctx)?;
+		if self.coefficient == N::parse("0", self.ctx)? {
+			return Expr::number("0", self.ctx);
+		}
+		// fractions of integers are reduced instead of divided, so they aren't rounded, e.g. `6/4` results in `3/2`
+		match gcd(&self.coefficient, &self.divisor) {
+			Some(gcd) => {
+				self.coefficient = self.coefficient.div(gcd.clone())?;
+				self.divisor = self.divisor.div(gcd)?;
+			}
+			None => {
+				self.coefficient = self.coefficient.div(self.divisor)?;
+				self.divisor = one.clone();
+			}
+		}
+		if is_negative(&self.divisor) {
+			self.coefficient = self.coefficient.neg()?;
+			self.divisor = self.divisor.neg()?;
+		}
+
+		// powers of the same base are divided, e.g. `x**3/x` results in `x**2`
+		let mut numerator = Vec::new();
+		let mut denominator = Vec::new();
+		for (base, exponent) in self.numerator {
+			match self
+				.denominator
+				.iter()
+				.position(|(other, _)| *other == base)
+			{
+				Some(i) => {
+					let (_, divisor) = self.denominator.remove(i);
+					let exponent = exponent.sub(divisor).simplify(self.ctx)?;
+					numerator.push((base, exponent));
+				}
+				None => numerator.push((base, exponent)),
+			}
+		}
+		denominator.extend(self.denominator);
+		// factors with negative exponents are moved, e.g. `x**-2` results in `1/x**2`
+		let (numerator, moved): (Vec<_>, Vec<_>) = numerator
+			.into_iter()
+			.filter(|(_, exponent)| !exponent.is("0"))
+			.partition(|(_, exponent)| !exponent.has_minus());
+		for (base, exponent) in moved {
+			denominator.push((base, exponent.negate(self.ctx)?));
+		}
+		let power = |(base, exponent): (Expr<N>, Expr<N>)| {
+			if exponent.is("1") {
+				base
+			} else {
+				base.pow(exponent)
+			}
+		};
+
+		let minus_one = one.clone().neg()?;
+		let mut numerator = numerator.into_iter().map(power);
+		let mut result = match numerator.next() {
+			Some(first) if self.coefficient == one => first,
+			Some(first) if self.coefficient == minus_one => first.neg(),
+			Some(first) => Expr::Number(self.coefficient).mul(first),
+			None => Expr::Number(self.coefficient),
+		};
+		for factor in numerator {
+			result = result.mul(factor);
+		}
+
+		let mut denominator = denominator.into_iter().map(power);
+		let divisor = match denominator.next() {
+			Some(first) if self.divisor == one => first,
+			Some(first) => Expr::Number(self.divisor).mul(first),
+			None if self.divisor == one => return Ok(result),
+			None => Expr::Number(self.divisor),
+		};
+		Ok(result.div(denominator.fold(divisor, Expr::mul)))
+	}
+}
+
+fn is_negative<N: Number>(number: &N) -> bool {
+	number.to_f64() < 0.0
+}
+
+fn is_integer<N: Number>(number: &N) -> bool {
+	number.to_f64().fract() == 0.0
+}
+
+/// Greatest common divisor of integers, `None` for other numbers.
+fn gcd<N: Number>(a: &N, b: &N) -> Option<N> {
+	if !is_integer(a) || !is_integer(b) {
+		return None;
+	}
+	let (mut a, mut b) = (a.clone().abs().ok()?, b.clone().abs().ok()?);
+	while b.to_f64() != 0.0 {
+		let remainder = a.clone().rem(b.clone()).ok()?;
+		a = b;
+		b = remainder;
+	}
+	(a.to_f64() != 0.0).then_some(a)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{parse, Environment};
+
+	fn simplify(input: &str) -> String {
+		let mut env = Environment::<f64>::new();
+		env.init_std();
+		parse(input, &env)
+			.unwrap()
+			.into_number()
+			.unwrap()
+			.simplify(&())
+			.unwrap()
+			.to_string()
+	}
+
+	#[test]
+	fn test_01_identities() {
+		assert_eq!(simplify("x*1 + 0"), "x");
+		assert_eq!(simplify("0*x + y**1"), "y");
+		assert_eq!(simplify("x**0 - 0"), "1");
+		assert_eq!(simplify("0 - x"), "-x");
+		assert_eq!(simplify("x - x"), "0");
+		assert_eq!(simplify("x*ln e + ln x"), "x + ln x");
+	}
+
+	#[test]
+	fn test_02_products() {
+		assert_eq!(simplify("2 * 3 + 4"), "10");
+		assert_eq!(simplify("x*2*y*3"), "6*x*y");
+		assert_eq!(simplify("-x * -y"), "x*y");
+		assert_eq!(simplify("a + -2*x"), "a - 2*x");
+		assert_eq!(simplify("6*x/(3*x)"), "2");
+		assert_eq!(simplify("1/3*x"), "x/3");
+	}
+
+	#[test]
+	fn test_03_like_terms() {
+		assert_eq!(simplify("x * 1 + 0 + 2*x"), "3*x");
+		assert_eq!(simplify("2*x*y - y*x*2 + 1"), "1");
+		assert_eq!(simplify("1 - x + 3 - (2 - x)"), "2");
+		assert_eq!(simplify("x/2 + 3*x/2"), "2*x");
+		assert_eq!(simplify("a + b - a + b"), "2*b");
+		assert_eq!(simplify("sin x + 2 + sin x"), "2*sin x + 2");
+	}
+
+	#[test]
+	fn test_04_powers() {
+		assert_eq!(simplify("x*x*x"), "x**3");
+		assert_eq!(simplify("x**2 * x**3"), "x**5");
+		assert_eq!(simplify("x**a * x**b"), "x**(a + b)");
+		assert_eq!(simplify("x**3 / x"), "x**2");
+		assert_eq!(simplify("x / x**3"), "1/x**2");
+		assert_eq!(simplify("(x**2)**3"), "x**6");
+		assert_eq!(simplify("(x**2)**(1/2)"), "(x**2)**(1/2)");
+		assert_eq!(simplify("2*y**-1"), "2/y");
+	}
+}
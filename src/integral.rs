@@ -0,0 +1,154 @@
+use crate::{Error, Token};
+
+/// Maximum number of segments the interval is split into by the adaptive quadrature.
+const MAX_SEGMENTS: usize = 1000;
+
+/// Nodes of the 15-point Kronrod rule on `[-1, 1]`, the odd ones are the nodes of the 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+	0.9914553711208126,
+	0.9491079123427585,
+	0.8648644233597691,
+	0.7415311855993945,
+	0.5860872354676911,
+	0.4058451513773972,
+	0.20778495500789848,
+	0.0,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+	0.022935322010529224,
+	0.06309209262997856,
+	0.10479001032225019,
+	0.14065325971552592,
+	0.1690047266392679,
+	0.19035057806478542,
+	0.20443294007529889,
+	0.20948214108472782,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+	0.1294849661688697,
+	0.27970539148927664,
+	0.3818300505051189,
+	0.4179591836734694,
+];
+
+/// Function or expression which is integrated by `integrate`.
+pub(crate) enum Integrand<N> {
+	/// Expression in a variable, e.g. `integrate(x**2, x, 0, 1)`.
+	Expression { body: Vec<Token<N>>, var: String },
+	/// Function of one argument, e.g. `integrate(sin, 0, pi)`.
+	Function(String),
+}
+
+/// Approximated definite integral with the estimated absolute error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Integral<N> {
+	pub value: N,
+	pub error: N,
+}
+
+struct Segment {
+	a: f64,
+	b: f64,
+	value: f64,
+	error: f64,
+}
+
+/// Integrates with the adaptive Gauss-Kronrod (7/15) quadrature, returning the value and the error estimate.
+///
+/// The segment with the largest error is bisected until the total error is below the tolerance,
+/// which is absolute for small and relative for large integrals.
+pub(crate) fn quadrature(
+	mut f: impl FnMut(f64) -> Result<f64, Error>,
+	a: f64,
+	b: f64,
+	tolerance: f64,
+) -> Result<(f64, f64), Error> {
+	if !a.is_finite() || !b.is_finite() {
+		return Err(Error::Runtime("Integration bounds must be finite!"));
+	}
+	let mut segments = vec![gauss_kronrod(&mut f, a, b)?];
+	loop {
+		let value: f64 = segments.iter().map(|segment| segment.value).sum();
+		let error: f64 = segments.iter().map(|segment| segment.error).sum();
+		if error <= tolerance.max(tolerance * value.abs()) {
+			return Ok((value, error));
+		}
+		let i = segments
+			.iter()
+			.enumerate()
+			.max_by(|(_, x), (_, y)| x.error.total_cmp(&y.error))
+			.map_or(0, |(i, _)| i);
+		let segment = segments.swap_remove(i);
+		let mid = (segment.a + segment.b) / 2.0;
+		if segments.len() + 2 > MAX_SEGMENTS || mid == segment.a || mid == segment.b {
+			return Err(Error::Runtime("Integral doesn't converge!"));
+		}
+		segments.push(gauss_kronrod(&mut f, segment.a, mid)?);
+		segments.push(gauss_kronrod(&mut f, mid, segment.b)?);
+	}
+}
+
+/// Applies both rules on one segment, their difference is the error estimate.
+fn gauss_kronrod(
+	f: &mut impl FnMut(f64) -> Result<f64, Error>,
+	a: f64,
+	b: f64,
+) -> Result<Segment, Error> {
+	let center = (a + b) / 2.0;
+	let half = (b - a) / 2.0;
+	let mut eval = |x: f64| {
+		let y = f(x)?;
+		if y.is_finite() {
+			Ok(y)
+		} else {
+			Err(Error::Runtime("Integrand isn't finite in the interval!"))
+		}
+	};
+
+	let y = eval(center)?;
+	let mut kronrod = KRONROD_WEIGHTS[7] * y;
+	let mut gauss = GAUSS_WEIGHTS[3] * y;
+	for (i, node) in KRONROD_NODES[..7].iter().enumerate() {
+		let y = eval(center - half * node)? + eval(center + half * node)?;
+		kronrod += KRONROD_WEIGHTS[i] * y;
+		if i % 2 == 1 {
+			gauss += GAUSS_WEIGHTS[i / 2] * y;
+		}
+	}
+	Ok(Segment {
+		a,
+		b,
+		value: kronrod * half,
+		error: ((kronrod - gauss) * half).abs(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_01_quadrature() {
+		let (value, error) = quadrature(|x| Ok(x * x), 0.0, 3.0, 1e-10).unwrap();
+		assert!((value - 9.0).abs() < 1e-12 && error < 1e-10);
+
+		let (value, _) = quadrature(|x| Ok(x.sin()), 0.0, std::f64::consts::PI, 1e-10).unwrap();
+		assert!((value - 2.0).abs() < 1e-12);
+
+		// the singularity at the bound is resolved by bisection
+		let (value, _) = quadrature(|x| Ok(1.0 / x.sqrt()), 0.0, 1.0, 1e-8).unwrap();
+		assert!((value - 2.0).abs() < 1e-7);
+
+		let (value, _) = quadrature(Ok, 1.0, -1.0, 1e-10).unwrap();
+		assert_eq!(value, 0.0);
+
+		assert_eq!(
+			quadrature(|x| Ok(1.0 / x), 0.0, 1.0, 1e-10),
+			Err(Error::Runtime("Integral doesn't converge!"))
+		);
+		assert_eq!(
+			quadrature(|x| Ok(1.0 / x), -1.0, 1.0, 1e-10),
+			Err(Error::Runtime("Integrand isn't finite in the interval!"))
+		);
+	}
+}
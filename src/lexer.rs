@@ -1,7 +1,12 @@
-use crate::{AddOperator, Error, ExpOperator, MulOperator, Token, TokenValue};
+use num_bigint::BigInt;
 
-pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
-	let mut tokens: Vec<Token> = Vec::new();
+use crate::{
+	AddOperator, BitOperator, CmpOperator, Date, DateTime, Error, ExpOperator, LogicOperator,
+	MulOperator, Number, Time, Token, TokenValue,
+};
+
+pub fn tokenize<N: Number>(input: &str, ctx: &N::Context) -> Result<Vec<Token<N>>, Error> {
+	let mut tokens: Vec<Token<N>> = Vec::new();
 	let mut chars = input.chars().peekable();
 	let mut start = 0;
 	while let Some(char) = chars.next() {
@@ -15,6 +20,9 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
 			}
 			'(' => value = TokenValue::OpenBracket,
 			')' => value = TokenValue::CloseBracket,
+			'[' => value = TokenValue::OpenSquareBracket,
+			']' => value = TokenValue::CloseSquareBracket,
+			',' => value = TokenValue::Comma,
 			'+' => value = TokenValue::AddOperator(AddOperator::Add),
 			'-' => value = TokenValue::AddOperator(AddOperator::Sub),
 			'*' => match chars.peek() {
@@ -32,25 +40,96 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
 				_ => value = TokenValue::MulOperator(MulOperator::Div),
 			},
 			'%' => value = TokenValue::MulOperator(MulOperator::Mod),
-			'=' => value = TokenValue::Equals,
+			'\\' => value = TokenValue::MulOperator(MulOperator::LeftDiv),
+			'=' => match chars.peek() {
+				Some('=') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::CmpOperator(CmpOperator::Equal);
+				}
+				_ => value = TokenValue::Equals,
+			},
+			'!' => match chars.peek() {
+				Some('=') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::CmpOperator(CmpOperator::NotEqual);
+				}
+				_ => value = TokenValue::Not,
+			},
+			'<' => match chars.peek() {
+				Some('=') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::CmpOperator(CmpOperator::LessEqual);
+				}
+				Some('<') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::BitOperator(BitOperator::ShiftLeft);
+				}
+				_ => value = TokenValue::CmpOperator(CmpOperator::Less),
+			},
+			'>' => match chars.peek() {
+				Some('=') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::CmpOperator(CmpOperator::GreaterEqual);
+				}
+				Some('>') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::BitOperator(BitOperator::ShiftRight);
+				}
+				_ => value = TokenValue::CmpOperator(CmpOperator::Greater),
+			},
+			'&' => match chars.peek() {
+				Some('&') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::LogicOperator(LogicOperator::And);
+				}
+				_ => value = TokenValue::BitOperator(BitOperator::And),
+			},
+			'|' => match chars.peek() {
+				Some('|') => {
+					src.push(chars.next().unwrap());
+					value = TokenValue::LogicOperator(LogicOperator::Or);
+				}
+				_ => value = TokenValue::BitOperator(BitOperator::Or),
+			},
+			'^' => value = TokenValue::BitOperator(BitOperator::Xor),
+			'~' => value = TokenValue::BitNot,
 			'$' => value = TokenValue::LastResult,
-			c if c.is_ascii_digit() => {
-				let mut point = false;
-				while let Some(n_char) = chars.peek() {
-					if n_char.is_numeric() || (*n_char == '.' && !point) {
-						if let Some(char) = chars.next() {
-							if char == '.' {
-								point = true;
+			c if c.is_ascii_digit()
+				|| c == '.' && matches!(chars.peek(), Some(c) if c.is_ascii_digit()) =>
+			{
+				if let Some((len, literal)) = date_time_literal(&input[start..]) {
+					src = input[start..start + len].to_owned();
+					for _ in 1..len {
+						chars.next();
+					}
+					value = literal.ok_or_else(|| Error::UnexpectedToken {
+						token: src.clone(),
+						start,
+						end: start + len - 1,
+					})?;
+				} else {
+					let (len, literal) = number_literal(&input[start..], start)?;
+					src = input[start..start + len].to_owned();
+					for _ in 1..len {
+						chars.next();
+					}
+					let mut number = N::parse(&literal, ctx)?;
+
+					// imaginary literals like `2i` are only used if the number type supports them
+					if let Some(suffix @ ('i' | 'j')) = chars.peek().copied() {
+						let mut lookahead = chars.clone();
+						lookahead.next();
+						if !matches!(lookahead.peek(), Some(c) if c.is_ascii_alphanumeric() || *c == '_')
+						{
+							let imaginary = format!("{}{}", literal, suffix);
+							if let Ok(imaginary_number) = N::parse(&imaginary, ctx) {
+								chars.next();
+								src.push(suffix);
+								number = imaginary_number;
 							}
-							src.push(char);
-							continue;
 						}
 					}
-					break;
-				}
-				match src.parse() {
-					Ok(number) => value = TokenValue::Number(number),
-					Err(_) => return Err(Error::Fatal("Cannot parse number!")), // should never happen
+					value = TokenValue::Number(number);
 				}
 			}
 			c if c.is_ascii_alphabetic() || c == '_' => {
@@ -63,11 +142,15 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
 					}
 					break;
 				}
-				if src == "let" {
-					value = TokenValue::Let;
-				} else {
-					value = TokenValue::Identifier(src.clone());
-				}
+				value = match src.as_str() {
+					"let" => TokenValue::Let,
+					"if" => TokenValue::If,
+					"then" => TokenValue::Then,
+					"else" => TokenValue::Else,
+					"true" => TokenValue::Bool(true),
+					"false" => TokenValue::Bool(false),
+					_ => TokenValue::Identifier(src.clone()),
+				};
 			}
 			c => return Err(Error::InvalidCharacter(c, start)),
 		}
@@ -86,6 +169,185 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
 	Ok(tokens)
 }
 
+/// Recognizes number literals like `42`, `.5`, `6.02e23`, `1_000_000` or `0xFF` at the start of the input.
+/// Returns the length of the literal and its decimal representation, which is parsed by the number type.
+fn number_literal(src: &str, start: usize) -> Result<(usize, String), Error> {
+	let bytes = src.as_bytes();
+	let invalid = |len: usize| {
+		// the rest of a malformed literal is part of the error, e.g. `0b102` or `1.2.3`
+		let len = len
+			+ bytes[len..]
+				.iter()
+				.take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.'))
+				.count();
+		Error::InvalidNumber {
+			literal: src[..len].to_owned(),
+			start,
+			end: start + len - 1,
+		}
+	};
+	let scan = |from: usize, radix: u32| {
+		from + bytes[from..]
+			.iter()
+			.take_while(|b| (**b as char).is_digit(radix) || **b == b'_')
+			.count()
+	};
+
+	// a radix prefix must be lowercase and followed by a digit, e.g. `0x`, `0XFF` or `0o8` are invalid
+	if bytes[0] == b'0' && matches!(bytes.get(1), Some(b'x' | b'o' | b'b' | b'X' | b'O' | b'B')) {
+		let radix = match bytes[1] {
+			b'x' => 16,
+			b'o' => 8,
+			b'b' => 2,
+			_ => return Err(invalid(2)),
+		};
+		let len = scan(2, radix);
+		let value = digits(&src[2..len], radix)
+			.and_then(|digits| BigInt::parse_bytes(digits.as_bytes(), radix));
+		return match value {
+			Some(value) if !matches!(bytes.get(len), Some(b) if b.is_ascii_alphanumeric() || *b == b'.') => {
+				Ok((len, value.to_string()))
+			}
+			_ => Err(invalid(len)),
+		};
+	}
+
+	let len = scan(0, 10);
+	let integer = digits(&src[..len], 10).ok_or_else(|| invalid(len))?;
+	let mut literal = if integer.is_empty() {
+		"0".to_owned()
+	} else {
+		integer
+	};
+	let mut len = len;
+	if bytes.get(len) == Some(&b'.') {
+		let end = scan(len + 1, 10);
+		let fraction = digits(&src[len + 1..end], 10).ok_or_else(|| invalid(end))?;
+		if !fraction.is_empty() {
+			literal = format!("{}.{}", literal, fraction);
+		}
+		len = end;
+	}
+
+	// `2e` is still two times Euler's number, only a following digit or sign starts an exponent
+	let exponent = matches!(bytes.get(len), Some(b'e' | b'E'));
+	let sign = matches!(bytes.get(len + 1), Some(b'+' | b'-')) as usize;
+	let digit = matches!(bytes.get(len + 1 + sign), Some(b) if b.is_ascii_digit());
+	if exponent && sign == 1 && !digit {
+		return Err(invalid(len + 2));
+	}
+	if exponent && digit {
+		let end = scan(len + 1 + sign, 10);
+		let exponent = digits(&src[len + 1 + sign..end], 10).ok_or_else(|| invalid(end))?;
+		literal = format!("{}e{}{}", literal, &src[len + 1..len + 1 + sign], exponent);
+		len = end;
+	}
+	if bytes.get(len) == Some(&b'.') {
+		return Err(invalid(len));
+	}
+	Ok((len, literal))
+}
+
+/// Removes the underscores between digits, which must not be at the start or end or follow each other.
+fn digits(src: &str, radix: u32) -> Option<String> {
+	if src.is_empty() {
+		return Some(String::new());
+	}
+	let groups: Vec<_> = src.split('_').collect();
+	let valid = groups
+		.iter()
+		.all(|group| !group.is_empty() && group.chars().all(|c| c.is_digit(radix)));
+	valid.then(|| groups.concat())
+}
+
+/// Recognizes date, time and duration literals like `2026-10-17`, `14:30` or `1h30m` at the start of the input.
+/// Returns the length of the literal and its value, which is `None` for invalid dates like `2026-02-30`.
+fn date_time_literal<N>(src: &str) -> Option<(usize, Option<TokenValue<N>>)> {
+	let bytes = src.as_bytes();
+	let digits = |from: usize| {
+		bytes
+			.iter()
+			.skip(from)
+			.take_while(|b| b.is_ascii_digit())
+			.count()
+	};
+	let number = |from: usize, len: usize| src[from..from + len].parse().unwrap_or(u32::MAX);
+	let time = |from: usize| {
+		let hours = digits(from);
+		if !(1..=2).contains(&hours)
+			|| bytes.get(from + hours) != Some(&b':')
+			|| digits(from + hours + 1) != 2
+		{
+			return None;
+		}
+		let mut len = hours + 3;
+		let mut second = 0;
+		if bytes.get(from + len) == Some(&b':') && digits(from + len + 1) == 2 {
+			second = number(from + len + 1, 2);
+			len += 3;
+		}
+		let minute = number(from + hours + 1, 2);
+		Some((len, Time::new(number(from, hours), minute, second)))
+	};
+
+	if digits(0) == 4
+		&& bytes.get(4) == Some(&b'-')
+		&& digits(5) == 2
+		&& bytes.get(7) == Some(&b'-')
+		&& digits(8) == 2
+	{
+		let date = Date::new(number(0, 4) as i64, number(5, 2), number(8, 2));
+		if bytes.get(10) == Some(&b'T') {
+			if let Some((len, time)) = time(11) {
+				let date_time = date.zip(time).map(|(date, time)| DateTime::new(date, time));
+				return Some((11 + len, date_time.map(TokenValue::DateTime)));
+			}
+		}
+		return Some((10, date.map(TokenValue::Date)));
+	}
+	if let Some((len, time)) = time(0) {
+		return Some((len, time.map(TokenValue::Time)));
+	}
+	duration_literal(src)
+}
+
+/// Recognizes compound durations like `1h30m` or `2d12h`,
+/// which consist of at least two components in descending order.
+fn duration_literal<N>(src: &str) -> Option<(usize, Option<TokenValue<N>>)> {
+	const UNITS: [(u8, i64); 4] = [(b'd', 86400), (b'h', 3600), (b'm', 60), (b's', 1)];
+	let bytes = src.as_bytes();
+	let (mut len, mut components, mut next_unit) = (0, 0, 0);
+	let mut seconds = Some(0i64);
+	loop {
+		let digits = bytes[len..]
+			.iter()
+			.take_while(|b| b.is_ascii_digit())
+			.count();
+		if digits == 0 {
+			break;
+		}
+		let symbol = bytes.get(len + digits);
+		let unit = next_unit
+			+ UNITS[next_unit..]
+				.iter()
+				.position(|(unit, _)| Some(unit) == symbol)?;
+		let value = src[len..len + digits].parse::<i64>().ok();
+		seconds = seconds
+			.zip(value)
+			.and_then(|(seconds, value)| value.checked_mul(UNITS[unit].1)?.checked_add(seconds));
+		len += digits + 1;
+		components += 1;
+		next_unit = unit + 1;
+		if matches!(bytes.get(len), Some(b) if b.is_ascii_alphabetic() || *b == b'_') {
+			return None;
+		}
+	}
+	if components < 2 {
+		return None;
+	}
+	Some((len, seconds.map(TokenValue::Duration)))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -93,7 +355,7 @@ mod tests {
 	#[test]
 	fn test_01_blank_input() {
 		assert_eq!(
-			tokenize("   \n\n \r \t\t		").unwrap(),
+			tokenize::<f64>("   \n\n \r \t\t		", &()).unwrap(),
 			vec![Token::new(TokenValue::Eof, "EOF".to_owned(), 12, 12)]
 		);
 	}
@@ -101,7 +363,7 @@ mod tests {
 	#[test]
 	fn test_02_numerical_literal() {
 		assert_eq!(
-			tokenize("9 44.4").unwrap(),
+			tokenize::<f64>("9 44.4", &()).unwrap(),
 			vec![
 				Token::new(TokenValue::Number(9.0), "9".to_owned(), 0, 0),
 				Token::new(TokenValue::Number(44.4), "44.4".to_owned(), 2, 5),
@@ -113,7 +375,7 @@ mod tests {
 	#[test]
 	fn test_03_add_operator_literal() {
 		assert_eq!(
-			tokenize("+-").unwrap(),
+			tokenize::<f64>("+-", &()).unwrap(),
 			vec![
 				Token::new(
 					TokenValue::AddOperator(AddOperator::Add),
@@ -135,7 +397,7 @@ mod tests {
 	#[test]
 	fn test_04_mul_operator_literal() {
 		assert_eq!(
-			tokenize("*/%").unwrap(),
+			tokenize::<f64>("*/%", &()).unwrap(),
 			vec![
 				Token::new(
 					TokenValue::MulOperator(MulOperator::Mul),
@@ -163,7 +425,7 @@ mod tests {
 	#[test]
 	fn test_05_bracket_literal() {
 		assert_eq!(
-			tokenize("()").unwrap(),
+			tokenize::<f64>("()", &()).unwrap(),
 			vec![
 				Token::new(TokenValue::OpenBracket, "(".to_owned(), 0, 0),
 				Token::new(TokenValue::CloseBracket, ")".to_owned(), 1, 1),
@@ -175,7 +437,7 @@ mod tests {
 	#[test]
 	fn test_06_equals_character() {
 		assert_eq!(
-			tokenize("= 4").unwrap(),
+			tokenize::<f64>("= 4", &()).unwrap(),
 			vec![
 				Token::new(TokenValue::Equals, "=".to_owned(), 0, 0),
 				Token::new(TokenValue::Number(4.0), "4".to_owned(), 2, 2),
@@ -187,7 +449,7 @@ mod tests {
 	#[test]
 	fn test_07_identifier() {
 		assert_eq!(
-			tokenize("Id id123").unwrap(),
+			tokenize::<f64>("Id id123", &()).unwrap(),
 			vec![
 				Token::new(
 					TokenValue::Identifier("Id".to_owned()),
@@ -206,7 +468,7 @@ mod tests {
 		);
 
 		assert_eq!(
-			tokenize("4id").unwrap(),
+			tokenize::<f64>("4id", &()).unwrap(),
 			vec![
 				Token::new(TokenValue::Number(4.0), "4".to_owned(), 0, 0),
 				Token::new(
@@ -222,7 +484,7 @@ mod tests {
 
 	#[test]
 	fn test_08_invalid_character() {
-		match tokenize("<") {
+		match tokenize::<f64>("#", &()) {
 			Err(_) => (),
 			_ => panic!(),
 		}
@@ -231,7 +493,7 @@ mod tests {
 	#[test]
 	fn test_09_last_result() {
 		assert_eq!(
-			tokenize("a$4").unwrap(),
+			tokenize::<f64>("a$4", &()).unwrap(),
 			vec![
 				Token::new(TokenValue::Identifier("a".to_owned()), "a".to_owned(), 0, 0),
 				Token::new(TokenValue::LastResult, "$".to_owned(), 1, 1),
@@ -244,7 +506,7 @@ mod tests {
 	#[test]
 	fn test_10_exp_operator_literal() {
 		assert_eq!(
-			tokenize("**//").unwrap(),
+			tokenize::<f64>("**//", &()).unwrap(),
 			vec![
 				Token::new(
 					TokenValue::ExpOperator(ExpOperator::Power),
@@ -262,4 +524,211 @@ mod tests {
 			]
 		);
 	}
+
+	#[test]
+	fn test_11_comparison_and_logic_literal() {
+		assert_eq!(
+			tokenize::<f64>("== != < <= > >= && || !", &())
+				.unwrap()
+				.into_iter()
+				.map(|token| token.value)
+				.collect::<Vec<_>>(),
+			vec![
+				TokenValue::CmpOperator(CmpOperator::Equal),
+				TokenValue::CmpOperator(CmpOperator::NotEqual),
+				TokenValue::CmpOperator(CmpOperator::Less),
+				TokenValue::CmpOperator(CmpOperator::LessEqual),
+				TokenValue::CmpOperator(CmpOperator::Greater),
+				TokenValue::CmpOperator(CmpOperator::GreaterEqual),
+				TokenValue::LogicOperator(LogicOperator::And),
+				TokenValue::LogicOperator(LogicOperator::Or),
+				TokenValue::Not,
+				TokenValue::Eof,
+			]
+		);
+
+		assert_eq!(
+			tokenize::<f64>("true false", &()).unwrap(),
+			vec![
+				Token::new(TokenValue::Bool(true), "true".to_owned(), 0, 3),
+				Token::new(TokenValue::Bool(false), "false".to_owned(), 5, 9),
+				Token::new(TokenValue::Eof, "EOF".to_owned(), 10, 10)
+			]
+		);
+	}
+
+	#[test]
+	fn test_12_conditional_keywords() {
+		assert_eq!(
+			tokenize::<f64>("if x then 1 else iffy", &())
+				.unwrap()
+				.into_iter()
+				.map(|token| token.value)
+				.collect::<Vec<_>>(),
+			vec![
+				TokenValue::If,
+				TokenValue::Identifier("x".to_owned()),
+				TokenValue::Then,
+				TokenValue::Number(1.0),
+				TokenValue::Else,
+				TokenValue::Identifier("iffy".to_owned()),
+				TokenValue::Eof,
+			]
+		);
+	}
+
+	#[test]
+	fn test_13_list_literal() {
+		assert_eq!(
+			tokenize::<f64>("[1,2]", &()).unwrap(),
+			vec![
+				Token::new(TokenValue::OpenSquareBracket, "[".to_owned(), 0, 0),
+				Token::new(TokenValue::Number(1.0), "1".to_owned(), 1, 1),
+				Token::new(TokenValue::Comma, ",".to_owned(), 2, 2),
+				Token::new(TokenValue::Number(2.0), "2".to_owned(), 3, 3),
+				Token::new(TokenValue::CloseSquareBracket, "]".to_owned(), 4, 4),
+				Token::new(TokenValue::Eof, "EOF".to_owned(), 5, 5)
+			]
+		);
+	}
+
+	#[test]
+	fn test_14_date_time_literal() {
+		let date = Date::new(2026, 10, 17).unwrap();
+		let time = Time::new(14, 30, 0).unwrap();
+		assert_eq!(
+			tokenize::<f64>("2026-10-17 14:30 2026-10-17T14:30 1h30m", &()).unwrap(),
+			vec![
+				Token::new(TokenValue::Date(date), "2026-10-17".to_owned(), 0, 9),
+				Token::new(TokenValue::Time(time), "14:30".to_owned(), 11, 15),
+				Token::new(
+					TokenValue::DateTime(DateTime::new(date, time)),
+					"2026-10-17T14:30".to_owned(),
+					17,
+					32
+				),
+				Token::new(TokenValue::Duration(5400), "1h30m".to_owned(), 34, 38),
+				Token::new(TokenValue::Eof, "EOF".to_owned(), 39, 39)
+			]
+		);
+
+		// single components and arithmetic on numbers are not affected
+		assert_eq!(
+			tokenize::<f64>("30m 2026-10", &())
+				.unwrap()
+				.into_iter()
+				.map(|token| token.src)
+				.collect::<Vec<_>>(),
+			vec!["30", "m", "2026", "-", "10", "EOF"]
+		);
+		assert_eq!(
+			tokenize::<f64>("1 + 2026-13-01", &()),
+			Err(Error::UnexpectedToken {
+				token: "2026-13-01".to_owned(),
+				start: 4,
+				end: 13
+			})
+		);
+	}
+
+	#[test]
+	fn test_15_number_literal_grammar() {
+		let numbers = |input: &str| -> Vec<(f64, String)> {
+			tokenize::<f64>(input, &())
+				.unwrap()
+				.into_iter()
+				.filter_map(|token| match token.value {
+					TokenValue::Number(number) => Some((number, token.src)),
+					_ => None,
+				})
+				.collect()
+		};
+		assert_eq!(
+			numbers("0xFF 0b1010 0o755 6.02e23 1_000_000 .5 5. 2E-3 1e+2"),
+			vec![
+				(255.0, "0xFF".to_owned()),
+				(10.0, "0b1010".to_owned()),
+				(493.0, "0o755".to_owned()),
+				(6.02e23, "6.02e23".to_owned()),
+				(1e6, "1_000_000".to_owned()),
+				(0.5, ".5".to_owned()),
+				(5.0, "5.".to_owned()),
+				(0.002, "2E-3".to_owned()),
+				(100.0, "1e+2".to_owned()),
+			]
+		);
+
+		// letters which don't continue a literal are identifiers
+		assert_eq!(
+			tokenize::<f64>("2e 0a", &())
+				.unwrap()
+				.into_iter()
+				.map(|token| token.src)
+				.collect::<Vec<_>>(),
+			vec!["2", "e", "0", "a", "EOF"]
+		);
+
+		for (input, literal, start, end) in [
+			("1 + 0b102", "0b102", 4, 8),
+			("1.2.3", "1.2.3", 0, 4),
+			("1__000", "1__000", 0, 5),
+			("2 * 10_", "10_", 4, 6),
+			("0xFG", "0xFG", 0, 3),
+			("0x", "0x", 0, 1),
+			("0xG", "0xG", 0, 2),
+			("0o8", "0o8", 0, 2),
+			("0x_FF", "0x_FF", 0, 4),
+			("0XFF", "0XFF", 0, 3),
+			("1e+", "1e+", 0, 2),
+			("2 * 1e-x", "1e-x", 4, 7),
+		] {
+			assert_eq!(
+				tokenize::<f64>(input, &()),
+				Err(Error::InvalidNumber {
+					literal: literal.to_owned(),
+					start,
+					end
+				})
+			);
+		}
+	}
+
+	#[test]
+	fn test_16_bitwise_operator_literal() {
+		assert_eq!(
+			tokenize::<f64>("& | ^ ~ << >> && <= <", &())
+				.unwrap()
+				.into_iter()
+				.map(|token| token.value)
+				.collect::<Vec<_>>(),
+			vec![
+				TokenValue::BitOperator(BitOperator::And),
+				TokenValue::BitOperator(BitOperator::Or),
+				TokenValue::BitOperator(BitOperator::Xor),
+				TokenValue::BitNot,
+				TokenValue::BitOperator(BitOperator::ShiftLeft),
+				TokenValue::BitOperator(BitOperator::ShiftRight),
+				TokenValue::LogicOperator(LogicOperator::And),
+				TokenValue::CmpOperator(CmpOperator::LessEqual),
+				TokenValue::CmpOperator(CmpOperator::Less),
+				TokenValue::Eof,
+			]
+		);
+	}
+
+	#[test]
+	fn test_17_left_division_literal() {
+		assert_eq!(
+			tokenize::<f64>("\\", &()).unwrap(),
+			vec![
+				Token::new(
+					TokenValue::MulOperator(MulOperator::LeftDiv),
+					"\\".to_owned(),
+					0,
+					0
+				),
+				Token::new(TokenValue::Eof, "EOF".to_owned(), 1, 1)
+			]
+		);
+	}
 }
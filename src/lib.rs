@@ -4,25 +4,72 @@ pub mod error;
 pub use error::*;
 mod cursor;
 use cursor::*;
+mod derivative;
+use derivative::*;
 mod environment;
 use environment::*;
+mod expression;
+use expression::*;
+mod integral;
+pub use integral::Integral;
+use integral::*;
 mod lexer;
+mod minimize;
+use minimize::*;
+pub mod number;
+pub use number::*;
 mod parser;
 use parser::Parser;
+mod solve;
+use solve::*;
 mod token;
 use token::*;
+pub mod value;
+pub use value::*;
 
 /// Representation of a calculator instance.
-pub struct Calculator {
-	env: Environment,
+///
+/// The calculator is generic over the used [`Number`] type and uses `f64` by default.
+pub struct Calculator<N: Number = f64> {
+	env: Environment<N>,
 }
 
 impl Calculator {
-	/// This creates a new empty instance of Calculator.
+	/// This creates a new empty instance of Calculator using `f64` numbers.
+	/// Use [`Calculator::default`] for creating an instance with another number type.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::<f32>::default();
+	///
+	/// let val = calculator.calculate("4.5 * 2").unwrap();
+	/// assert_eq!(val, 9.0f32);
+	/// ```
 	pub fn new() -> Self {
-		Self {
-			env: Environment::new(),
-		}
+		Self::default()
+	}
+}
+
+impl<N: Number> Calculator<N> {
+	/// This creates a new empty instance of Calculator using the given context for its numbers.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::<Decimal>::with_context(DecimalContext::new(50, RoundingMode::HalfEven));
+	///
+	/// let val = calculator.calculate("1 / 3").unwrap();
+	/// assert_eq!(val.to_string(), format!("0.{}", "3".repeat(50)));
+	/// ```
+	pub fn with_context(context: N::Context) -> Self {
+		let mut env = Environment::with_context(context);
+		env.set_differentiate(differentiate::<N>);
+		Self { env }
 	}
 
 	/// Initialize the std lib on this calculator instance.
@@ -43,21 +90,30 @@ impl Calculator {
 	///
 	/// let val = calculator.calculate("foo + 2").unwrap();
 	/// assert_eq!(val, 42.0);
+	///
+	/// calculator.add_var("series", Value::List(vec![1.0.into(), 2.0.into()]));
+	/// let val = calculator.calculate("series[1]").unwrap();
+	/// assert_eq!(val, 2.0);
 	/// ```
-	pub fn add_var(&mut self, id: impl Into<String>, val: f32) {
+	pub fn add_var(&mut self, id: impl Into<String>, val: impl Into<Value<N>>) {
 		self.env.assign_var(id, val);
 	}
 
 	/// Adds a custom function to this calculator instance.
 	/// This overrides any existing variable/function with this name without any warning.
 	///
-	/// Supported function types (details: [`Handler`](handler)):  
-	///  - fn(f32) -> f32
-	///  - fn(f32) -> Result<f32, Error>
-	///  - fn(f32, f32) -> f32
-	///  - fn(f32, f32) -> Result<f32, Error>
-	///  - fn(f32, f32, f32) -> f32
-	///  - fn(f32, f32, f32) -> Result<f32, Error>
+	/// Supported function types, where `N` is the number type of the calculator (details: [`Handler`](handler)):  
+	///  - fn(N) -> N
+	///  - fn(N) -> Result<N, Error>
+	///  - fn(N, N) -> N
+	///  - fn(N, N) -> Result<N, Error>
+	///  - fn(N, N, N) -> N
+	///  - fn(N, N, N) -> Result<N, Error>
+	///  - and so on up to six arguments
+	///
+	/// Arguments and results may also be a [`Value`] (e.g. for functions taking lists),
+	/// like `fn(Value<N>) -> Result<Value<N>, Error>`.
+	/// A last argument of type `Vec<Value<N>>` takes all remaining arguments (e.g. `fn(Vec<Value<N>>) -> N`).
 	///
 	/// # Example
 	///
@@ -65,7 +121,7 @@ impl Calculator {
 	/// use calculator::*;
 	///
 	///
-	/// fn div(left: f32, right: f32) -> Result<f32, Error> {
+	/// fn div(left: f64, right: f64) -> Result<f64, Error> {
 	///     if right == 0.0 {
 	///         Err(Error::Fatal("Division by zero!"))
 	///     } else {
@@ -74,7 +130,7 @@ impl Calculator {
 	/// }
 	///
 	/// let mut calculator = Calculator::new();
-	/// calculator.add_fn("double", |arg: f32| arg * 2.0);
+	/// calculator.add_fn("double", |arg: f64| arg * 2.0);
 	/// calculator.add_fn("div", div);
 	///
 	/// let val = calculator.calculate("double 4").unwrap();
@@ -85,12 +141,89 @@ impl Calculator {
 	/// ```
 	pub fn add_fn<H, T>(&mut self, id: impl Into<String>, fun: H)
 	where
-		H: Handler<T> + Clone + 'static,
+		H: Handler<N, T> + Clone + 'static,
 		T: 'static,
 	{
 		self.env.assign_fn(id, fun);
 	}
 
+	/// Adds a custom unit to this calculator instance, defined by an expression of existing units.
+	/// This overrides any existing unit with this symbol without any warning.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::new();
+	/// calculator.init_std();
+	/// calculator.add_unit("furlong", "201.168 m").unwrap();
+	///
+	/// let val = calculator.calculate("1 furlong to m").unwrap();
+	/// assert_eq!(val.to_string(), "201.168 m");
+	/// ```
+	pub fn add_unit(&mut self, symbol: impl Into<String>, definition: &str) -> Result<(), Error> {
+		let (factor, dimension) = self.evaluate_unit_definition(definition)?;
+		self.env.assign_unit(
+			symbol,
+			UnitDef {
+				factor,
+				dimension,
+				prefixable: false,
+				offset: None,
+			},
+		);
+		Ok(())
+	}
+
+	/// Adds a custom unit with a zero point offset like a temperature scale,
+	/// the offset is the value of its zero point, e.g. `273.15 K` for `degC`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::<Rational>::default();
+	/// calculator.init_std();
+	/// calculator.add_affine_unit("degRe", "(5/4) K", "273.15 K").unwrap();
+	///
+	/// let val = calculator.calculate("80 degRe in degC").unwrap();
+	/// assert_eq!(val.to_string(), "100 degC");
+	/// ```
+	pub fn add_affine_unit(
+		&mut self,
+		symbol: impl Into<String>,
+		definition: &str,
+		offset: &str,
+	) -> Result<(), Error> {
+		let (factor, dimension) = self.evaluate_unit_definition(definition)?;
+		let (offset, offset_dimension) = self.evaluate_unit_definition(offset)?;
+		if offset_dimension != dimension {
+			return Err(Error::Runtime(
+				"Offset must have the same dimension as the unit!",
+			));
+		}
+		self.env.assign_unit(
+			symbol,
+			UnitDef {
+				factor,
+				dimension,
+				prefixable: false,
+				offset: Some(offset),
+			},
+		);
+		Ok(())
+	}
+
+	/// Evaluates a unit definition into its coherent SI value and dimension.
+	fn evaluate_unit_definition(&mut self, input: &str) -> Result<(N, Dimension), Error> {
+		let tokens = Cursor::new(lexer::tokenize(input, self.env.context())?);
+		Parser::new(tokens, &mut self.env)
+			.evaluate_expression()?
+			.into_si()
+	}
+
 	/// Calculates the result of the given expression
 	///
 	/// # Example
@@ -108,15 +241,105 @@ impl Calculator {
 	///
 	/// This evaluation can fail if the structure of the input is not valid.
 	/// For example if the input contains invalid characters or have bad syntax.
-	pub fn calculate(&mut self, input: &str) -> Result<f32, Error> {
-		let tokens = Cursor::new(lexer::tokenize(input)?);
+	pub fn calculate(&mut self, input: &str) -> Result<Value<N>, Error> {
+		let tokens = Cursor::new(lexer::tokenize(input, self.env.context())?);
 		Parser::new(tokens, &mut self.env).evaluate()
 	}
+
+	/// Calculates the derivative of the expression for the given variable symbolically.
+	/// Other variables are treated as constants and the result is simplified.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::new();
+	/// calculator.init_std();
+	///
+	/// let derivative = calculator.derive("x**2 * sin x", "x").unwrap();
+	/// assert_eq!(derivative, "2*x*sin x + x**2*cos x");
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Only arithmetic operators and calls of functions with known derivatives are supported,
+	/// e.g. `sin`, `exp`, `ln` or user defined functions. Comparisons and assignments result in an error.
+	pub fn derive(&self, input: &str, var: &str) -> Result<String, Error> {
+		let ctx = self.env.context();
+		let derivative = parse(input, &self.env)?
+			.map_numbers(&|expr| expr.derive(var, ctx)?.simplify(ctx), &|factor| {
+				Ok(factor)
+			})?;
+		Ok(derivative.to_string())
+	}
+
+	/// Simplifies the expression symbolically, e.g. `x * 1 + 0 + 2*x` results in `3*x`.
+	///
+	/// Constants are folded, like terms are collected, neutral and absorbing elements are removed
+	/// and factors with the same base are combined into powers. Variables aren't replaced by their values.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::new();
+	/// calculator.init_std();
+	///
+	/// let simplified = calculator.simplify("x * 1 + 0 + 2*x").unwrap();
+	/// assert_eq!(simplified, "3*x");
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Only arithmetic operators and function calls are supported, comparisons and assignments result in an error.
+	pub fn simplify(&self, input: &str) -> Result<String, Error> {
+		let ctx = self.env.context();
+		let simplified = parse(input, &self.env)?
+			.map_numbers(&|expr| expr.simplify(ctx), &|factor| Ok(factor))?;
+		Ok(simplified.to_string())
+	}
+
+	/// Sets the tolerance of numerical methods like `integrate`, which is `1e-10` by default.
+	/// The tolerance is absolute for small and relative for large results.
+	pub fn set_tolerance(&mut self, tolerance: f64) {
+		self.env.set_tolerance(tolerance)
+	}
+
+	/// Integrates the expression for the given variable from `a` to `b` with adaptive Gauss-Kronrod quadrature,
+	/// the result contains the estimated absolute error.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use calculator::*;
+	///
+	/// let mut calculator = Calculator::new();
+	/// calculator.init_std();
+	///
+	/// let integral = calculator.integrate("x**2", "x", 0.0, 3.0).unwrap();
+	/// assert!((integral.value - 9.0).abs() < 1e-12);
+	/// assert!(integral.error < 1e-10);
+	/// ```
+	///
+	/// # Errors
+	///
+	/// The integral doesn't converge if the tolerance can't be reached, e.g. for `1/x` from `0` to `1`.
+	pub fn integrate(&mut self, input: &str, var: &str, a: N, b: N) -> Result<Integral<N>, Error> {
+		let mut body = lexer::tokenize(input, self.env.context())?;
+		body.pop(); // EOF
+		let integrand = Integrand::Expression {
+			body,
+			var: var.to_owned(),
+		};
+		Parser::new(Cursor::new(Vec::new()), &mut self.env).integrate(&integrand, a, b)
+	}
 }
 
-impl Default for Calculator {
+impl<N: Number> Default for Calculator<N> {
 	fn default() -> Self {
-		Self::new()
+		Self::with_context(N::Context::default())
 	}
 }
 
@@ -239,12 +462,12 @@ mod tests {
 
 	#[test]
 	fn test_10_custom_function() {
-		fn max(arg1: f32, arg2: f32) -> Result<f32, Error> {
+		fn max(arg1: f64, arg2: f64) -> Result<f64, Error> {
 			Ok(arg1.max(arg2))
 		}
 
 		let mut calc = Calculator::new();
-		calc.add_fn("double", |arg: f32| arg * 2.0);
+		calc.add_fn("double", |arg: f64| arg * 2.0);
 		calc.add_fn("max", max);
 
 		assert_eq!(calc.calculate("double 4 + 2").unwrap(), 10.0);
@@ -258,4 +481,1523 @@ mod tests {
 
 		assert_eq!(calc.calculate("foo + 2").unwrap(), 42.0);
 	}
+
+	#[test]
+	fn test_12_number_types() {
+		let mut calc = Calculator::<f32>::default();
+		calc.add_fn("double", |arg: f32| arg * 2.0);
+
+		assert_eq!(calc.calculate("double 4.5").unwrap(), 9.0);
+		assert_eq!(calc.calculate("16777217").unwrap(), 16777216.0);
+
+		let mut calc = Calculator::new();
+		assert_eq!(calc.calculate("16777217").unwrap(), 16777217.0);
+		assert_eq!(calc.calculate("0.1 + 0.2").unwrap(), 0.1 + 0.2);
+	}
+
+	#[test]
+	fn test_13_big_integers() {
+		let mut calc = Calculator::<Integer>::default();
+		calc.init_std();
+
+		assert_eq!(
+			calc.calculate("fact 25 % 1000000007").unwrap(),
+			Integer::from(440732388)
+		);
+		assert_eq!(
+			calc.calculate("2 ** 64 - 1").unwrap().to_string(),
+			"18446744073709551615"
+		);
+		assert!(calc.calculate("pi").is_err());
+		assert_eq!(
+			calc.calculate("4.5"),
+			Err(Error::Runtime("Number is not an integer!"))
+		);
+	}
+
+	#[test]
+	fn test_14_complex_numbers() {
+		let mut calc = Calculator::<Complex>::default();
+		calc.init_std();
+
+		assert_eq!(
+			calc.calculate("(2 + 3i) * (2 - 3j)").unwrap(),
+			Complex::new(13.0, 0.0)
+		);
+		assert_eq!(calc.calculate("i ** 2").unwrap(), Complex::new(-1.0, 0.0));
+		assert_eq!(calc.calculate("sqrt -9").unwrap(), Complex::new(0.0, 3.0));
+		assert_eq!(
+			calc.calculate("abs (3 + 4i)").unwrap(),
+			Complex::new(5.0, 0.0)
+		);
+		assert_eq!(calc.calculate("conj $").unwrap(), Complex::new(5.0, 0.0));
+		assert_eq!(
+			calc.calculate("re (1 - 2i)").unwrap(),
+			Complex::new(1.0, 0.0)
+		);
+		assert_eq!(
+			calc.calculate("im (1 - 2i)").unwrap(),
+			Complex::new(-2.0, 0.0)
+		);
+
+		let z = calc
+			.calculate("polar 2 (pi / 2)")
+			.unwrap()
+			.into_number()
+			.unwrap();
+		assert!(z.re.abs() < 1e-12 && (z.im - 2.0).abs() < 1e-12);
+		let z = calc
+			.calculate("exp (i * pi) + 1")
+			.unwrap()
+			.into_number()
+			.unwrap();
+		assert!(z.re.abs() < 1e-12 && z.im.abs() < 1e-12);
+		assert_eq!(
+			calc.calculate("r2d i"),
+			Err(Error::Runtime("Number is not a real number!"))
+		);
+
+		// imaginary literals are only used by complex number types
+		let mut calc = Calculator::new();
+		assert!(calc.calculate("2i").is_err());
+		calc.add_var("i", 3.0);
+		assert_eq!(calc.calculate("2 * i").unwrap(), 6.0);
+	}
+
+	#[test]
+	fn test_15_intervals() {
+		let mut calc = Calculator::<Interval>::default();
+		calc.init_std();
+
+		assert_eq!(
+			calc.calculate("interval 1 2 * 3").unwrap(),
+			Interval::new(3.0, 6.0)
+		);
+		assert_eq!(
+			calc.calculate("interval -1 2 ** 2").unwrap(),
+			Interval::new(0.0, 4.0)
+		);
+		assert!(calc
+			.calculate("sin pi")
+			.unwrap()
+			.into_number()
+			.unwrap()
+			.contains(0.0));
+		assert!(calc
+			.calculate("0.1 * 3")
+			.unwrap()
+			.into_number()
+			.unwrap()
+			.contains(0.3));
+		assert_eq!(
+			calc.calculate("1 / interval -1 1"),
+			Err(Error::IntervalContainsZero { lo: -1.0, hi: 1.0 })
+		);
+
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(calc.calculate("interval 2 2").unwrap(), 2.0);
+		assert!(calc.calculate("interval 1 2").is_err());
+	}
+
+	#[test]
+	fn test_16_units() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+
+		assert_eq!(calc.calculate("5 m / 2 s").unwrap().to_string(), "2.5 m/s");
+		assert_eq!(
+			calc.calculate("1 km + 300 m").unwrap().to_string(),
+			"1.3 km"
+		);
+		assert_eq!(calc.calculate("5 m**2").unwrap().to_string(), "5 m**2");
+		assert_eq!(calc.calculate("(2 m) ** 2").unwrap().to_string(), "4 m**2");
+		assert_eq!(
+			calc.calculate("(1 m**2000000000) ** 2"),
+			Err(Error::Runtime("Unit exponent is out of range!"))
+		);
+		assert_eq!(
+			calc.calculate("1 m**2000000000 * 1 m**2000000000"),
+			Err(Error::Runtime("Unit exponent is out of range!"))
+		);
+		assert_eq!(calc.calculate("16 m**2 // 2").unwrap().to_string(), "4 m");
+		assert_eq!(
+			calc.calculate("2 kg * 9.81 m s**-2").unwrap().to_string(),
+			"19.62 kg*m/s**2"
+		);
+		assert_eq!(calc.calculate("3 ms / 1 s").unwrap(), 0.003);
+		assert_eq!(calc.calculate("sin (90 deg)").unwrap(), 1.0);
+		assert_eq!(
+			calc.calculate("3 m + 2 kg"),
+			Err(Error::DimensionMismatch {
+				left: "m".to_owned(),
+				right: "kg".to_owned(),
+				start: 4,
+				end: 4,
+			})
+		);
+		// errors of function calls and powers point at the function name or the operator
+		assert_eq!(
+			calc.calculate("1 + sum(1 m, 2 kg)"),
+			Err(Error::DimensionMismatch {
+				left: "m".to_owned(),
+				right: "kg".to_owned(),
+				start: 4,
+				end: 6,
+			})
+		);
+		assert_eq!(
+			calc.calculate("[1, 2] ** [1, 2, 3]"),
+			Err(Error::ShapeMismatch {
+				left: vec![2],
+				right: vec![3],
+				start: 7,
+				end: 8,
+			})
+		);
+		assert_eq!(
+			calc.calculate("sin (2 m)"),
+			Err(Error::Runtime("Expected a dimensionless number!"))
+		);
+
+		// units do not collide with variables
+		calc.calculate("m = 3").unwrap();
+		assert_eq!(calc.calculate("2 m * m").unwrap().to_string(), "6 m");
+
+		let mut calc = Calculator::<Decimal>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("0.1 km + 0.2 m").unwrap().to_string(),
+			"0.1002 km"
+		);
+	}
+
+	#[test]
+	fn test_17_unit_conversion() {
+		let mut calc = Calculator::<Rational>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("60 mph to km/h").unwrap().to_string(),
+			"301752/3125 km/h"
+		);
+
+		let mut calc = Calculator::<Decimal>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("300 K to degC").unwrap().to_string(),
+			"26.85 degC"
+		);
+		assert_eq!(
+			calc.calculate("212 degF in degC").unwrap().to_string(),
+			"100 degC"
+		);
+		assert_eq!(
+			calc.calculate("(1 km to m) + 5 m").unwrap().to_string(),
+			"1005 m"
+		);
+		assert_eq!(
+			calc.calculate("9.81 kg m s**-2 to N").unwrap().to_string(),
+			"9.81 N"
+		);
+		assert_eq!(
+			calc.calculate("1 km/h to m/s").unwrap().to_string(),
+			"0.2777777777777777777777777778 m/s"
+		);
+		assert_eq!(
+			calc.calculate("3.6 km/h to m/s").unwrap().to_string(),
+			"1 m/s"
+		);
+		assert_eq!(
+			calc.calculate("1 degC/s to K/s").unwrap().to_string(),
+			"1 K/s"
+		);
+		assert_eq!(calc.calculate("6 m/2").unwrap().to_string(), "3 m");
+
+		assert_eq!(
+			calc.calculate("1 m to s"),
+			Err(Error::DimensionMismatch {
+				left: "m".to_string(),
+				right: "s".to_string(),
+				start: 4,
+				end: 5
+			})
+		);
+		assert_eq!(
+			calc.calculate("1 m to foo"),
+			Err(Error::UnitNotFound {
+				unit: "foo".to_string(),
+				start: 7,
+				end: 9
+			})
+		);
+
+		// `to` and `in` are still valid variable names
+		calc.calculate("in = 2").unwrap();
+		assert_eq!(calc.calculate("in * 3").unwrap(), Decimal::from(6));
+
+		calc.add_unit("furlong", "201.168 m").unwrap();
+		assert_eq!(
+			calc.calculate("2 furlong in km").unwrap().to_string(),
+			"0.402336 km"
+		);
+		assert_eq!(
+			calc.add_affine_unit("degX", "2 K", "1 m"),
+			Err(Error::Runtime(
+				"Offset must have the same dimension as the unit!"
+			))
+		);
+	}
+
+	#[test]
+	fn test_18_booleans() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(calc.calculate("true").unwrap(), Value::Bool(true));
+		assert_eq!(calc.calculate("!true").unwrap(), Value::Bool(false));
+		assert_eq!(calc.calculate("2 + 3 == 5").unwrap(), Value::Bool(true));
+		assert_eq!(calc.calculate("2 != 2").unwrap(), Value::Bool(false));
+		assert_eq!(calc.calculate("2 ** 3 < 3 * 3").unwrap(), Value::Bool(true));
+		assert_eq!(
+			calc.calculate("4 <= 4 && 5 >= 6").unwrap(),
+			Value::Bool(false)
+		);
+		assert_eq!(
+			calc.calculate("1 > 2 || 3 > 2 && true").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("!(1 > 2) == true").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(calc.calculate("1 km > 999 m").unwrap(), Value::Bool(true));
+
+		calc.calculate("x = 7").unwrap();
+		calc.calculate("y = 2").unwrap();
+		assert_eq!(
+			calc.calculate("ok = x > 5 && y <= 3").unwrap().to_string(),
+			"true"
+		);
+		assert_eq!(calc.calculate("ok || false").unwrap(), Value::Bool(true));
+
+		// the right operand is only evaluated if it decides the result
+		calc.calculate("x = 0").unwrap();
+		assert_eq!(
+			calc.calculate("x != 0 && 1/x > 2").unwrap(),
+			Value::Bool(false)
+		);
+		assert_eq!(
+			calc.calculate("true || 1/0 > 0").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("false && 1/0 > 0 || true").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("true || 1/0 > 0 && false").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("if x == 0 || 1/x > 2 then 1 else 2")
+				.unwrap(),
+			1.0
+		);
+		assert_eq!(
+			calc.calculate("false && (if true then 1/0 else 0) > 0 || x == 0")
+				.unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("false && true || 1 && true"),
+			Err(Error::Runtime("Expected a boolean value!"))
+		);
+
+		assert_eq!(
+			calc.calculate("1 + true"),
+			Err(Error::Runtime("Expected a number!"))
+		);
+		assert_eq!(
+			calc.calculate("1 && true"),
+			Err(Error::Runtime("Expected a boolean value!"))
+		);
+		assert_eq!(
+			calc.calculate("1 m < 2 s"),
+			Err(Error::DimensionMismatch {
+				left: "m".to_string(),
+				right: "s".to_string(),
+				start: 4,
+				end: 4
+			})
+		);
+		assert!(matches!(
+			calc.calculate("1 < 2 < 3"),
+			Err(Error::UnexpectedToken { start: 6, .. })
+		));
+
+		let mut calc = Calculator::<Rational>::default();
+		assert_eq!(calc.calculate("1/3 < 0.34").unwrap(), Value::Bool(true));
+		assert_eq!(calc.calculate("2/6 == 1/3").unwrap(), Value::Bool(true));
+
+		let mut calc = Calculator::<Complex>::default();
+		calc.init_std();
+		assert_eq!(calc.calculate("i * i == -1").unwrap(), Value::Bool(true));
+		assert_eq!(
+			calc.calculate("i < 1"),
+			Err(Error::Runtime("Numbers are not comparable!"))
+		);
+
+		let mut calc = Calculator::<Interval>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("interval 1 2 < 3").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("interval 1 2 < 1.5"),
+			Err(Error::Runtime(
+				"Order of the overlapping intervals is not defined!"
+			))
+		);
+	}
+
+	#[test]
+	fn test_19_conditionals() {
+		let mut calc = Calculator::new();
+		calc.add_var("x", 0.0);
+		assert_eq!(calc.calculate("if x == 0 then 0 else 1 / x").unwrap(), 0.0);
+		calc.add_var("x", 4.0);
+		assert_eq!(calc.calculate("if x == 0 then 0 else 1 / x").unwrap(), 0.25);
+
+		// only the taken branch is evaluated
+		assert_eq!(
+			calc.calculate("2 * (if x > 0 then x else 1 / 0) + 1")
+				.unwrap(),
+			9.0
+		);
+		assert_eq!(
+			calc.calculate("if x < 0 then undefined else x + 1")
+				.unwrap(),
+			5.0
+		);
+		assert_eq!(
+			calc.calculate("if x > 5 then 1 else if x > 3 then 2 else 3")
+				.unwrap(),
+			2.0
+		);
+		assert_eq!(
+			calc.calculate("if x > 3 then (if x > 5 then 1 else 2) else 3")
+				.unwrap(),
+			2.0
+		);
+		assert_eq!(calc.calculate("y = if x > 3 then x else 0").unwrap(), 4.0);
+
+		// errors of the taken branch keep their position
+		assert_eq!(
+			calc.calculate("if x > 0 then undefined else 1"),
+			Err(Error::VariableNotFound {
+				var: "undefined".to_string(),
+				start: 14,
+				end: 22
+			})
+		);
+		assert_eq!(
+			calc.calculate("if x then 1 else 2"),
+			Err(Error::Runtime("Expected a boolean value!"))
+		);
+		assert_eq!(
+			calc.calculate("if x > 0 then 1 2"),
+			Err(Error::UnexpectedToken {
+				token: "2".to_string(),
+				start: 16,
+				end: 16
+			})
+		);
+		assert_eq!(
+			calc.calculate("if x > 0 then 1"),
+			Err(Error::UnexpectedEndOfInput)
+		);
+	}
+
+	#[test]
+	fn test_20_lists() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("[1, 2, 3]").unwrap().to_string(),
+			"[1, 2, 3]"
+		);
+		assert_eq!(calc.calculate("[]").unwrap(), Value::List(vec![]));
+		assert_eq!(
+			calc.calculate("[1, 2, 3] + [10, 20, 30]")
+				.unwrap()
+				.to_string(),
+			"[11, 22, 33]"
+		);
+		assert_eq!(
+			calc.calculate("2 * [1, 2] - 1").unwrap().to_string(),
+			"[1, 3]"
+		);
+		assert_eq!(
+			calc.calculate("[4, 9] / [2, 3]").unwrap().to_string(),
+			"[2, 3]"
+		);
+		assert_eq!(calc.calculate("-[1, -2]").unwrap().to_string(), "[-1, 2]");
+		assert_eq!(
+			calc.calculate("[1 + 1, if true then 3 else 1 / 0]")
+				.unwrap()
+				.to_string(),
+			"[2, 3]"
+		);
+
+		// lists are stored in variables and can be indexed
+		calc.calculate("v = [3, 4, 12]").unwrap();
+		assert_eq!(calc.calculate("v[0]").unwrap(), 3.0);
+		assert_eq!(calc.calculate("v[1 + 1] * 2").unwrap(), 24.0);
+		assert_eq!(calc.calculate("[[1, 2], [3, 4]][1][0]").unwrap(), 3.0);
+		assert_eq!(
+			calc.calculate("v[3]"),
+			Err(Error::Runtime("Index out of bounds!"))
+		);
+		assert_eq!(
+			calc.calculate("v[0.5]"),
+			Err(Error::Runtime("Index must be a non-negative integer!"))
+		);
+
+		assert_eq!(calc.calculate("sum v").unwrap(), 19.0);
+		assert_eq!(calc.calculate("sum []").unwrap(), 0.0);
+		assert_eq!(calc.calculate("mean [1, 2, 6]").unwrap(), 3.0);
+		assert_eq!(calc.calculate("len v").unwrap(), 3.0);
+		assert_eq!(calc.calculate("dot [1, 2, 3] [4, 5, 6]").unwrap(), 32.0);
+		assert_eq!(calc.calculate("norm v").unwrap(), 13.0);
+		// an index must directly follow the value, otherwise the list is another argument
+		assert_eq!(calc.calculate("dot v [1, 0, 0]").unwrap(), 3.0);
+		assert_eq!(
+			calc.calculate("[1, 2] == [1, 2]").unwrap(),
+			Value::Bool(true)
+		);
+
+		assert_eq!(
+			calc.calculate("sum [1 m, 50 cm] to cm")
+				.unwrap()
+				.to_string(),
+			"150 cm"
+		);
+		assert_eq!(
+			calc.calculate("[1, 2] km to m").unwrap().to_string(),
+			"[1000 m, 2000 m]"
+		);
+
+		assert_eq!(
+			calc.calculate("[1, 2] + [1, 2, 3]"),
+			Err(Error::ShapeMismatch {
+				left: vec![2],
+				right: vec![3],
+				start: 7,
+				end: 7
+			})
+		);
+		assert_eq!(
+			calc.calculate("1 + dot([1, 2], [1, 2, 3])"),
+			Err(Error::ShapeMismatch {
+				left: vec![2],
+				right: vec![3],
+				start: 4,
+				end: 6
+			})
+		);
+		assert_eq!(
+			calc.calculate("sin [1, 2]"),
+			Err(Error::Runtime("Expected a number!"))
+		);
+		assert_eq!(
+			calc.calculate("len 4"),
+			Err(Error::Runtime("Expected a list!"))
+		);
+		assert_eq!(calc.calculate("[1, 2"), Err(Error::UnexpectedEndOfInput));
+
+		let mut calc = Calculator::<Complex>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("norm [3i, 4]").unwrap(),
+			Complex::new(5.0, 0.0)
+		);
+	}
+
+	#[test]
+	fn test_21_matrices() {
+		let mut calc = Calculator::<Rational>::default();
+		calc.init_std();
+		calc.calculate("A = [[4, 7], [2, 6]]").unwrap();
+		assert!(matches!(calc.calculate("A").unwrap(), Value::Matrix(_)));
+		assert_eq!(calc.calculate("det A").unwrap().to_string(), "10");
+		assert_eq!(
+			calc.calculate("inverse A").unwrap().to_string(),
+			"[[3/5, -7/10], [-1/5, 2/5]]"
+		);
+		assert_eq!(
+			calc.calculate("A * inverse A == identity 2").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("A * [[1, 0], [0, 2]]").unwrap().to_string(),
+			"[[4, 14], [2, 12]]"
+		);
+		assert_eq!(
+			calc.calculate("transpose A").unwrap().to_string(),
+			"[[4, 2], [7, 6]]"
+		);
+		assert_eq!(
+			calc.calculate("2 * A - A").unwrap().to_string(),
+			"[[4, 7], [2, 6]]"
+		);
+		assert_eq!(
+			calc.calculate("A ** 2").unwrap().to_string(),
+			"[[30, 70], [20, 50]]"
+		);
+		assert_eq!(calc.calculate("A[1][0]").unwrap().to_string(), "2");
+
+		// lists are used as column vectors
+		assert_eq!(calc.calculate("A * [1, 1]").unwrap().to_string(), "[11, 8]");
+		assert_eq!(
+			calc.calculate(r"A \ [11, 8]").unwrap().to_string(),
+			"[1, 1]"
+		);
+		assert_eq!(calc.calculate(r"6 \ 3").unwrap().to_string(), "1/2");
+
+		assert_eq!(
+			calc.calculate("A * [[1, 2, 3]]"),
+			Err(Error::ShapeMismatch {
+				left: vec![2, 2],
+				right: vec![1, 3],
+				start: 2,
+				end: 2
+			})
+		);
+		assert_eq!(
+			calc.calculate("A + [1, 2]"),
+			Err(Error::ShapeMismatch {
+				left: vec![2, 2],
+				right: vec![2],
+				start: 2,
+				end: 2
+			})
+		);
+		assert_eq!(
+			calc.calculate(r"A \ [1, 2, 3]"),
+			Err(Error::ShapeMismatch {
+				left: vec![2, 2],
+				right: vec![3, 1],
+				start: 2,
+				end: 2
+			})
+		);
+		assert_eq!(
+			calc.calculate("inverse [[1, 2], [2, 4]]"),
+			Err(Error::Runtime("Matrix is singular!"))
+		);
+		assert_eq!(
+			calc.calculate("det [[1, 2, 3], [4, 5, 6]]"),
+			Err(Error::Runtime("Matrix is not square!"))
+		);
+
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate(r"[[2, 1], [1, 1]] \ [3, 2]")
+				.unwrap()
+				.to_string(),
+			"[1, 1]"
+		);
+
+		// determinants of integer matrices only need exact divisions
+		let mut calc = Calculator::<Integer>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("det [[1, 2], [3, 4]]").unwrap().to_string(),
+			"-2"
+		);
+		assert_eq!(
+			calc.calculate("det [[2, 3, 1], [4, 1, 5], [6, 2, 7]]")
+				.unwrap()
+				.to_string(),
+			"2"
+		);
+	}
+
+	#[test]
+	fn test_22_dates_and_times() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		let eval = |calc: &mut Calculator, input| calc.calculate(input).unwrap().to_string();
+		assert_eq!(eval(&mut calc, "2026-12-24 - 2026-10-17"), "68 d");
+		assert_eq!(eval(&mut calc, "2026-10-17 + 90 days"), "2027-01-15");
+		assert_eq!(eval(&mut calc, "2024-02-28 + 1 day"), "2024-02-29");
+		assert_eq!(eval(&mut calc, "2026-10-17 + 1h30m"), "2026-10-17T01:30");
+		assert_eq!(eval(&mut calc, "2026-10-17T14:30 - 2026-10-17"), "870 min");
+		assert_eq!(
+			eval(&mut calc, "2026-10-17 + 14:30:15"),
+			"2026-10-17T14:30:15"
+		);
+		assert_eq!(eval(&mut calc, "23:30 + 45 min"), "00:15");
+		assert_eq!(eval(&mut calc, "17:00 - 9:30"), "450 min");
+		assert_eq!(eval(&mut calc, "1h30m to min"), "90 min");
+		assert_eq!(eval(&mut calc, "2d12h"), "60 h");
+		assert_eq!(eval(&mut calc, "0000-01-01 - 1 day"), "-0001-12-31");
+		assert_eq!(eval(&mut calc, "2026-01-31 + 1 month"), "2026-02-28");
+		assert_eq!(eval(&mut calc, "2024-02-29 + 1 year"), "2025-02-28");
+		assert_eq!(
+			eval(&mut calc, "2026-10-17T14:30 - 10 months"),
+			"2025-12-17T14:30"
+		);
+		assert_eq!(eval(&mut calc, "1 year to d"), "365.2425 d");
+		assert_eq!(eval(&mut calc, "2026-10-17 < 2026-12-24"), "true");
+		assert_eq!(eval(&mut calc, "2026-10-17T00:00 == 2026-10-17"), "true");
+
+		let today = calc.calculate("today").unwrap();
+		assert!(matches!(today, Value::Date(_)));
+		assert!(matches!(
+			calc.calculate("now + 90 days").unwrap(),
+			Value::DateTime(_)
+		));
+		assert_eq!(calc.calculate("now >= today").unwrap(), Value::Bool(true));
+
+		assert_eq!(
+			calc.calculate("2026-02-30 + 1 day"),
+			Err(Error::UnexpectedToken {
+				token: "2026-02-30".to_owned(),
+				start: 0,
+				end: 9
+			})
+		);
+		assert_eq!(
+			calc.calculate("12:60"),
+			Err(Error::UnexpectedToken {
+				token: "12:60".to_owned(),
+				start: 0,
+				end: 4
+			})
+		);
+		assert_eq!(
+			calc.calculate("2026-10-17 + 3 m"),
+			Err(Error::Runtime("Expected a duration!"))
+		);
+		assert_eq!(
+			calc.calculate("2026-10-17 + 1.5 months"),
+			Err(Error::Runtime(
+				"Months and years must be whole numbers to move a date!"
+			))
+		);
+		assert_eq!(
+			calc.calculate("2026-10-17 + 2026-10-17"),
+			Err(Error::Runtime(
+				"Operation is not defined for dates and times!"
+			))
+		);
+		assert_eq!(
+			calc.calculate("2 * 14:30"),
+			Err(Error::Runtime("Expected a number!"))
+		);
+	}
+
+	#[test]
+	fn test_23_user_functions() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("f(x) = x**2 + 1").unwrap().to_string(),
+			"f(x) = x**2 + 1"
+		);
+		assert_eq!(calc.calculate("f(3)").unwrap(), 10.0);
+		assert_eq!(calc.calculate("f 3 * 2").unwrap(), 20.0);
+		calc.calculate("area(w, h) = w * h").unwrap();
+		assert_eq!(calc.calculate("area(2, f(1))").unwrap(), 4.0);
+		assert_eq!(calc.calculate("polar(2, 0) + sin(0)").unwrap(), 2.0);
+
+		// parameters don't leak into the environment
+		calc.calculate("x = 5").unwrap();
+		assert_eq!(calc.calculate("f(1) + x").unwrap(), 7.0);
+
+		calc.calculate("fib(n) = if n < 2 then n else fib(n - 1) + fib(n - 2)")
+			.unwrap();
+		assert_eq!(calc.calculate("fib(10)").unwrap(), 55.0);
+		calc.calculate("loop(n) = loop(n + 1)").unwrap();
+		assert_eq!(
+			calc.calculate("loop(0)"),
+			Err(Error::Runtime("Maximum recursion depth exceeded!"))
+		);
+
+		// deeply nested input results in an error instead of a stack overflow
+		assert_eq!(
+			calc.calculate(&format!("{}1", "-".repeat(200))),
+			Err(Error::Runtime("Maximum nesting depth exceeded!"))
+		);
+		let brackets = format!("{}1{}", "(".repeat(400), ")".repeat(400));
+		let result = std::thread::Builder::new()
+			.stack_size(8 << 20)
+			.spawn(move || Calculator::<f64>::new().calculate(&brackets))
+			.unwrap()
+			.join()
+			.unwrap();
+		assert_eq!(
+			result,
+			Err(Error::Runtime("Maximum nesting depth exceeded!"))
+		);
+		assert_eq!(
+			calc.calculate("area(1)"),
+			Err(Error::Runtime("Too few arguments!"))
+		);
+		assert_eq!(
+			calc.calculate("sin(1, 2)"),
+			Err(Error::Runtime("Too many arguments!"))
+		);
+	}
+
+	#[test]
+	fn test_24_derivatives() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		calc.calculate("x = 2").unwrap();
+		assert_eq!(calc.calculate("diff(x**3, x)").unwrap(), 12.0);
+		assert_eq!(calc.calculate("diff(x**2 * sin x, x)").unwrap(), {
+			let x = 2.0f64;
+			2.0 * x * x.sin() + x * x * x.cos()
+		});
+		assert_eq!(
+			calc.calculate("diff(exp(2 * x), x)").unwrap(),
+			2.0 * 4f64.exp()
+		);
+		assert_eq!(calc.calculate("diff(3, x)").unwrap(), 0.0);
+		assert_eq!(calc.calculate("diff(sin, 0)").unwrap(), 1.0);
+		assert_eq!(
+			calc.calculate("diff(x**x, x)").unwrap(),
+			4.0 * (2f64.ln() + 1.0)
+		);
+
+		calc.calculate("f(t) = t**2 / 2").unwrap();
+		calc.calculate("g(t) = f(3 * t)").unwrap();
+		assert_eq!(calc.calculate("diff(f, 3)").unwrap(), 3.0);
+		assert_eq!(calc.calculate("diff(g, 1)").unwrap(), 9.0);
+		assert_eq!(calc.calculate("diff(f(x) + x, x)").unwrap(), 3.0);
+		assert_eq!(
+			calc.calculate("diff([x, x**2], x)").unwrap().to_string(),
+			"[1, 4]"
+		);
+
+		// the derivative of a quantity is divided by the unit of the variable
+		calc.calculate("t = 2 s").unwrap();
+		assert_eq!(
+			calc.calculate("diff(5 m s**-2 * t**2 / 2, t)")
+				.unwrap()
+				.to_string(),
+			"10 m/s"
+		);
+
+		let mut calc = Calculator::<Rational>::default();
+		calc.init_std();
+		calc.calculate("x = 1/3").unwrap();
+		assert_eq!(calc.calculate("diff(1 / x, x)").unwrap().to_string(), "-9");
+
+		assert_eq!(
+			calc.calculate("diff(y**2, y)"),
+			Err(Error::VariableNotFound {
+				var: "y".to_owned(),
+				start: 11,
+				end: 11
+			})
+		);
+		assert_eq!(
+			calc.calculate("diff(diff(x**2, x), x)"),
+			Err(Error::Runtime("Derivatives can't be nested!"))
+		);
+		assert_eq!(
+			calc.calculate("diff(x < 1, x)"),
+			Err(Error::Runtime("Only numbers can be differentiated!"))
+		);
+	}
+
+	#[test]
+	fn test_25_symbolic_derivatives() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+
+		assert_eq!(
+			calc.derive("x**2 * sin x", "x").unwrap(),
+			"2*x*sin x + x**2*cos x"
+		);
+		assert_eq!(
+			calc.derive("a*x**3 - 4*x + 7", "x").unwrap(),
+			"3*a*x**2 - 4"
+		);
+		assert_eq!(calc.derive("1 / x", "x").unwrap(), "-1/x**2");
+		assert_eq!(calc.derive("exp(2*x)", "x").unwrap(), "2*exp(2*x)");
+		assert_eq!(calc.derive("x**x", "x").unwrap(), "x**x*(ln x + 1)");
+		assert_eq!(calc.derive("x * y", "y").unwrap(), "x");
+
+		calc.calculate("f(t) = t**3 + 2*t").unwrap();
+		assert_eq!(calc.derive("f(2*x)", "x").unwrap(), "6*(2*x)**2 + 4");
+
+		let mut calc = Calculator::<Rational>::default();
+		calc.init_std();
+		assert_eq!(calc.derive("x // 2", "x").unwrap(), "1/(2*x**(1/2))");
+		assert_eq!(calc.derive("x**3 / 6", "x").unwrap(), "x**2/2");
+
+		assert_eq!(
+			calc.derive("abs x", "x"),
+			Err(Error::Runtime("Function can't be differentiated!"))
+		);
+		assert_eq!(
+			calc.derive("x % 2", "x"),
+			Err(Error::Runtime("Remainders can't be differentiated!"))
+		);
+
+		// the input has the same grammar as the evaluation
+		let mut calc = Calculator::new();
+		calc.init_std();
+		calc.calculate("x = 2").unwrap();
+		assert_eq!(calc.derive("6x**2", "x").unwrap(), "12*x");
+		assert_eq!(calc.derive("[x**2, 3 \\ x]", "x").unwrap(), "[2*x, 1/3]");
+		assert_eq!(
+			calc.derive("x * 3 km + x * 1 m", "x").unwrap(),
+			"3001/1000 km"
+		);
+	}
+
+	#[test]
+	fn test_26_simplification() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		calc.calculate("x = 5").unwrap();
+
+		assert_eq!(calc.simplify("x * 1 + 0 + 2*x").unwrap(), "3*x");
+		assert_eq!(calc.simplify("(x + 1)*(x + 1)/(x + 1)").unwrap(), "x + 1");
+		assert_eq!(calc.simplify("x**2 * x / x**4").unwrap(), "1/x");
+		assert_eq!(calc.simplify("sin(x*0 + x) * 2**3").unwrap(), "8*sin x");
+		assert_eq!(calc.simplify("2 - 3 * 4").unwrap(), "-10");
+
+		let calc = Calculator::<Rational>::default();
+		assert_eq!(calc.simplify("x/3 + x/6").unwrap(), "x/2");
+		assert_eq!(calc.simplify("6/4 * y - 1/3 + 1/2").unwrap(), "3*y/2 + 1/6");
+		assert_eq!(
+			calc.simplify("x = 2"),
+			Err(Error::UnexpectedToken {
+				token: "=".to_owned(),
+				start: 2,
+				end: 2
+			})
+		);
+	}
+
+	#[test]
+	fn test_27_polynomials() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+
+		assert_eq!(
+			calc.calculate("roots(x**3 - 6x**2 + 11x - 6)")
+				.unwrap()
+				.to_string(),
+			"[1, 2, 3]"
+		);
+		assert_eq!(
+			calc.calculate("roots([1, 0, -4])").unwrap().to_string(),
+			"[-2, 2]"
+		);
+		assert_eq!(
+			calc.calculate("roots(x**4 - 1)"),
+			Err(Error::Runtime(
+				"The polynomial has complex roots, which require complex numbers!"
+			))
+		);
+		assert_eq!(calc.calculate("2pi").unwrap(), 2.0 * std::f64::consts::PI);
+
+		calc.calculate("p = poly((z - 1)*(z + 2))").unwrap();
+		assert_eq!(calc.calculate("p").unwrap().to_string(), "z**2 + z - 2");
+		assert_eq!(calc.calculate("p(2)").unwrap(), 4.0);
+		assert_eq!(calc.calculate("diff(p, 2)").unwrap(), 5.0);
+		assert_eq!(
+			calc.calculate("p * poly(z + 1)").unwrap().to_string(),
+			"z**3 + 2*z**2 - z - 2"
+		);
+		assert_eq!(
+			calc.calculate("p / poly(z - 1)").unwrap().to_string(),
+			"z + 2"
+		);
+		assert_eq!(calc.calculate("p % poly(z)").unwrap().to_string(), "-2");
+		assert_eq!(calc.calculate("p ** 2 - p * p").unwrap().to_string(), "0");
+		calc.calculate("x = 2").unwrap();
+		assert_eq!(
+			calc.calculate("roots(x**3 - 6x**2 + 11x - 6)")
+				.unwrap()
+				.to_string(),
+			"[1, 2, 3]"
+		);
+		assert_eq!(
+			calc.calculate("poly(x**2 - 1)").unwrap().to_string(),
+			"x**2 - 1"
+		);
+		assert_eq!(
+			calc.calculate("roots(x*y)"),
+			Err(Error::Runtime("Expected a polynomial in one variable!"))
+		);
+		assert_eq!(
+			calc.calculate("p / poly(z)"),
+			Err(Error::Runtime(
+				"Division of polynomials has a remainder, use `%` for it!"
+			))
+		);
+		assert_eq!(
+			calc.calculate("roots(0)"),
+			Err(Error::Runtime(
+				"The zero polynomial has infinitely many roots!"
+			))
+		);
+
+		let mut calc = Calculator::<Complex>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("roots(x**2 + 4)").unwrap().to_string(),
+			"[-2i, 2i]"
+		);
+		assert_eq!(
+			calc.calculate("roots(x**4 - 1)").unwrap().to_string(),
+			"[-1, 1, -1i, 1i]"
+		);
+	}
+
+	#[test]
+	fn test_28_integration() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		let close = |val: Value<f64>, expected: f64| match val {
+			Value::Number(val) => (val - expected).abs() < 1e-9,
+			_ => false,
+		};
+
+		assert!(close(calc.calculate("integrate(sin, 0, pi)").unwrap(), 2.0));
+		assert!(close(
+			calc.calculate("integrate(x**2, x, 0, 3)").unwrap(),
+			9.0
+		));
+		assert!(close(
+			calc.calculate("integrate(exp(-(x**2)), x, -10, 10)")
+				.unwrap(),
+			std::f64::consts::PI.sqrt()
+		));
+		calc.calculate("f(t) = 1 / sqrt t").unwrap();
+		assert!(close(calc.calculate("integrate(f, 0, 4)").unwrap(), 4.0));
+		calc.calculate("p = poly(3x**2 + 1)").unwrap();
+		assert!(close(calc.calculate("integrate(p, 0, 2)").unwrap(), 10.0));
+		calc.add_fn("cube", |x: f64| x * x * x);
+		assert!(close(calc.calculate("integrate(cube, 0, 2)").unwrap(), 4.0));
+
+		// the variable is only bound during the integration
+		calc.calculate("x = 5").unwrap();
+		assert!(close(
+			calc.calculate("integrate(x * y, y, 0, 1) + x").unwrap(),
+			7.5
+		));
+		assert!(close(
+			calc.calculate("integrate(integrate(x * y, y, 0, 1), x, 0, 2)")
+				.unwrap(),
+			1.0
+		));
+
+		let integral = calc.integrate("sin x", "x", 0.0, 1.0).unwrap();
+		assert!((integral.value - (1.0 - 1f64.cos())).abs() < 1e-12);
+		assert!(integral.error < 1e-10);
+		calc.set_tolerance(1e-3);
+		let integral = calc.integrate("sqrt x", "x", 0.0, 1.0).unwrap();
+		assert!((integral.value - 2.0 / 3.0).abs() < 1e-3);
+		assert!(integral.error > 1e-10);
+
+		assert_eq!(
+			calc.calculate("integrate(1 / x, x, 0, 1)"),
+			Err(Error::Runtime("Integral doesn't converge!"))
+		);
+		assert_eq!(
+			calc.calculate("integrate(x < 1, x, 0, 1)"),
+			Err(Error::Runtime("Only numbers can be integrated!"))
+		);
+		assert_eq!(
+			calc.calculate("integrate(x, 2, 0, 1)"),
+			Err(Error::UnexpectedToken {
+				token: "2".to_owned(),
+				start: 13,
+				end: 13
+			})
+		);
+	}
+
+	#[test]
+	fn test_29_solve() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		let close = |val: Value<f64>, expected: f64| match val {
+			Value::Number(val) => (val - expected).abs() < 1e-14,
+			_ => false,
+		};
+
+		assert_eq!(calc.calculate("solve(x**2 - 4 = 0, x)").unwrap(), 2.0);
+		assert_eq!(calc.calculate("solve(x**2 = 4, x, -3)").unwrap(), -2.0);
+		assert!(close(
+			calc.calculate("solve(x**2 - 2, x)").unwrap(),
+			2f64.sqrt()
+		));
+		assert!(close(
+			calc.calculate("solve(cos x = x, x)").unwrap(),
+			0.7390851332151607
+		));
+		assert_eq!(
+			calc.calculate("solve(sin, 3)").unwrap(),
+			std::f64::consts::PI
+		);
+		assert_eq!(
+			calc.calculate("solve(sin x, x, -1, 7)")
+				.unwrap()
+				.to_string(),
+			format!("[0, {}, {}]", std::f64::consts::PI, std::f64::consts::TAU)
+		);
+
+		// the current value of the variable is the initial guess and isn't changed
+		calc.calculate("x = -10").unwrap();
+		assert_eq!(calc.calculate("solve(x**2 - 4, x)").unwrap(), -2.0);
+		assert_eq!(calc.calculate("x").unwrap(), -10.0);
+
+		// functions without derivatives are solved by bisection
+		calc.add_fn("cube", |x: f64| x * x * x);
+		assert_eq!(calc.calculate("solve(cube(y) = 8, y)").unwrap(), 2.0);
+
+		assert!(matches!(
+			calc.calculate("solve(y**2 + 1 = 0, y)"),
+			Err(Error::NoConvergence { .. })
+		));
+		// the difference is only zero for large values, because the 1 is absorbed by rounding
+		assert!(matches!(
+			calc.calculate("solve(y = y + 1, y)"),
+			Err(Error::NoConvergence { .. })
+		));
+		assert_eq!(
+			calc.calculate("solve(y < 1, y)"),
+			Err(Error::Runtime("Only equations of numbers can be solved!"))
+		);
+	}
+
+	#[test]
+	fn test_30_minimization() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		let close = |val: &Value<f64>, expected: f64| match val {
+			Value::Number(val) => (val - expected).abs() < 1e-7,
+			_ => false,
+		};
+		let result = |input: &str, calc: &mut Calculator| match calc.calculate(input).unwrap() {
+			Value::List(result) => (result[0].clone(), result[1].clone()),
+			val => panic!("Expected a list, found {}", val),
+		};
+
+		calc.calculate("f(x) = (x - 2)**2 + 1").unwrap();
+		let (x, y) = result("minimize(f, 0, 5)", &mut calc);
+		assert!(close(&x, 2.0) && close(&y, 1.0));
+		let (x, y) = result("maximize(sin, 0, 3)", &mut calc);
+		assert!(close(&x, std::f64::consts::FRAC_PI_2) && close(&y, 1.0));
+		calc.add_fn("parabola", |x: f64| x * x - 4.0 * x);
+		let (x, y) = result("minimize(parabola, -10, 10)", &mut calc);
+		assert!(close(&x, 2.0) && close(&y, -4.0));
+
+		calc.calculate("g(x, y) = (x - 1)**2 + (y + 2)**2 + 3")
+			.unwrap();
+		let (x, y) = result("minimize(g, [0, 0])", &mut calc);
+		match x {
+			Value::List(x) => assert!(close(&x[0], 1.0) && close(&x[1], -2.0)),
+			val => panic!("Expected a list, found {}", val),
+		}
+		assert!(close(&y, 3.0));
+
+		calc.calculate("h(x, y) = x + y").unwrap();
+		assert!(matches!(
+			calc.calculate("minimize(h, [0, 0])"),
+			Err(Error::NoConvergence { .. })
+		));
+		assert_eq!(
+			calc.calculate("minimize(g, 0, 1)"),
+			Err(Error::Runtime("Too few arguments!"))
+		);
+		assert_eq!(
+			calc.calculate("minimize(f, 0, 1e400)"),
+			Err(Error::Runtime("Minimization bounds must be finite!"))
+		);
+		assert_eq!(
+			calc.calculate("minimize(x, 0, 1)"),
+			Err(Error::Runtime("Expected a function!"))
+		);
+	}
+
+	#[test]
+	fn test_31_statistics() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		calc.calculate("v = [2, 4, 4, 4, 5, 5, 7, 9]").unwrap();
+		assert_eq!(calc.calculate("mean(1, 2, 6)").unwrap(), 3.0);
+		assert_eq!(calc.calculate("mean v").unwrap(), 5.0);
+		assert_eq!(calc.calculate("median(3, 1, 2, 10)").unwrap(), 2.5);
+		assert_eq!(calc.calculate("mode v").unwrap(), 4.0);
+		assert_eq!(calc.calculate("stdev(1, 3, 5)").unwrap(), 2.0);
+		assert_eq!(calc.calculate("variance(1, 3)").unwrap(), 2.0);
+		assert_eq!(calc.calculate("percentile(v, 50)").unwrap(), 4.5);
+		assert_eq!(
+			calc.calculate("percentile(1, 2, 3, 4, 5, 75)").unwrap(),
+			4.0
+		);
+		assert_eq!(calc.calculate("min(3, -1, 2)").unwrap(), -1.0);
+		assert_eq!(calc.calculate("max v").unwrap(), 9.0);
+		assert_eq!(calc.calculate("sum(1, 2, 3, 4)").unwrap(), 10.0);
+		assert_eq!(calc.calculate("product(1, 2, 3, 4)").unwrap(), 24.0);
+		assert_eq!(calc.calculate("sum()").unwrap(), 0.0);
+		assert_eq!(calc.calculate("mean([1, 2], 6)").unwrap(), 3.0);
+		assert_eq!(calc.calculate("mean [[1, 2], [3, 6]]").unwrap(), 3.0);
+		assert_eq!(calc.calculate("sum(1, [2, 3])").unwrap(), 6.0);
+		assert_eq!(
+			calc.calculate("percentile(v, 101)"),
+			Err(Error::Runtime("Percentile must be between 0 and 100!"))
+		);
+		assert_eq!(
+			calc.calculate("max(1 km, 300 m) to m").unwrap().to_string(),
+			"1000 m"
+		);
+		assert!(matches!(
+			calc.calculate("min(1 m, 2 s)"),
+			Err(Error::DimensionMismatch { .. })
+		));
+		assert_eq!(
+			calc.calculate("variance 1"),
+			Err(Error::Runtime("Variance needs at least two values!"))
+		);
+
+		// user functions can be variadic and take more than three arguments
+		calc.add_fn("count", |args: Vec<Value<f64>>| args.len() as f64);
+		calc.add_fn("weighted", |w: f64, a: f64, b: f64, c: f64, d: f64| {
+			w * (a + b + c + d)
+		});
+		assert_eq!(calc.calculate("count(1, [2, 3], 4)").unwrap(), 3.0);
+		assert_eq!(calc.calculate("count()").unwrap(), 0.0);
+		assert_eq!(calc.calculate("weighted(2, 1, 2, 3, 4)").unwrap(), 20.0);
+
+		let mut calc = Calculator::<Rational>::default();
+		calc.init_std();
+		assert_eq!(calc.calculate("mean(1, 2, 2)").unwrap().to_string(), "5/3");
+		assert_eq!(
+			calc.calculate("percentile(1, 2, 10)").unwrap().to_string(),
+			"11/10"
+		);
+	}
+
+	#[test]
+	fn test_32_number_literals() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(calc.calculate("0xFF + 0b1010 + 0o10").unwrap(), 273.0);
+		assert_eq!(calc.calculate("6.02e23").unwrap(), 6.02e23);
+		assert_eq!(calc.calculate("1_000 * .5 + 5.").unwrap(), 505.0);
+		assert_eq!(calc.calculate("2e-3 km to m").unwrap().to_string(), "2 m");
+		assert_eq!(calc.calculate("2e").unwrap(), 2.0 * std::f64::consts::E);
+		assert_eq!(
+			calc.calculate("3 * 0o78"),
+			Err(Error::InvalidNumber {
+				literal: "0o78".to_owned(),
+				start: 4,
+				end: 7
+			})
+		);
+
+		let mut calc = Calculator::<Integer>::default();
+		assert_eq!(
+			calc.calculate("0xFFFF_FFFF_FFFF_FFFF_FFFF")
+				.unwrap()
+				.to_string(),
+			"1208925819614629174706175"
+		);
+		assert_eq!(calc.calculate("1.5e3").unwrap().to_string(), "1500");
+
+		assert_eq!(
+			calc.calculate("1e999999999"),
+			Err(Error::Runtime("Number is out of range!"))
+		);
+
+		let mut calc = Calculator::<Rational>::default();
+		assert_eq!(calc.calculate(".1e-1").unwrap().to_string(), "1/100");
+		assert_eq!(
+			calc.calculate("1e-999999999 + 1"),
+			Err(Error::Runtime("Number is out of range!"))
+		);
+
+		let mut calc = Calculator::<Complex>::default();
+		assert_eq!(calc.calculate(".5i").unwrap(), Complex::new(0.0, 0.5));
+	}
+
+	#[test]
+	fn test_33_bitwise_operators() {
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(calc.calculate("0b1100 & 0b1010").unwrap(), 8.0);
+		assert_eq!(calc.calculate("0b1100 | 0b1010").unwrap(), 14.0);
+		assert_eq!(calc.calculate("0b1100 ^ 0b1010").unwrap(), 6.0);
+		assert_eq!(calc.calculate("~5").unwrap(), -6.0);
+		assert_eq!(calc.calculate("-8 >> 1").unwrap(), -4.0);
+		assert_eq!(calc.calculate("-7 >> 1").unwrap(), -4.0);
+		assert_eq!(calc.calculate("-1 & 0xFF").unwrap(), 255.0);
+
+		// shifts bind weaker than addition and `|` weaker than `^` and `&`
+		assert_eq!(calc.calculate("1 << 2 + 1").unwrap(), 8.0);
+		assert_eq!(calc.calculate("1 | 6 ^ 3 & 5").unwrap(), 7.0);
+		assert_eq!(
+			calc.calculate("(1 << 4) - 1 == 0xF").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(
+			calc.calculate("6 & 3 == 2 && true").unwrap(),
+			Value::Bool(true)
+		);
+
+		assert_eq!(
+			calc.calculate("1.5 & 1"),
+			Err(Error::Runtime(
+				"Bitwise operators are only defined for integers!"
+			))
+		);
+		assert_eq!(
+			calc.calculate("~true"),
+			Err(Error::Runtime(
+				"Bitwise operators are only defined for integers!"
+			))
+		);
+		assert_eq!(
+			calc.calculate("1 << -1"),
+			Err(Error::Runtime("Shift amount must not be negative!"))
+		);
+		assert_eq!(
+			calc.calculate("1 << 100000"),
+			Err(Error::Runtime("Shift amount is too large!"))
+		);
+
+		let mut calc = Calculator::<Integer>::default();
+		assert_eq!(
+			calc.calculate("1 << 100 | 1").unwrap().to_string(),
+			"1267650600228229401496703205377"
+		);
+		assert_eq!(
+			calc.calculate("(1 << 100) >> 99 ^ 3").unwrap().to_string(),
+			"1"
+		);
+
+		let mut calc = Calculator::<Rational>::default();
+		assert_eq!(calc.calculate("12 / 4 << 1").unwrap().to_string(), "6");
+		assert_eq!(
+			calc.calculate("1 / 3 & 1"),
+			Err(Error::Runtime(
+				"Bitwise operators are only defined for integers!"
+			))
+		);
+	}
+	#[test]
+	fn test_34_fixed_width_integers() {
+		let mut calc = Calculator::<Word>::default();
+		calc.init_std();
+		let word = |val: Value<Word>| val.into_number().unwrap();
+		assert_eq!(
+			word(calc.calculate("u8(300)").unwrap()),
+			Word::new(44, WordType::U8)
+		);
+		assert_eq!(
+			word(calc.calculate("i8(200)").unwrap()),
+			Word::new(-56, WordType::I8)
+		);
+		assert_eq!(
+			word(calc.calculate("u8(255) + 1").unwrap()),
+			Word::new(0, WordType::U8)
+		);
+		assert_eq!(
+			word(calc.calculate("0 - u16(1)").unwrap()),
+			Word::new(65535, WordType::U16)
+		);
+		assert_eq!(
+			word(calc.calculate("i16(i8(-1))").unwrap()),
+			Word::new(-1, WordType::I16)
+		);
+		assert_eq!(
+			word(calc.calculate("u16(i8(-1))").unwrap()),
+			Word::new(65535, WordType::U16)
+		);
+		assert_eq!(
+			word(calc.calculate("~u8(0)").unwrap()),
+			Word::new(255, WordType::U8)
+		);
+		assert_eq!(
+			word(calc.calculate("u8(1) << 8").unwrap()),
+			Word::new(0, WordType::U8)
+		);
+		assert_eq!(
+			word(calc.calculate("i8(-128) >> 7").unwrap()),
+			Word::new(-1, WordType::I8)
+		);
+		assert_eq!(
+			word(calc.calculate("u8(128) >> 7").unwrap()),
+			Word::new(1, WordType::U8)
+		);
+		assert_eq!(
+			word(calc.calculate("i32(-1) * u8(3)").unwrap()),
+			Word::new(-3, WordType::I32)
+		);
+		assert_eq!(
+			word(calc.calculate("-7 / 2").unwrap()),
+			Word::new(-3, WordType::I64)
+		);
+		assert_eq!(
+			word(calc.calculate("2 ** 63").unwrap()),
+			Word::new(i64::MIN as i128, WordType::I64)
+		);
+		assert_eq!(
+			calc.calculate("u8(100) > i8(-1)").unwrap(),
+			Value::Bool(true)
+		);
+		assert_eq!(calc.calculate("u8(3) == 3").unwrap(), Value::Bool(true));
+		assert_eq!(calc.calculate("u8(3) != 3").unwrap(), Value::Bool(false));
+		assert_eq!(calc.calculate("u8(255) == -1").unwrap(), Value::Bool(true));
+		assert_eq!(calc.calculate("3 != i8(4)").unwrap(), Value::Bool(true));
+
+		let val = word(calc.calculate("i8(-2)").unwrap());
+		assert_eq!(val.word_type().to_string(), "i8");
+		assert_eq!(format!("{:#x} {:#b}", val, val), "0xfe 0b11111110");
+
+		// the context is the type of literals
+		let mut calc = Calculator::<Word>::with_context(WordType::U8);
+		assert_eq!(
+			word(calc.calculate("300").unwrap()),
+			Word::new(44, WordType::U8)
+		);
+
+		// casts wrap around for other number types as well
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(calc.calculate("u8(300)").unwrap(), 44.0);
+		assert_eq!(calc.calculate("i8(128)").unwrap(), -128.0);
+		assert_eq!(
+			calc.calculate("u8(1.5)"),
+			Err(Error::Runtime("Only integers can be cast!"))
+		);
+	}
+	#[test]
+	fn test_35_number_format() {
+		let format = |notation, precision| NumberFormat {
+			notation,
+			precision,
+			..Default::default()
+		};
+		let auto = NumberFormat::default();
+
+		let mut calc = Calculator::<f32>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("0.1 + 0.2").unwrap().format(&auto).unwrap(),
+			"0.3"
+		);
+		assert_eq!(
+			calc.calculate("pi")
+				.unwrap()
+				.format(&format(Notation::Plain, Precision::Fixed(2)))
+				.unwrap(),
+			"3.14"
+		);
+
+		let mut calc = Calculator::new();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("1 / 3").unwrap().format(&auto).unwrap(),
+			"0.333333333333333"
+		);
+		assert_eq!(
+			calc.calculate("4.7 kg")
+				.unwrap()
+				.format(&format(Notation::Scientific, Precision::Auto))
+				.unwrap(),
+			"4.7e0 kg"
+		);
+		assert_eq!(
+			calc.calculate("[0.1 + 0.2, [1, 2e6]]")
+				.unwrap()
+				.format(&format(Notation::Si, Precision::Auto))
+				.unwrap(),
+			"[300m, [1, 2M]]"
+		);
+		assert_eq!(
+			calc.calculate("[[1, 2], [3, 4]] / 3")
+				.unwrap()
+				.format(&format(Notation::Plain, Precision::Significant(2)))
+				.unwrap(),
+			"[[0.33, 0.67], [1.0, 1.3]]"
+		);
+		assert_eq!(
+			calc.calculate("true").unwrap().format(&auto).unwrap(),
+			"true"
+		);
+
+		let hex = NumberFormat {
+			radix: 16,
+			separator: Some('_'),
+			..Default::default()
+		};
+		assert_eq!(
+			calc.calculate("0xDEADBEEF").unwrap().format(&hex).unwrap(),
+			"0xdead_beef"
+		);
+
+		let mut calc = Calculator::<Word>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("i16(-2)").unwrap().format(&hex).unwrap(),
+			"0xfffe"
+		);
+
+		let mut calc = Calculator::<Integer>::default();
+		let separated = NumberFormat {
+			separator: Some(','),
+			..Default::default()
+		};
+		assert_eq!(
+			calc.calculate("2 ** 70")
+				.unwrap()
+				.format(&separated)
+				.unwrap(),
+			"1,180,591,620,717,411,303,424"
+		);
+
+		let mut calc =
+			Calculator::<Decimal>::with_context(DecimalContext::new(30, RoundingMode::HalfEven));
+		assert_eq!(
+			calc.calculate("2 / 3").unwrap().format(&auto).unwrap(),
+			"0.666666666666666666666666666667"
+		);
+		assert_eq!(
+			calc.calculate("1234.5")
+				.unwrap()
+				.format(&format(Notation::Engineering, Precision::Fixed(2)))
+				.unwrap(),
+			"1.23e3"
+		);
+
+		let mut calc = Calculator::<Rational>::default();
+		assert_eq!(
+			calc.calculate("1 / 4").unwrap().format(&auto).unwrap(),
+			"0.25"
+		);
+
+		let mut calc = Calculator::<Complex>::default();
+		calc.init_std();
+		assert_eq!(
+			calc.calculate("(0.1 + 0.2) - 2i")
+				.unwrap()
+				.format(&auto)
+				.unwrap(),
+			"0.3-2i"
+		);
+	}
 }
@@ -0,0 +1,183 @@
+use crate::Error;
+
+/// Maximum number of Nelder-Mead iterations per dimension.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Ratio of the golden section, `1/φ`.
+const INVERSE_PHI: f64 = 0.6180339887498949;
+
+/// Returns the precision of the arguments, which is limited to the square root of the machine precision,
+/// because functions are flat near their minimum.
+fn precision(tolerance: f64) -> f64 {
+	tolerance.max(f64::EPSILON.sqrt())
+}
+
+/// Finds a minimum in the range with the golden section search, returning the argument and the value.
+///
+/// The range is narrowed until its width is below the tolerance, for multiple local minima any of them may be found.
+pub(crate) fn golden_section(
+	f: &mut impl FnMut(f64) -> Result<f64, Error>,
+	a: f64,
+	b: f64,
+	tolerance: f64,
+) -> Result<(f64, f64), Error> {
+	if !(b - a).is_finite() {
+		return Err(Error::Runtime("Minimization bounds must be finite!"));
+	}
+	let (mut a, mut b) = (a.min(b), a.max(b));
+	let mut c = b - INVERSE_PHI * (b - a);
+	let mut d = a + INVERSE_PHI * (b - a);
+	let (mut fc, mut fd) = (finite(f(c)?), finite(f(d)?));
+	while b - a > precision(tolerance) * a.abs().max(b.abs()).max(1.0) {
+		if fc < fd {
+			(b, d, fd) = (d, c, fc);
+			c = b - INVERSE_PHI * (b - a);
+			fc = finite(f(c)?);
+		} else {
+			(a, c, fc) = (c, d, fd);
+			d = a + INVERSE_PHI * (b - a);
+			fd = finite(f(d)?);
+		}
+	}
+	let x = (a + b) / 2.0;
+	Ok((x, f(x)?))
+}
+
+/// Finds a local minimum near the start with the Nelder-Mead method, returning the arguments and the value.
+///
+/// The simplex is reflected, expanded and contracted until the function values and the size of the simplex
+/// are below the tolerance.
+pub(crate) fn nelder_mead(
+	f: &mut impl FnMut(&[f64]) -> Result<f64, Error>,
+	start: &[f64],
+	tolerance: f64,
+) -> Result<(Vec<f64>, f64), Error> {
+	let n = start.len();
+	let mut simplex = vec![(start.to_vec(), finite(f(start)?))];
+	for i in 0..n {
+		let mut x = start.to_vec();
+		x[i] = if x[i] == 0.0 { 0.00025 } else { 1.05 * x[i] };
+		let y = finite(f(&x)?);
+		simplex.push((x, y));
+	}
+
+	for _ in 0..MAX_ITERATIONS * n {
+		simplex.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+		let (best, worst) = (&simplex[0], &simplex[n]);
+		let size = simplex[1..]
+			.iter()
+			.flat_map(|(x, _)| x.iter().zip(&best.0).map(|(a, b)| (a - b).abs()))
+			.fold(0.0, f64::max);
+		let scale = best.0.iter().fold(1.0, |max: f64, x| max.max(x.abs()));
+		if worst.1 - best.1 <= tolerance * best.1.abs().max(1.0)
+			&& size <= precision(tolerance) * scale
+		{
+			let (x, y) = simplex.swap_remove(0);
+			return Ok((x, y));
+		}
+
+		// moves the worst point through the centroid of the others by the factor
+		let centroid: Vec<f64> = (0..n)
+			.map(|i| simplex[..n].iter().map(|(x, _)| x[i]).sum::<f64>() / n as f64)
+			.collect();
+		let point = |factor: f64| -> Vec<f64> {
+			centroid
+				.iter()
+				.zip(&simplex[n].0)
+				.map(|(c, w)| c + factor * (c - w))
+				.collect()
+		};
+
+		let reflected = point(1.0);
+		let fr = finite(f(&reflected)?);
+		if fr < simplex[0].1 {
+			let expanded = point(2.0);
+			let fe = finite(f(&expanded)?);
+			simplex[n] = if fe < fr {
+				(expanded, fe)
+			} else {
+				(reflected, fr)
+			};
+		} else if fr < simplex[n - 1].1 {
+			simplex[n] = (reflected, fr);
+		} else {
+			let contracted = if fr < simplex[n].1 {
+				point(0.5)
+			} else {
+				point(-0.5)
+			};
+			let fc = finite(f(&contracted)?);
+			if fc < fr.min(simplex[n].1) {
+				simplex[n] = (contracted, fc);
+			} else {
+				// shrinks the simplex towards the best point
+				let best = simplex[0].0.clone();
+				for (x, y) in &mut simplex[1..] {
+					for (x, b) in x.iter_mut().zip(&best) {
+						*x = b + 0.5 * (*x - b);
+					}
+					*y = finite(f(x)?);
+				}
+			}
+		}
+	}
+
+	simplex.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+	Err(Error::NoConvergence {
+		iterations: MAX_ITERATIONS * n,
+		x: simplex[0].0.clone(),
+		residual: simplex[n].1 - simplex[0].1,
+	})
+}
+
+/// Replaces undefined values, so they are never a minimum.
+fn finite(y: f64) -> f64 {
+	if y.is_nan() {
+		f64::INFINITY
+	} else {
+		y
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(x: f64, expected: f64) -> bool {
+		(x - expected).abs() < 1e-7
+	}
+
+	#[test]
+	fn test_01_golden_section() {
+		let (x, y) = golden_section(&mut |x| Ok((x - 2.0).powi(2) + 1.0), 0.0, 5.0, 1e-10).unwrap();
+		assert!(close(x, 2.0) && close(y, 1.0));
+
+		// minima at the bounds are found as well
+		let (x, _) = golden_section(&mut |x| Ok(x), 3.0, -1.0, 1e-10).unwrap();
+		assert!(close(x, -1.0));
+
+		let (x, y) = golden_section(&mut |x| Ok(x.cos()), 0.0, 6.0, 1e-10).unwrap();
+		assert!(close(x, std::f64::consts::PI) && close(y, -1.0));
+
+		assert_eq!(
+			golden_section(&mut |x| Ok(x), 0.0, f64::INFINITY, 1e-10),
+			Err(Error::Runtime("Minimization bounds must be finite!"))
+		);
+	}
+
+	#[test]
+	fn test_02_nelder_mead() {
+		let mut rosenbrock =
+			|x: &[f64]| Ok((1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2));
+		let (x, y) = nelder_mead(&mut rosenbrock, &[-1.2, 1.0], 1e-10).unwrap();
+		assert!(close(x[0], 1.0) && close(x[1], 1.0) && close(y, 0.0));
+
+		let (x, _) = nelder_mead(&mut |x| Ok((x[0] - 3.0).powi(2)), &[0.0], 1e-10).unwrap();
+		assert!(close(x[0], 3.0));
+
+		assert!(matches!(
+			nelder_mead(&mut |x| Ok(-x[0] - x[1]), &[0.0, 0.0], 1e-10),
+			Err(Error::NoConvergence { .. })
+		));
+	}
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+
+use num_complex::Complex64;
+
+use crate::{Error, Number, NumberFormat};
+
+/// Complex number with `f64` real and imaginary parts.
+///
+/// Imaginary literals can be written with an `i` or `j` suffix like `2i`,
+/// the imaginary unit itself is available as the variables `i` and `j`.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Complex>::default();
+/// calculator.init_std();
+///
+/// let val = calculator.calculate("(1 + 2i) * (3 - i)").unwrap();
+/// assert_eq!(val, Complex::new(5.0, 5.0));
+///
+/// let val = calculator.calculate("sqrt -4").unwrap();
+/// assert_eq!(val.to_string(), "2i");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+	pub re: f64,
+	pub im: f64,
+}
+
+impl Complex {
+	pub fn new(re: f64, im: f64) -> Self {
+		Self { re, im }
+	}
+
+	pub fn is_real(&self) -> bool {
+		self.im == 0.0
+	}
+
+	fn is_zero(&self) -> bool {
+		self.re == 0.0 && self.im == 0.0
+	}
+
+	/// Returns the real part if the number has no imaginary part.
+	fn real(self) -> Result<f64, Error> {
+		if self.is_real() {
+			Ok(self.re)
+		} else {
+			Err(Error::Runtime("Number is not a real number!"))
+		}
+	}
+}
+
+impl From<Complex64> for Complex {
+	fn from(value: Complex64) -> Self {
+		Self::new(value.re, value.im)
+	}
+}
+
+impl From<Complex> for Complex64 {
+	fn from(value: Complex) -> Self {
+		Complex64::new(value.re, value.im)
+	}
+}
+
+impl From<f64> for Complex {
+	fn from(value: f64) -> Self {
+		Self::new(value, 0.0)
+	}
+}
+
+impl fmt::Display for Complex {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.im == 0.0 {
+			write!(f, "{}", self.re)
+		} else if self.re == 0.0 {
+			write!(f, "{}i", self.im)
+		} else if self.im < 0.0 {
+			write!(f, "{}-{}i", self.re, -self.im)
+		} else {
+			write!(f, "{}+{}i", self.re, self.im)
+		}
+	}
+}
+
+/// Applies a function of `num_complex` on the given number.
+fn apply(value: Complex, fun: impl FnOnce(Complex64) -> Complex64) -> Result<Complex, Error> {
+	Ok(fun(value.into()).into())
+}
+
+impl Number for Complex {
+	type Context = ();
+
+	fn parse(src: &str, _: &()) -> Result<Self, Error> {
+		let (src, imaginary) = match src.strip_suffix(['i', 'j']) {
+			Some(src) => (src, true),
+			None => (src, false),
+		};
+		let value: f64 = src
+			.parse()
+			.map_err(|_| Error::Fatal("Cannot parse number!"))?;
+		Ok(if imaginary {
+			Self::new(0.0, value)
+		} else {
+			Self::new(value, 0.0)
+		})
+	}
+
+	fn from_f64(value: f64, _: &()) -> Result<Self, Error> {
+		Ok(value.into())
+	}
+
+	/// Returns the real part, numbers with an imaginary part result in `NaN`.
+	fn to_f64(&self) -> f64 {
+		if self.is_real() {
+			self.re
+		} else {
+			f64::NAN
+		}
+	}
+
+	fn context(&self) {}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::new(self.re + rhs.re, self.im + rhs.im))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::new(self.re - rhs.re, self.im - rhs.im))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		apply(self, |z| z * Complex64::from(rhs))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		apply(self, |z| z / Complex64::from(rhs))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok((self.real()? % rhs.real()?).into())
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_zero() {
+			return match rhs.real() {
+				Ok(exp) if exp < 0.0 => Err(Error::Runtime("Division by 0!")),
+				Ok(0.0) => Ok(1.0.into()),
+				_ => Ok(self),
+			};
+		}
+		if let Ok(exp) = rhs.real() {
+			// integer and real powers are calculated directly to avoid rounding errors
+			if exp.fract() == 0.0 && exp.abs() <= i32::MAX as f64 {
+				return apply(self, |z| z.powi(exp as i32));
+			}
+			if self.is_real() && self.re > 0.0 {
+				return Ok(self.re.powf(exp).into());
+			}
+		}
+		apply(self, |z| z.powc(rhs.into()))
+	}
+
+	/// Calculates the principal root, e.g. `-8 // 3` results in `1+1.732…i`.
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		if rhs == 2.0.into() {
+			return self.sqrt();
+		}
+		self.pow(Self::from(1.0).div(rhs)?)
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		// avoids negative zeros, so `sqrt -4` stays on the principal branch
+		Ok(Self::new(0.0 - self.re, 0.0 - self.im))
+	}
+
+	fn pi(_: &()) -> Result<Self, Error> {
+		Ok(std::f64::consts::PI.into())
+	}
+
+	fn e(_: &()) -> Result<Self, Error> {
+		Ok(std::f64::consts::E.into())
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		apply(self, Complex64::sin)
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		apply(self, Complex64::asin)
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		apply(self, Complex64::cos)
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		apply(self, Complex64::acos)
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		apply(self, Complex64::tan)
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		apply(self, Complex64::atan)
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		Ok(self.real()?.to_degrees().into())
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		Ok(self.real()?.to_radians().into())
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		apply(self, Complex64::exp)
+	}
+
+	fn ln(self) -> Result<Self, Error> {
+		if self.is_zero() {
+			return Err(Error::Runtime("Logarithm of 0 is not defined!"));
+		}
+		apply(self, Complex64::ln)
+	}
+
+	fn sqrt(self) -> Result<Self, Error> {
+		apply(self, Complex64::sqrt)
+	}
+
+	fn abs(self) -> Result<Self, Error> {
+		Ok(Complex64::from(self).norm().into())
+	}
+
+	fn arg(self) -> Result<Self, Error> {
+		Ok(Complex64::from(self).arg().into())
+	}
+
+	fn re(self) -> Result<Self, Error> {
+		Ok(self.re.into())
+	}
+
+	fn im(self) -> Result<Self, Error> {
+		Ok(self.im.into())
+	}
+
+	fn conj(self) -> Result<Self, Error> {
+		Ok(Self::new(self.re, -self.im))
+	}
+
+	fn from_polar(r: Self, phi: Self) -> Result<Self, Error> {
+		Ok(Complex64::from_polar(r.real()?, phi.real()?).into())
+	}
+
+	fn imaginary_unit(_: &()) -> Result<Self, Error> {
+		Ok(Self::new(0.0, 1.0))
+	}
+
+	/// Formats the real and imaginary part like [`Display`](fmt::Display) does.
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		let re = format.format_float(self.re, f64::DIGITS)?;
+		let im = format.format_float(self.im.abs(), f64::DIGITS)?;
+		Ok(if self.im == 0.0 {
+			re
+		} else if self.re == 0.0 {
+			format!("{}{}i", if self.im < 0.0 { "-" } else { "" }, im)
+		} else if self.im < 0.0 {
+			format!("{}-{}i", re, im)
+		} else {
+			format!("{}+{}i", re, im)
+		})
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		Ok(self.real()?.factorial()?.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn c(re: f64, im: f64) -> Complex {
+		Complex::new(re, im)
+	}
+
+	fn assert_close(left: Complex, right: Complex) {
+		assert!(
+			(left.re - right.re).abs() < 1e-12 && (left.im - right.im).abs() < 1e-12,
+			"{} != {}",
+			left,
+			right
+		);
+	}
+
+	#[test]
+	fn test_01_parse_display() {
+		assert_eq!(Complex::parse("4.5", &()).unwrap(), c(4.5, 0.0));
+		assert_eq!(Complex::parse("2i", &()).unwrap(), c(0.0, 2.0));
+		assert_eq!(Complex::parse("2.5j", &()).unwrap(), c(0.0, 2.5));
+		assert!(Complex::parse("i", &()).is_err());
+		assert_eq!(c(4.5, 0.0).to_string(), "4.5");
+		assert_eq!(c(0.0, -2.0).to_string(), "-2i");
+		assert_eq!(c(3.0, 4.0).to_string(), "3+4i");
+		assert_eq!(c(3.0, -4.0).to_string(), "3-4i");
+	}
+
+	#[test]
+	fn test_02_arithmetic() {
+		assert_eq!(c(1.0, 2.0).add(c(3.0, -1.0)).unwrap(), c(4.0, 1.0));
+		assert_eq!(c(1.0, 2.0).sub(c(3.0, -1.0)).unwrap(), c(-2.0, 3.0));
+		assert_eq!(c(1.0, 2.0).mul(c(3.0, -1.0)).unwrap(), c(5.0, 5.0));
+		assert_eq!(c(5.0, 5.0).div(c(3.0, -1.0)).unwrap(), c(1.0, 2.0));
+		assert_eq!(c(0.0, 1.0).pow(c(2.0, 0.0)).unwrap(), c(-1.0, 0.0));
+		assert_eq!(c(7.0, 0.0).rem(c(2.0, 0.0)).unwrap(), c(1.0, 0.0));
+		assert!(c(7.0, 1.0).rem(c(2.0, 0.0)).is_err());
+		assert_close(c(-8.0, 0.0).root(c(3.0, 0.0)).unwrap(), c(1.0, 3f64.sqrt()));
+		assert_close(
+			c(0.0, 1.0).pow(c(0.0, 1.0)).unwrap(),
+			c((-std::f64::consts::FRAC_PI_2).exp(), 0.0),
+		);
+	}
+
+	#[test]
+	fn test_03_functions() {
+		use std::f64::consts::PI;
+
+		assert_eq!(c(-4.0, 0.0).sqrt().unwrap(), c(0.0, 2.0));
+		assert_eq!(c(4.0, 0.0).neg().unwrap().sqrt().unwrap(), c(0.0, 2.0));
+		assert_close(c(0.0, PI).exp().unwrap(), c(-1.0, 0.0));
+		assert_close(c(-1.0, 0.0).ln().unwrap(), c(0.0, PI));
+		assert_close(c(0.0, 1.0).sin().unwrap(), c(0.0, 1f64.sinh()));
+		assert_close(c(0.0, 1.0).cos().unwrap(), c(1f64.cosh(), 0.0));
+		assert_eq!(c(3.0, 4.0).abs().unwrap(), c(5.0, 0.0));
+		assert_close(c(0.0, 2.0).arg().unwrap(), c(PI / 2.0, 0.0));
+		assert_eq!(c(3.0, 4.0).re().unwrap(), c(3.0, 0.0));
+		assert_eq!(c(3.0, 4.0).im().unwrap(), c(4.0, 0.0));
+		assert_eq!(c(3.0, 4.0).conj().unwrap(), c(3.0, -4.0));
+		assert_close(
+			Complex::from_polar(c(2.0, 0.0), c(PI / 2.0, 0.0)).unwrap(),
+			c(0.0, 2.0),
+		);
+	}
+
+	#[test]
+	fn test_04_division_by_0() {
+		assert!(c(4.0, 1.0).div(c(0.0, 0.0)).is_err());
+		assert!(c(4.0, 0.0).rem(c(0.0, 0.0)).is_err());
+		assert!(c(4.0, 0.0).root(c(0.0, 0.0)).is_err());
+		assert!(c(0.0, 0.0).pow(c(-1.0, 0.0)).is_err());
+		assert!(c(0.0, 0.0).ln().is_err());
+	}
+}
@@ -0,0 +1,1147 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use super::{exact_factorial, parse_literal};
+use crate::{Error, Number, NumberFormat};
+
+/// Number of additional digits used for intermediate results of transcendental functions.
+const GUARD_DIGITS: u32 = 10;
+
+/// Maximal number of digits an exactly calculated power, root or integer may have.
+const MAX_EXACT_DIGITS: u64 = 20_000;
+
+/// Maximal exponent of the most significant digit of arguments of trigonometric functions,
+/// the reduction by multiples of `2 * pi` needs this many additional digits.
+const MAX_REDUCTION_DIGITS: i64 = 1000;
+
+/// Rounding mode used by [`Decimal`] numbers.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum RoundingMode {
+	/// Round towards zero.
+	Down,
+	/// Round away from zero.
+	Up,
+	/// Round towards positive infinity.
+	Ceiling,
+	/// Round towards negative infinity.
+	Floor,
+	/// Round to the nearest neighbour, ties away from zero.
+	HalfUp,
+	/// Round to the nearest neighbour, ties towards zero.
+	HalfDown,
+	/// Round to the nearest neighbour, ties to the even neighbour.
+	#[default]
+	HalfEven,
+}
+
+/// Configuration of [`Decimal`] numbers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DecimalContext {
+	/// Number of significant digits of a number.
+	pub precision: u32,
+	/// Rounding mode used if a result does not fit into the precision.
+	pub rounding: RoundingMode,
+}
+
+impl DecimalContext {
+	pub fn new(precision: u32, rounding: RoundingMode) -> Self {
+		Self {
+			precision: precision.max(1),
+			rounding,
+		}
+	}
+
+	/// Context used for intermediate results, which are rounded to this context afterwards.
+	fn working(&self, extra_digits: u32) -> Self {
+		Self::new(
+			self.precision.saturating_add(GUARD_DIGITS + extra_digits),
+			RoundingMode::HalfEven,
+		)
+	}
+}
+
+impl Default for DecimalContext {
+	fn default() -> Self {
+		Self::new(28, RoundingMode::HalfEven)
+	}
+}
+
+/// Arbitrary-precision decimal floating point number.
+///
+/// Each number is rounded to the precision of its [`DecimalContext`],
+/// so `0.1 + 0.2` evaluates exactly to `0.3`.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Decimal>::with_context(DecimalContext::new(5, RoundingMode::Down));
+///
+/// let val = calculator.calculate("0.1 + 0.2").unwrap();
+/// assert_eq!(val.to_string(), "0.3");
+///
+/// let val = calculator.calculate("2 / 3").unwrap();
+/// assert_eq!(val.to_string(), "0.66666");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decimal {
+	/// The value of the number is `coefficient * 10^exponent`.
+	/// Trailing zeros of the coefficient are always removed.
+	coefficient: BigInt,
+	exponent: i64,
+	context: DecimalContext,
+}
+
+impl Decimal {
+	/// Creates the number `coefficient * 10^exponent` rounded to the given context.
+	pub fn new(coefficient: impl Into<BigInt>, exponent: i64, context: DecimalContext) -> Self {
+		let context = DecimalContext::new(context.precision, context.rounding);
+		let mut coefficient = coefficient.into();
+		let mut exponent = exponent;
+
+		let precision = context.precision as u64;
+		let digits = digits(&coefficient);
+		if digits > precision {
+			let dropped = digits - precision;
+			let divisor = pow10(dropped);
+			let (quotient, remainder) = coefficient.div_rem(&divisor);
+			let negative = coefficient.is_negative();
+			let increment = !remainder.is_zero()
+				&& match context.rounding {
+					RoundingMode::Down => false,
+					RoundingMode::Up => true,
+					RoundingMode::Ceiling => !negative,
+					RoundingMode::Floor => negative,
+					mode => match (remainder.abs() * 2u32).cmp(&divisor) {
+						std::cmp::Ordering::Greater => true,
+						std::cmp::Ordering::Less => false,
+						std::cmp::Ordering::Equal => match mode {
+							RoundingMode::HalfUp => true,
+							RoundingMode::HalfDown => false,
+							_ => quotient.is_odd(),
+						},
+					},
+				};
+			coefficient = match (increment, negative) {
+				(false, _) => quotient,
+				(true, false) => quotient + 1,
+				(true, true) => quotient - 1,
+			};
+			exponent = exponent.saturating_add(dropped as i64);
+		}
+
+		// remove trailing zeros
+		if coefficient.is_zero() {
+			exponent = 0;
+		} else {
+			let ten = BigInt::from(10);
+			loop {
+				let (quotient, remainder) = coefficient.div_rem(&ten);
+				if !remainder.is_zero() {
+					break;
+				}
+				coefficient = quotient;
+				exponent = exponent.saturating_add(1);
+			}
+		}
+
+		Self {
+			coefficient,
+			exponent,
+			context,
+		}
+	}
+
+	/// Rounds this number to the given context.
+	pub fn with_context(self, context: DecimalContext) -> Self {
+		Self::new(self.coefficient, self.exponent, context)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.coefficient.is_zero()
+	}
+
+	pub fn is_negative(&self) -> bool {
+		self.coefficient.is_negative()
+	}
+
+	pub fn is_integer(&self) -> bool {
+		self.exponent >= 0 || self.is_zero()
+	}
+
+	fn from_i64(value: i64, context: DecimalContext) -> Self {
+		Self::new(value, 0, context)
+	}
+
+	/// Exponent of the most significant digit, e.g. `2` for `123.4`.
+	fn adjusted(&self) -> i64 {
+		self.exponent + digits(&self.coefficient) as i64 - 1
+	}
+
+	fn abs(self) -> Self {
+		Self {
+			coefficient: self.coefficient.abs(),
+			..self
+		}
+	}
+
+	fn negated(self) -> Self {
+		Self {
+			coefficient: -self.coefficient,
+			..self
+		}
+	}
+
+	/// Multiplies this number by `10^exponent` without rounding.
+	fn shifted(self, exponent: i64) -> Self {
+		Self {
+			exponent: self.exponent + exponent,
+			..self
+		}
+	}
+
+	/// Returns the integer value of this number rounded to the nearest integer (ties away from zero).
+	fn round_to_integer(&self) -> Result<BigInt, Error> {
+		if self.exponent >= 0 {
+			return Ok(&self.coefficient * checked_pow10(self.exponent as u64)?);
+		}
+		if self.adjusted() < -1 {
+			return Ok(BigInt::zero());
+		}
+		let divisor = pow10(self.exponent.unsigned_abs());
+		let (quotient, remainder) = self.coefficient.div_rem(&divisor);
+		if remainder.abs() * 2u32 >= divisor {
+			Ok(quotient + self.coefficient.signum())
+		} else {
+			Ok(quotient)
+		}
+	}
+
+	fn add_with(&self, rhs: &Self, context: DecimalContext) -> Self {
+		if rhs.is_zero() {
+			return self.clone().with_context(context);
+		}
+		if self.is_zero() {
+			return rhs.clone().with_context(context);
+		}
+
+		let (big, small) = if self.adjusted() >= rhs.adjusted() {
+			(self, rhs)
+		} else {
+			(rhs, self)
+		};
+
+		// digits far below the precision are only relevant for rounding,
+		// so they are replaced by a single digit to keep the alignment small
+		let limit = big.adjusted() - context.precision as i64 - 2;
+		let sticky;
+		let small = if small.adjusted() < limit {
+			sticky = Self {
+				coefficient: small.coefficient.signum(),
+				exponent: big.exponent.min(limit) - 1,
+				context,
+			};
+			&sticky
+		} else {
+			small
+		};
+
+		let exponent = big.exponent.min(small.exponent);
+		let coefficient = &big.coefficient * pow10((big.exponent - exponent) as u64)
+			+ &small.coefficient * pow10((small.exponent - exponent) as u64);
+		Self::new(coefficient, exponent, context)
+	}
+
+	fn sub_with(&self, rhs: &Self, context: DecimalContext) -> Self {
+		self.add_with(&rhs.clone().negated(), context)
+	}
+
+	fn mul_with(&self, rhs: &Self, context: DecimalContext) -> Result<Self, Error> {
+		let exponent = checked_exponent(self.exponent.checked_add(rhs.exponent))?;
+		Ok(Self::new(
+			&self.coefficient * &rhs.coefficient,
+			exponent,
+			context,
+		))
+	}
+
+	fn div_with(&self, rhs: &Self, context: DecimalContext) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		if self.is_zero() {
+			return Ok(Self::new(0, 0, context));
+		}
+
+		// the quotient needs at least two digits more than the precision for correct rounding
+		let shift = (context.precision as i64 + 1 + digits(&rhs.coefficient) as i64
+			- digits(&self.coefficient) as i64)
+			.max(0);
+		let numerator = &self.coefficient * pow10(shift as u64);
+		let (mut quotient, remainder) = numerator.div_rem(&rhs.coefficient);
+		let mut exponent = checked_exponent(
+			self.exponent
+				.checked_sub(rhs.exponent)
+				.and_then(|e| e.checked_sub(shift)),
+		)?;
+		if !remainder.is_zero() {
+			// append a sticky digit, so the result is not mistaken for an exact one
+			quotient = quotient * 10 + numerator.signum() * rhs.coefficient.signum();
+			exponent -= 1;
+		}
+		Ok(Self::new(quotient, exponent, context))
+	}
+
+	fn rem_with(&self, rhs: &Self, context: DecimalContext) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		if self.clone().abs() < rhs.clone().abs() {
+			return Ok(self.clone().with_context(context));
+		}
+		let exponent = self.exponent.min(rhs.exponent);
+		let left = &self.coefficient * checked_pow10((self.exponent - exponent) as u64)?;
+		let right = &rhs.coefficient * checked_pow10((rhs.exponent - exponent) as u64)?;
+		Ok(Self::new(left % right, exponent, context))
+	}
+
+	fn powi_with(&self, exp: &BigInt, context: DecimalContext) -> Result<Self, Error> {
+		if self.is_zero() {
+			return match exp.sign() {
+				num_bigint::Sign::Plus => Ok(Self::new(0, 0, context)),
+				num_bigint::Sign::NoSign => Ok(Self::new(1, 0, context)),
+				num_bigint::Sign::Minus => Err(Error::Runtime("Division by 0!")),
+			};
+		}
+
+		let magnitude = exp.magnitude();
+		let result = match magnitude.to_u64() {
+			Some(n) if digits(&self.coefficient).saturating_mul(n) <= MAX_EXACT_DIGITS => {
+				let exponent = checked_exponent(self.exponent.checked_mul(n as i64))?;
+				Self {
+					coefficient: self.coefficient.pow(n as u32),
+					exponent,
+					context,
+				}
+			}
+			_ => {
+				// square and multiply with a higher precision
+				let working = context.working(digits(&BigInt::from(magnitude.clone())) as u32);
+				let mut result = Self::from_i64(1, working);
+				let mut base = self.clone().with_context(working);
+				for i in 0..magnitude.bits() {
+					if magnitude.bit(i) {
+						result = result.mul_with(&base, working)?;
+					}
+					base = base.mul_with(&base, working)?;
+				}
+				result
+			}
+		};
+
+		if exp.is_negative() {
+			Self::from_i64(1, context).div_with(&result, context)
+		} else {
+			Ok(result.with_context(context))
+		}
+	}
+
+	fn pow_with(&self, rhs: &Self, context: DecimalContext) -> Result<Self, Error> {
+		if rhs.is_integer() {
+			return self.powi_with(&rhs.round_to_integer()?, context);
+		}
+		if self.is_zero() {
+			return if rhs.is_negative() {
+				Err(Error::Runtime("Division by 0!"))
+			} else {
+				Ok(Self::new(0, 0, context))
+			};
+		}
+		if self.is_negative() {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+
+		// errors of the logarithm grow with the magnitude of the exponent
+		let exponent = rhs.mul_with(&self.ln_with(context.working(0))?, context.working(0))?;
+		let extra = (exponent.adjusted() + 1).max(0) as u32;
+		let working = context.working(extra);
+		let exponent = rhs.mul_with(&self.ln_with(working)?, working)?;
+		Ok(exponent.exp_with(working)?.with_context(context))
+	}
+
+	/// Calculates the `n`-th root of this number, the result is correctly rounded.
+	fn nth_root_with(&self, n: u32, context: DecimalContext) -> Result<Self, Error> {
+		if self.is_negative() {
+			return if n % 2 == 1 {
+				Ok(self.clone().negated().nth_root_with(n, context)?.negated())
+			} else {
+				Err(Error::Runtime("Result is not a real number!"))
+			};
+		}
+		if self.is_zero() {
+			return Ok(Self::new(0, 0, context));
+		}
+
+		// scale the coefficient, so the root has at least two digits more than the precision
+		let n_i64 = n as i64;
+		let wanted = n_i64 * (context.precision as i64 + 2);
+		if wanted as u64 > MAX_EXACT_DIGITS {
+			return Err(Error::Runtime("Number is out of range!"));
+		}
+		let mut shift = (wanted - digits(&self.coefficient) as i64).max(0);
+		shift += (self.exponent - shift).rem_euclid(n_i64);
+		let scaled = &self.coefficient * pow10(shift as u64);
+		let mut root = scaled.nth_root(n);
+		let mut exponent = (self.exponent - shift) / n_i64;
+		if root.pow(n) != scaled {
+			root = root * 10 + 1;
+			exponent -= 1;
+		}
+		Ok(Self::new(root, exponent, context))
+	}
+
+	fn root_with(&self, rhs: &Self, context: DecimalContext) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		// roots of a high degree are calculated by the logarithm instead of exactly
+		let exact = |n: &BigInt| {
+			n.magnitude().to_u32().filter(|n| {
+				(*n as u64).saturating_mul(context.working(0).precision as u64 + 2)
+					<= MAX_EXACT_DIGITS
+			})
+		};
+		if rhs.is_integer() && rhs.adjusted() < 10 {
+			let n = rhs.round_to_integer()?;
+			if let Some(root) = exact(&n) {
+				let result = self.nth_root_with(root, context.working(0))?;
+				return if n.is_negative() {
+					Self::from_i64(1, context).div_with(&result, context)
+				} else {
+					Ok(result.with_context(context))
+				};
+			}
+		}
+		let working = context.working(0);
+		let exponent = Self::from_i64(1, working).div_with(rhs, working)?;
+		Ok(self.pow_with(&exponent, working)?.with_context(context))
+	}
+
+	fn sqrt_with(&self, context: DecimalContext) -> Result<Self, Error> {
+		self.nth_root_with(2, context)
+	}
+
+	/// Checks if a term of a series is too small to change the sum anymore.
+	fn is_negligible(term: &Self, sum: &Self, context: DecimalContext) -> bool {
+		term.is_zero() || term.adjusted() < sum.adjusted() - context.precision as i64 - 1
+	}
+
+	fn exp_with(&self, context: DecimalContext) -> Result<Self, Error> {
+		if self.is_zero() {
+			return Ok(Self::from_i64(1, context));
+		}
+		// the result doesn't fit into the exponent
+		if self.adjusted() > 20 {
+			return Err(Error::Runtime("Number is out of range!"));
+		}
+
+		// reduce the argument below 0.5 and square the result afterwards
+		let halvings = if self.adjusted() >= 0 {
+			((self.adjusted() + 1) as f64 * std::f64::consts::LOG2_10).ceil() as u32 + 1
+		} else {
+			0
+		};
+		let working = context.working(halvings / 3 + 1);
+		let divisor = Self::new(BigInt::one() << halvings, 0, working);
+		let reduced = self.div_with(&divisor, working)?;
+
+		let mut sum = Self::from_i64(1, working);
+		let mut term = Self::from_i64(1, working);
+		let mut k = 1;
+		loop {
+			term = term
+				.mul_with(&reduced, working)?
+				.div_with(&Self::from_i64(k, working), working)?;
+			if Self::is_negligible(&term, &sum, working) {
+				break;
+			}
+			sum = sum.add_with(&term, working);
+			k += 1;
+		}
+
+		for _ in 0..halvings {
+			sum = sum.mul_with(&sum, working)?;
+		}
+		Ok(sum.with_context(context))
+	}
+
+	/// Calculates `atanh(z)` for `|z| < 1` using the taylor series.
+	fn atanh_series(z: &Self, context: DecimalContext) -> Result<Self, Error> {
+		let z_squared = z.mul_with(z, context)?;
+		let mut sum = z.clone();
+		let mut power = z.clone();
+		let mut k = 1;
+		loop {
+			power = power.mul_with(&z_squared, context)?;
+			let term = power.div_with(&Self::from_i64(2 * k + 1, context), context)?;
+			if Self::is_negligible(&term, &sum, context) {
+				break;
+			}
+			sum = sum.add_with(&term, context);
+			k += 1;
+		}
+		Ok(sum)
+	}
+
+	/// Calculates `ln(x)` for `x` in `[0.5, 2)` using `ln(x) = 2 * atanh((x - 1) / (x + 1))`.
+	fn ln_reduced(&self, context: DecimalContext) -> Result<Self, Error> {
+		let one = Self::from_i64(1, context);
+		if *self == one {
+			return Ok(Self::new(0, 0, context));
+		}
+		let z = self
+			.sub_with(&one, context)
+			.div_with(&self.add_with(&one, context), context)?;
+		let atanh = Self::atanh_series(&z, context)?;
+		atanh.mul_with(&Self::from_i64(2, context), context)
+	}
+
+	fn ln_with(&self, context: DecimalContext) -> Result<Self, Error> {
+		if self.is_negative() || self.is_zero() {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+
+		let half = Self::new(5, -1, context);
+		let two = Self::from_i64(2, context);
+		let working = context.working(digits(&BigInt::from(self.adjusted())) as u32);
+		if *self >= half && *self < two {
+			return Ok(self.ln_reduced(working)?.with_context(context));
+		}
+
+		// x = m * 2^j * 10^adjusted with m in [1, 2)
+		let adjusted = self.adjusted();
+		let mut mantissa = self.clone().shifted(-adjusted).with_context(working);
+		let mut halvings = 0;
+		while mantissa >= two {
+			mantissa = mantissa.div_with(&two, working)?;
+			halvings += 1;
+		}
+
+		let ln2 = Self::atanh_series(
+			&Self::new(1, 0, working).div_with(&Self::from_i64(3, working), working)?,
+			working,
+		)?
+		.mul_with(&two, working)?;
+		let ln10 = Self::atanh_series(
+			&Self::from_i64(1, working).div_with(&Self::from_i64(9, working), working)?,
+			working,
+		)?
+		.mul_with(&two, working)?
+		.add_with(
+			&ln2.mul_with(&Self::from_i64(3, working), working)?,
+			working,
+		);
+
+		let result = mantissa
+			.ln_reduced(working)?
+			.add_with(
+				&ln2.mul_with(&Self::from_i64(halvings, working), working)?,
+				working,
+			)
+			.add_with(
+				&ln10.mul_with(&Self::from_i64(adjusted, working), working)?,
+				working,
+			);
+		Ok(result.with_context(context))
+	}
+
+	/// Calculates `atan(x)` for `|x| <= 1` using the taylor series.
+	fn atan_series(x: &Self, context: DecimalContext) -> Result<Self, Error> {
+		let x_squared = x.mul_with(x, context)?;
+		let mut sum = x.clone();
+		let mut power = x.clone();
+		let mut k = 1;
+		loop {
+			power = power.mul_with(&x_squared, context)?.negated();
+			let term = power.div_with(&Self::from_i64(2 * k + 1, context), context)?;
+			if Self::is_negligible(&term, &sum, context) {
+				break;
+			}
+			sum = sum.add_with(&term, context);
+			k += 1;
+		}
+		Ok(sum)
+	}
+
+	fn pi_with(context: DecimalContext) -> Result<Self, Error> {
+		// Machin's formula: pi = 16 * atan(1/5) - 4 * atan(1/239)
+		let working = context.working(0);
+		let one = Self::from_i64(1, working);
+		let a = Self::atan_series(
+			&one.div_with(&Self::from_i64(5, working), working)?,
+			working,
+		)?;
+		let b = Self::atan_series(
+			&one.div_with(&Self::from_i64(239, working), working)?,
+			working,
+		)?;
+		let pi = a
+			.mul_with(&Self::from_i64(16, working), working)?
+			.sub_with(&b.mul_with(&Self::from_i64(4, working), working)?, working);
+		Ok(pi.with_context(context))
+	}
+
+	fn atan_with(&self, context: DecimalContext) -> Result<Self, Error> {
+		if self.is_zero() {
+			return Ok(Self::new(0, 0, context));
+		}
+		let working = context.working(0);
+		let one = Self::from_i64(1, working);
+
+		if self.clone().abs() > one {
+			// atan(x) = sign(x) * pi / 2 - atan(1 / x)
+			let half_pi = Self::pi_with(working)?.div_with(&Self::from_i64(2, working), working)?;
+			let half_pi = if self.is_negative() {
+				half_pi.negated()
+			} else {
+				half_pi
+			};
+			let inverse = one.div_with(self, working)?;
+			return Ok(half_pi
+				.sub_with(&inverse.atan_with(working)?, working)
+				.with_context(context));
+		}
+
+		// atan(x) = 2 * atan(x / (1 + sqrt(1 + x^2))), applied twice for a faster convergence
+		let mut reduced = self.clone().with_context(working);
+		for _ in 0..2 {
+			let root = one
+				.add_with(&reduced.mul_with(&reduced, working)?, working)
+				.sqrt_with(working)?;
+			reduced = reduced.div_with(&one.add_with(&root, working), working)?;
+		}
+		let result =
+			Self::atan_series(&reduced, working)?.mul_with(&Self::from_i64(4, working), working)?;
+		Ok(result.with_context(context))
+	}
+
+	/// Calculates `sin(x)` and `cos(x)`.
+	fn sin_cos_with(&self, context: DecimalContext) -> Result<(Self, Self), Error> {
+		// the reduction by multiples of 2 * pi needs additional digits for the integer part
+		if self.adjusted() > MAX_REDUCTION_DIGITS {
+			return Err(Error::Runtime("Number is out of range!"));
+		}
+		let working = context.working((self.adjusted() + 1).max(0) as u32);
+		let one = Self::from_i64(1, working);
+
+		let two_pi = Self::pi_with(working)?.mul_with(&Self::from_i64(2, working), working)?;
+		let turns = self.div_with(&two_pi, working)?.round_to_integer()?;
+		let x = if turns.is_zero() {
+			self.clone()
+		} else {
+			self.sub_with(
+				&two_pi.mul_with(&Self::new(turns, 0, working), working)?,
+				working,
+			)
+		};
+
+		let mut sin = x.clone();
+		let mut cos = one.clone();
+		let mut term = one;
+		let mut k = 1;
+		loop {
+			// term = x^k / k!
+			term = term
+				.mul_with(&x, working)?
+				.div_with(&Self::from_i64(k, working), working)?;
+			if Self::is_negligible(&term, &cos, working)
+				&& Self::is_negligible(&term, &sin, working)
+			{
+				break;
+			}
+			let signed = if (k / 2) % 2 == 0 {
+				term.clone()
+			} else {
+				term.clone().negated()
+			};
+			if k % 2 == 0 {
+				cos = cos.add_with(&signed, working);
+			} else if k > 1 {
+				sin = sin.add_with(&signed, working);
+			}
+			k += 1;
+		}
+
+		Ok((sin.with_context(context), cos.with_context(context)))
+	}
+}
+
+/// Number of decimal digits of the given integer.
+fn digits(value: &BigInt) -> u64 {
+	if value.is_zero() {
+		1
+	} else {
+		value.magnitude().to_str_radix(10).len() as u64
+	}
+}
+
+fn pow10(exponent: u64) -> BigInt {
+	BigInt::from(10).pow(exponent as u32)
+}
+
+/// Calculates `10^exponent` for exponents which depend on the size of the input.
+fn checked_pow10(exponent: u64) -> Result<BigInt, Error> {
+	if exponent > MAX_EXACT_DIGITS {
+		return Err(Error::Runtime("Number is out of range!"));
+	}
+	Ok(pow10(exponent))
+}
+
+fn checked_exponent(exponent: Option<i64>) -> Result<i64, Error> {
+	exponent.ok_or(Error::Runtime("Number is out of range!"))
+}
+
+impl PartialEq for Decimal {
+	fn eq(&self, other: &Self) -> bool {
+		self.coefficient == other.coefficient && self.exponent == other.exponent
+	}
+}
+
+impl PartialOrd for Decimal {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		// the difference keeps its sign if it's rounded to the digits of both numbers,
+		// which avoids aligning numbers with very different exponents
+		let precision = digits(&self.coefficient) + digits(&other.coefficient) + 2;
+		let context = DecimalContext::new(
+			precision.min((u32::MAX - GUARD_DIGITS) as u64) as u32,
+			RoundingMode::HalfEven,
+		);
+		let difference = self.sub_with(other, context);
+		Some(difference.coefficient.sign().cmp(&num_bigint::Sign::NoSign))
+	}
+}
+
+impl fmt::Display for Decimal {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let sign = if self.is_negative() { "-" } else { "" };
+		let digits = self.coefficient.magnitude().to_str_radix(10);
+		let adjusted = self.adjusted();
+
+		if adjusted < -7 || adjusted >= (self.context.precision as i64).max(21) {
+			let (first, rest) = digits.split_at(1);
+			if rest.is_empty() {
+				write!(f, "{}{}e{}", sign, first, adjusted)
+			} else {
+				write!(f, "{}{}.{}e{}", sign, first, rest, adjusted)
+			}
+		} else if self.exponent >= 0 {
+			write!(
+				f,
+				"{}{}{}",
+				sign,
+				digits,
+				"0".repeat(self.exponent as usize)
+			)
+		} else {
+			let point = digits.len() as i64 + self.exponent;
+			if point > 0 {
+				let (integer, fraction) = digits.split_at(point as usize);
+				write!(f, "{}{}.{}", sign, integer, fraction)
+			} else {
+				write!(
+					f,
+					"{}0.{}{}",
+					sign,
+					"0".repeat(point.unsigned_abs() as usize),
+					digits
+				)
+			}
+		}
+	}
+}
+
+impl FromStr for Decimal {
+	type Err = Error;
+
+	/// Parses a number using the default context.
+	fn from_str(src: &str) -> Result<Self, Self::Err> {
+		Self::parse(src, &DecimalContext::default())
+	}
+}
+
+impl From<i64> for Decimal {
+	fn from(value: i64) -> Self {
+		Self::from_i64(value, DecimalContext::default())
+	}
+}
+
+impl Number for Decimal {
+	type Context = DecimalContext;
+
+	fn parse(src: &str, ctx: &DecimalContext) -> Result<Self, Error> {
+		let (coefficient, exponent) =
+			parse_literal(src).ok_or(Error::Fatal("Cannot parse number!"))?;
+		Ok(Self::new(coefficient, exponent, *ctx))
+	}
+
+	fn from_f64(value: f64, ctx: &DecimalContext) -> Result<Self, Error> {
+		if !value.is_finite() {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		Self::parse(&format!("{:e}", value), ctx)
+	}
+
+	fn to_f64(&self) -> f64 {
+		format!("{}e{}", self.coefficient, self.exponent)
+			.parse()
+			.unwrap_or(f64::NAN)
+	}
+
+	fn context(&self) -> DecimalContext {
+		self.context
+	}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.add_with(&rhs, self.context))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.sub_with(&rhs, self.context))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		self.mul_with(&rhs, self.context)
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		self.div_with(&rhs, self.context)
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		self.rem_with(&rhs, self.context)
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		self.pow_with(&rhs, self.context)
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		self.root_with(&rhs, self.context)
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(self.negated())
+	}
+
+	fn compare(&self, other: &Self) -> Result<std::cmp::Ordering, Error> {
+		Ok(self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal))
+	}
+
+	/// Trailing zeros are removed, so only a non-negative exponent is an integer.
+	fn to_integer(&self) -> Option<BigInt> {
+		let exponent = u64::try_from(self.exponent).ok()?;
+		Some(&self.coefficient * checked_pow10(exponent).ok()?)
+	}
+
+	fn pi(ctx: &DecimalContext) -> Result<Self, Error> {
+		Self::pi_with(*ctx)
+	}
+
+	fn e(ctx: &DecimalContext) -> Result<Self, Error> {
+		Self::from_i64(1, *ctx).exp_with(*ctx)
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		Ok(self.sin_cos_with(self.context)?.0)
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		let context = self.context;
+		let working = context.working(context.precision);
+		let one = Self::from_i64(1, working);
+		match self.clone().abs().partial_cmp(&one) {
+			Some(std::cmp::Ordering::Greater) => {
+				Err(Error::Runtime("Result is not a real number!"))
+			}
+			Some(std::cmp::Ordering::Equal) => {
+				let half_pi =
+					Self::pi_with(working)?.div_with(&Self::from_i64(2, working), working)?;
+				let half_pi = if self.is_negative() {
+					half_pi.negated()
+				} else {
+					half_pi
+				};
+				Ok(half_pi.with_context(context))
+			}
+			_ => {
+				// asin(x) = atan(x / sqrt(1 - x^2))
+				let root = one
+					.sub_with(&self.mul_with(&self, working)?, working)
+					.sqrt_with(working)?;
+				Ok(self
+					.div_with(&root, working)?
+					.atan_with(working)?
+					.with_context(context))
+			}
+		}
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		Ok(self.sin_cos_with(self.context)?.1)
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		let context = self.context;
+		let working = context.working(0);
+		let one = Self::from_i64(1, working);
+		if self.clone().abs() > one {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		if self == one.clone().negated() {
+			return Self::pi_with(context);
+		}
+
+		// acos(x) = 2 * atan(sqrt((1 - x) / (1 + x)))
+		let root = one
+			.sub_with(&self, working)
+			.div_with(&one.add_with(&self, working), working)?
+			.sqrt_with(working)?;
+		let result = root
+			.atan_with(working)?
+			.mul_with(&Self::from_i64(2, working), working)?;
+		Ok(result.with_context(context))
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		let context = self.context;
+		let working = context.working(0);
+		let (sin, cos) = self.sin_cos_with(working)?;
+		Ok(sin.div_with(&cos, working)?.with_context(context))
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		self.atan_with(self.context)
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		let context = self.context;
+		let working = context.working(0);
+		let result = self
+			.mul_with(&Self::from_i64(180, working), working)?
+			.div_with(&Self::pi_with(working)?, working)?;
+		Ok(result.with_context(context))
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		let context = self.context;
+		let working = context.working(0);
+		let result = self
+			.mul_with(&Self::pi_with(working)?, working)?
+			.div_with(&Self::from_i64(180, working), working)?;
+		Ok(result.with_context(context))
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		self.exp_with(self.context)
+	}
+
+	fn ln(self) -> Result<Self, Error> {
+		self.ln_with(self.context)
+	}
+
+	fn sqrt(self) -> Result<Self, Error> {
+		self.sqrt_with(self.context)
+	}
+
+	fn abs(self) -> Result<Self, Error> {
+		Ok(Decimal::abs(self))
+	}
+
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		format.format_decimal(&self.coefficient, self.exponent)
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		if !self.is_integer() {
+			return Err(Error::Runtime(
+				"Factorial is only defined for non-negative integers!",
+			));
+		}
+		let n = self.round_to_integer()?;
+		Ok(Self::new(exact_factorial(&n)?, 0, self.context))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn d(src: &str) -> Decimal {
+		src.parse().unwrap()
+	}
+
+	fn with(src: &str, precision: u32, rounding: RoundingMode) -> Decimal {
+		Decimal::parse(src, &DecimalContext::new(precision, rounding)).unwrap()
+	}
+
+	#[test]
+	fn test_01_parse_display() {
+		assert_eq!(d("0").to_string(), "0");
+		assert_eq!(d("4.50").to_string(), "4.5");
+		assert_eq!(d("0.001").to_string(), "0.001");
+		assert_eq!(d("1200").to_string(), "1200");
+		assert_eq!(d("-12.5").to_string(), "-12.5");
+		assert_eq!(d("1e30").to_string(), "1e30");
+		assert_eq!(d("1.5e-10").to_string(), "1.5e-10");
+		assert!(Decimal::parse("1.2.3", &DecimalContext::default()).is_err());
+		assert!(Decimal::parse(".", &DecimalContext::default()).is_err());
+	}
+
+	#[test]
+	fn test_02_exact_arithmetic() {
+		assert_eq!(d("0.1").add(d("0.2")).unwrap(), d("0.3"));
+		assert_eq!(d("1.1").mul(d("1.1")).unwrap(), d("1.21"));
+		assert_eq!(d("10").sub(d("0.01")).unwrap(), d("9.99"));
+		assert_eq!(d("1").div(d("8")).unwrap(), d("0.125"));
+		assert_eq!(d("4.5").rem(d("3")).unwrap(), d("1.5"));
+		assert_eq!(d("-7").rem(d("2")).unwrap(), d("-1"));
+		assert_eq!(d("2").pow(d("10")).unwrap(), d("1024"));
+		assert_eq!(d("2").pow(d("-2")).unwrap(), d("0.25"));
+		assert_eq!(d("8").root(d("3")).unwrap(), d("2"));
+		assert_eq!(d("-8").root(d("3")).unwrap(), d("-2"));
+	}
+
+	#[test]
+	fn test_03_rounding() {
+		let third = |precision, rounding| {
+			with("1", precision, rounding)
+				.div(with("3", precision, rounding))
+				.unwrap()
+				.to_string()
+		};
+		assert_eq!(third(5, RoundingMode::HalfEven), "0.33333");
+		assert_eq!(third(5, RoundingMode::Up), "0.33334");
+		assert_eq!(
+			third(50, RoundingMode::HalfEven),
+			format!("0.{}", "3".repeat(50))
+		);
+
+		let round = |src, rounding| with(src, 2, rounding).to_string();
+		assert_eq!(round("2.25", RoundingMode::HalfEven), "2.2");
+		assert_eq!(round("2.35", RoundingMode::HalfEven), "2.4");
+		assert_eq!(round("2.25", RoundingMode::HalfUp), "2.3");
+		assert_eq!(round("2.25", RoundingMode::HalfDown), "2.2");
+		assert_eq!(round("-2.21", RoundingMode::Floor), "-2.3");
+		assert_eq!(round("-2.29", RoundingMode::Ceiling), "-2.2");
+		assert_eq!(round("2.29", RoundingMode::Down), "2.2");
+		assert_eq!(round("2.21", RoundingMode::Up), "2.3");
+
+		// far smaller operands are still relevant for rounding
+		let big = with("1e40", 5, RoundingMode::Up);
+		assert_eq!(
+			big.add(with("1e-40", 5, RoundingMode::Up))
+				.unwrap()
+				.to_string(),
+			"1.0001e40"
+		);
+	}
+
+	#[test]
+	fn test_04_division_by_0() {
+		assert!(d("4").div(d("0")).is_err());
+		assert!(d("4").rem(d("0")).is_err());
+		assert!(d("4").root(d("0")).is_err());
+		assert!(d("0").pow(d("-1")).is_err());
+	}
+
+	#[test]
+	fn test_05_functions() {
+		let ctx = DecimalContext::new(30, RoundingMode::HalfEven);
+		assert_eq!(
+			Decimal::pi(&ctx).unwrap().to_string(),
+			"3.14159265358979323846264338328"
+		);
+		assert_eq!(
+			Decimal::e(&ctx).unwrap().to_string(),
+			"2.71828182845904523536028747135"
+		);
+		let two = Decimal::parse("2", &ctx).unwrap();
+		assert_eq!(
+			two.clone().root(two.clone()).unwrap().to_string(),
+			"1.41421356237309504880168872421"
+		);
+		assert_eq!(
+			two.clone()
+				.pow(Decimal::parse("0.5", &ctx).unwrap())
+				.unwrap()
+				.to_string(),
+			"1.41421356237309504880168872421"
+		);
+		assert_eq!(
+			two.clone().sin().unwrap().to_string(),
+			"0.909297426825681695396019865912"
+		);
+		assert_eq!(
+			two.clone().cos().unwrap().to_string(),
+			"-0.416146836547142386997568229501"
+		);
+		assert_eq!(
+			two.clone().atan().unwrap().to_string(),
+			"1.10714871779409050301706546018"
+		);
+		assert_eq!(
+			Decimal::parse("0.5", &ctx)
+				.unwrap()
+				.asin()
+				.unwrap()
+				.to_string(),
+			"0.523598775598298873077107230547"
+		);
+		assert_eq!(
+			Decimal::parse("0.5", &ctx)
+				.unwrap()
+				.acos()
+				.unwrap()
+				.to_string(),
+			"1.04719755119659774615421446109"
+		);
+		assert_eq!(
+			Decimal::pi(&ctx).unwrap().to_degrees().unwrap().to_string(),
+			"180"
+		);
+		assert!(two.asin().is_err());
+
+		let thirty = Decimal::parse("30", &ctx).unwrap();
+		assert_eq!(
+			thirty.factorial().unwrap().to_string(),
+			"2.6525285981219105863630848e32"
+		);
+	}
+
+	#[test]
+	fn test_06_large_exponents() {
+		let err = Err(Error::Runtime("Number is out of range!"));
+		let large = d("1e999999999");
+		assert_eq!(d("2").pow(large.clone()), err);
+		assert_eq!(large.clone().rem(d("7")), err);
+		assert_eq!(large.clone().sin(), err);
+		assert_eq!(large.clone().exp(), err);
+		assert_eq!(
+			large.clone().ln().unwrap().to_string(),
+			"2302585090.691460591023945771"
+		);
+		assert!(large > d("1") && d("1e-999999999") < large);
+		assert_eq!(d("1e-999999999").rem(d("7")).unwrap(), d("1e-999999999"));
+
+		// roots of a high degree aren't calculated exactly
+		assert_eq!(
+			d("2").root(d("100000000")).unwrap().to_string(),
+			"1.000000006931471829622103846"
+		);
+	}
+}
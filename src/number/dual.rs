@@ -0,0 +1,304 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{Error, Number};
+
+/// Dual number `value + derivative * ε` with `ε² = 0`, used for forward-mode automatic differentiation.
+///
+/// Evaluating an expression with the variable `x + 1ε` results in `f(x) + f'(x)ε`,
+/// so the derivative is exact up to the precision of the underlying number type.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::new();
+/// calculator.init_std();
+/// calculator.add_var("x", 2.0);
+///
+/// let val = calculator.calculate("diff(x**3, x)").unwrap();
+/// assert_eq!(val, 12.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dual<N> {
+	pub value: N,
+	pub derivative: N,
+}
+
+impl<N: Number> Dual<N> {
+	pub fn new(value: N, derivative: N) -> Self {
+		Self { value, derivative }
+	}
+
+	/// Creates a number which doesn't depend on the variable.
+	pub fn constant(value: N) -> Result<Self, Error> {
+		let derivative = N::parse("0", &value.context())?;
+		Ok(Self::new(value, derivative))
+	}
+
+	/// Creates the variable which is differentiated for.
+	pub fn variable(value: N) -> Result<Self, Error> {
+		let derivative = N::parse("1", &value.context())?;
+		Ok(Self::new(value, derivative))
+	}
+
+	fn is_constant(&self) -> bool {
+		self.derivative.to_f64() == 0.0
+	}
+
+	/// Applies a function with the given derivative `f'(value)` using the chain rule.
+	fn chain(self, value: N, derivative: N) -> Result<Self, Error> {
+		Ok(Self::new(value, self.derivative.mul(derivative)?))
+	}
+
+	fn number(&self, value: f64) -> Result<N, Error> {
+		N::from_f64(value, &self.value.context())
+	}
+}
+
+impl<N: fmt::Display> fmt::Display for Dual<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}+{}ε", self.value, self.derivative)
+	}
+}
+
+impl<N: Number> Number for Dual<N> {
+	type Context = N::Context;
+
+	fn parse(src: &str, ctx: &Self::Context) -> Result<Self, Error> {
+		Self::constant(N::parse(src, ctx)?)
+	}
+
+	fn from_f64(value: f64, ctx: &Self::Context) -> Result<Self, Error> {
+		Self::constant(N::from_f64(value, ctx)?)
+	}
+
+	fn to_f64(&self) -> f64 {
+		self.value.to_f64()
+	}
+
+	fn context(&self) -> Self::Context {
+		self.value.context()
+	}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::new(
+			self.value.add(rhs.value)?,
+			self.derivative.add(rhs.derivative)?,
+		))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::new(
+			self.value.sub(rhs.value)?,
+			self.derivative.sub(rhs.derivative)?,
+		))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		let derivative = self
+			.derivative
+			.mul(rhs.value.clone())?
+			.add(self.value.clone().mul(rhs.derivative)?)?;
+		Ok(Self::new(self.value.mul(rhs.value)?, derivative))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		let value = self.value.div(rhs.value.clone())?;
+		// (a / b)' = (a' - a / b * b') / b
+		let derivative = self
+			.derivative
+			.sub(value.clone().mul(rhs.derivative)?)?
+			.div(rhs.value)?;
+		Ok(Self::new(value, derivative))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		let value = self.value.clone().rem(rhs.value.clone())?;
+		// a % b = a - q * b with the integer quotient q, which is locally constant
+		let quotient = self.value.sub(value.clone())?.div(rhs.value)?;
+		let derivative = self.derivative.sub(quotient.mul(rhs.derivative)?)?;
+		Ok(Self::new(value, derivative))
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		let value = self.value.clone().pow(rhs.value.clone())?;
+		// (a ** b)' = b * a ** (b - 1) * a' + a ** b * ln(a) * b'
+		let mut derivative = self.number(0.0)?;
+		if !self.is_constant() {
+			let exponent = rhs.value.clone().sub(self.number(1.0)?)?;
+			let term = rhs
+				.value
+				.clone()
+				.mul(self.value.clone().pow(exponent)?)?
+				.mul(self.derivative.clone())?;
+			derivative = derivative.add(term)?;
+		}
+		if !rhs.is_constant() {
+			let term = value.clone().mul(self.value.ln()?)?.mul(rhs.derivative)?;
+			derivative = derivative.add(term)?;
+		}
+		Ok(Self::new(value, derivative))
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		let value = self.value.clone().root(rhs.value.clone())?;
+		// (a // b)' = a // b * (a' / (b * a) - ln(a) * b' / b ** 2)
+		let mut derivative = self.number(0.0)?;
+		if !self.is_constant() {
+			let term = value
+				.clone()
+				.mul(self.derivative.clone())?
+				.div(rhs.value.clone().mul(self.value.clone())?)?;
+			derivative = derivative.add(term)?;
+		}
+		if !rhs.is_constant() {
+			let term = value
+				.clone()
+				.mul(self.value.ln()?)?
+				.mul(rhs.derivative)?
+				.div(rhs.value.clone().mul(rhs.value)?)?;
+			derivative = derivative.sub(term)?;
+		}
+		Ok(Self::new(value, derivative))
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(Self::new(self.value.neg()?, self.derivative.neg()?))
+	}
+
+	fn compare(&self, other: &Self) -> Result<Ordering, Error> {
+		self.value.compare(&other.value)
+	}
+
+	fn pi(ctx: &Self::Context) -> Result<Self, Error> {
+		Self::constant(N::pi(ctx)?)
+	}
+
+	fn e(ctx: &Self::Context) -> Result<Self, Error> {
+		Self::constant(N::e(ctx)?)
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		let (sin, cos) = (self.value.clone().sin()?, self.value.clone().cos()?);
+		self.chain(sin, cos)
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		let asin = self.value.clone().asin()?;
+		// 1 / sqrt(1 - x ** 2)
+		let one = self.number(1.0)?;
+		let square = self.value.clone().mul(self.value.clone())?;
+		let derivative = one.clone().div(one.sub(square)?.sqrt()?)?;
+		self.chain(asin, derivative)
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		let (cos, sin) = (self.value.clone().cos()?, self.value.clone().sin()?);
+		self.chain(cos, sin.neg()?)
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		let acos = self.value.clone().acos()?;
+		let derivative = self.clone().asin()?.derivative.neg()?;
+		Ok(Self::new(acos, derivative))
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		let tan = self.value.clone().tan()?;
+		// 1 + tan(x) ** 2
+		let derivative = self.number(1.0)?.add(tan.clone().mul(tan.clone())?)?;
+		self.chain(tan, derivative)
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		let atan = self.value.clone().atan()?;
+		let one = self.number(1.0)?;
+		let square = self.value.clone().mul(self.value.clone())?;
+		let derivative = one.clone().div(one.add(square)?)?;
+		self.chain(atan, derivative)
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		Ok(Self::new(
+			self.value.to_degrees()?,
+			self.derivative.to_degrees()?,
+		))
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		Ok(Self::new(
+			self.value.to_radians()?,
+			self.derivative.to_radians()?,
+		))
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		let exp = self.value.clone().exp()?;
+		self.chain(exp.clone(), exp)
+	}
+
+	fn ln(self) -> Result<Self, Error> {
+		let ln = self.value.clone().ln()?;
+		let derivative = self.derivative.div(self.value)?;
+		Ok(Self::new(ln, derivative))
+	}
+
+	fn re(self) -> Result<Self, Error> {
+		Ok(Self::new(self.value.re()?, self.derivative.re()?))
+	}
+
+	fn im(self) -> Result<Self, Error> {
+		Ok(Self::new(self.value.im()?, self.derivative.im()?))
+	}
+
+	fn conj(self) -> Result<Self, Error> {
+		Ok(Self::new(self.value.conj()?, self.derivative.conj()?))
+	}
+
+	fn imaginary_unit(ctx: &Self::Context) -> Result<Self, Error> {
+		Self::constant(N::imaginary_unit(ctx)?)
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		if !self.is_constant() {
+			return Err(Error::Runtime("Factorial is not differentiable!"));
+		}
+		Self::constant(self.value.factorial()?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn x(value: f64) -> Dual<f64> {
+		Dual::variable(value).unwrap()
+	}
+
+	#[test]
+	fn test_01_arithmetic() {
+		let three = Dual::constant(3.0).unwrap();
+		assert_eq!(x(2.0).mul(x(2.0)).unwrap(), Dual::new(4.0, 4.0));
+		assert_eq!(three.clone().div(x(2.0)).unwrap(), Dual::new(1.5, -0.75));
+		assert_eq!(x(2.0).pow(three.clone()).unwrap(), Dual::new(8.0, 12.0));
+		assert_eq!(three.pow(x(2.0)).unwrap().derivative, 9.0 * 3f64.ln());
+		assert_eq!(
+			x(7.0).rem(Dual::constant(3.0).unwrap()).unwrap(),
+			Dual::new(1.0, 1.0)
+		);
+	}
+
+	#[test]
+	fn test_02_functions() {
+		assert_eq!(x(0.0).sin().unwrap(), Dual::new(0.0, 1.0));
+		assert_eq!(x(0.0).exp().unwrap(), Dual::new(1.0, 1.0));
+		assert_eq!(x(2.0).ln().unwrap(), Dual::new(2f64.ln(), 0.5));
+		assert_eq!(x(4.0).sqrt().unwrap(), Dual::new(2.0, 0.25));
+		assert_eq!(x(1.0).atan().unwrap().derivative, 0.5);
+		assert_eq!(
+			x(3.0).factorial(),
+			Err(Error::Runtime("Factorial is not differentiable!"))
+		);
+	}
+}
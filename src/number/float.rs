@@ -0,0 +1,180 @@
+use crate::{Error, Number, NumberFormat};
+
+macro_rules! impl_float {
+	($ty:ident) => {
+		impl Number for $ty {
+			type Context = ();
+
+			fn parse(src: &str, _: &()) -> Result<Self, Error> {
+				src.parse()
+					.map_err(|_| Error::Fatal("Cannot parse number!"))
+			}
+
+			fn from_f64(value: f64, _: &()) -> Result<Self, Error> {
+				Ok(value as $ty)
+			}
+
+			fn to_f64(&self) -> f64 {
+				*self as f64
+			}
+
+			fn context(&self) {}
+
+			fn add(self, rhs: Self) -> Result<Self, Error> {
+				Ok(self + rhs)
+			}
+
+			fn sub(self, rhs: Self) -> Result<Self, Error> {
+				Ok(self - rhs)
+			}
+
+			fn mul(self, rhs: Self) -> Result<Self, Error> {
+				Ok(self * rhs)
+			}
+
+			fn div(self, rhs: Self) -> Result<Self, Error> {
+				if rhs == 0.0 {
+					return Err(Error::Runtime("Division by 0!"));
+				}
+				Ok(self / rhs)
+			}
+
+			fn rem(self, rhs: Self) -> Result<Self, Error> {
+				if rhs == 0.0 {
+					return Err(Error::Runtime("Division by 0!"));
+				}
+				Ok(self % rhs)
+			}
+
+			fn pow(self, rhs: Self) -> Result<Self, Error> {
+				Ok(self.powf(rhs))
+			}
+
+			fn root(self, rhs: Self) -> Result<Self, Error> {
+				if rhs == 0.0 {
+					return Err(Error::Runtime("Division by 0!"));
+				}
+				Ok(self.powf(1.0 / rhs))
+			}
+
+			fn neg(self) -> Result<Self, Error> {
+				Ok(-self)
+			}
+
+			fn pi(_: &()) -> Result<Self, Error> {
+				Ok(std::$ty::consts::PI)
+			}
+
+			fn e(_: &()) -> Result<Self, Error> {
+				Ok(std::$ty::consts::E)
+			}
+
+			fn sin(self) -> Result<Self, Error> {
+				Ok($ty::sin(self))
+			}
+
+			fn asin(self) -> Result<Self, Error> {
+				Ok($ty::asin(self))
+			}
+
+			fn cos(self) -> Result<Self, Error> {
+				Ok($ty::cos(self))
+			}
+
+			fn acos(self) -> Result<Self, Error> {
+				Ok($ty::acos(self))
+			}
+
+			fn tan(self) -> Result<Self, Error> {
+				Ok($ty::tan(self))
+			}
+
+			fn atan(self) -> Result<Self, Error> {
+				Ok($ty::atan(self))
+			}
+
+			fn to_degrees(self) -> Result<Self, Error> {
+				Ok($ty::to_degrees(self))
+			}
+
+			fn to_radians(self) -> Result<Self, Error> {
+				Ok($ty::to_radians(self))
+			}
+
+			fn exp(self) -> Result<Self, Error> {
+				Ok($ty::exp(self))
+			}
+
+			fn ln(self) -> Result<Self, Error> {
+				Ok($ty::ln(self))
+			}
+
+			fn sqrt(self) -> Result<Self, Error> {
+				Ok($ty::sqrt(self))
+			}
+
+			fn abs(self) -> Result<Self, Error> {
+				Ok($ty::abs(self))
+			}
+
+			fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+				format.format_float(*self as f64, $ty::DIGITS)
+			}
+
+			fn factorial(self) -> Result<Self, Error> {
+				if self < 0.0 || self.fract() != 0.0 {
+					return Err(Error::Runtime(
+						"Factorial is only defined for non-negative integers!",
+					));
+				}
+				let mut result: $ty = 1.0;
+				let mut i: $ty = 2.0;
+				while i <= self && result.is_finite() {
+					result *= i;
+					i += 1.0;
+				}
+				Ok(result)
+			}
+		}
+	};
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_01_parse() {
+		assert_eq!(f32::parse("4.5", &()), Ok(4.5));
+		assert_eq!(f64::parse("455.555", &()), Ok(455.555));
+		assert!(f64::parse("abc", &()).is_err());
+	}
+
+	#[test]
+	fn test_02_precision() {
+		let a = f64::parse("16777217", &()).unwrap();
+		assert_eq!(a.add(1.0).unwrap(), 16777218.0);
+
+		let a = f32::parse("16777217", &()).unwrap();
+		assert_eq!(a.add(1.0).unwrap(), 16777216.0);
+	}
+
+	#[test]
+	fn test_03_division_by_0() {
+		assert!(4.0f64.div(0.0).is_err());
+		assert!(4.0f32.rem(0.0).is_err());
+		assert!(4.0f64.root(0.0).is_err());
+	}
+
+	#[test]
+	fn test_04_factorial() {
+		assert_eq!(0.0f64.factorial().unwrap(), 1.0);
+		assert_eq!(5.0f64.factorial().unwrap(), 120.0);
+		assert_eq!(200.0f64.factorial().unwrap(), f64::INFINITY);
+		assert!(4.5f32.factorial().is_err());
+		assert!((-1.0f64).factorial().is_err());
+	}
+}
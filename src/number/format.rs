@@ -0,0 +1,412 @@
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{FromPrimitive, Signed, Zero};
+
+use super::parse_literal;
+use crate::Error;
+
+/// SI prefixes used by [`Notation::Si`] with their exponents.
+const SI_PREFIXES: [(i64, &str); 17] = [
+	(-24, "y"),
+	(-21, "z"),
+	(-18, "a"),
+	(-15, "f"),
+	(-12, "p"),
+	(-9, "n"),
+	(-6, "µ"),
+	(-3, "m"),
+	(0, ""),
+	(3, "k"),
+	(6, "M"),
+	(9, "G"),
+	(12, "T"),
+	(15, "P"),
+	(18, "E"),
+	(21, "Z"),
+	(24, "Y"),
+];
+
+/// How the exponent of a formatted number is written.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Notation {
+	/// Without exponent, e.g. `4700`.
+	#[default]
+	Plain,
+	/// One digit before the decimal point, e.g. `4.7e3`.
+	Scientific,
+	/// Exponents which are multiples of three, e.g. `47e3`.
+	Engineering,
+	/// Engineering notation with an SI prefix instead of the exponent, e.g. `4.7k`.
+	Si,
+}
+
+/// How many digits of a formatted number are shown.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Precision {
+	/// All digits without trailing zeros, floating point numbers are rounded to the digits
+	/// they can represent, so `0.1 + 0.2` is shown as `0.3`.
+	#[default]
+	Auto,
+	/// Number of digits after the decimal point.
+	Fixed(usize),
+	/// Number of significant digits.
+	Significant(usize),
+}
+
+/// Options for formatting numbers with [`Number::format`](crate::Number::format)
+/// or [`Value::format`](crate::Value::format).
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::new();
+/// let val = calculator.calculate("0.1 + 0.2").unwrap();
+/// assert_eq!(val.format(&NumberFormat::default()).unwrap(), "0.3");
+///
+/// let format = NumberFormat {
+///     notation: Notation::Si,
+///     precision: Precision::Significant(2),
+///     ..Default::default()
+/// };
+/// let val = calculator.calculate("4712").unwrap();
+/// assert_eq!(val.format(&format).unwrap(), "4.7k");
+///
+/// let format = NumberFormat {
+///     separator: Some(','),
+///     ..Default::default()
+/// };
+/// let val = calculator.calculate("1234567.5").unwrap();
+/// assert_eq!(val.format(&format).unwrap(), "1,234,567.5");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NumberFormat {
+	pub notation: Notation,
+	pub precision: Precision,
+	/// Separates groups of digits before the decimal point, e.g. `1,000,000`.
+	/// Binary and hexadecimal digits are grouped by four, all others by three.
+	pub separator: Option<char>,
+	/// Radix of the output, which is either 2, 8, 10 or 16.
+	/// Other radixes than 10 are only supported for integers and ignore the notation and precision.
+	pub radix: u32,
+}
+
+impl Default for NumberFormat {
+	fn default() -> Self {
+		Self {
+			notation: Notation::default(),
+			precision: Precision::default(),
+			separator: None,
+			radix: 10,
+		}
+	}
+}
+
+impl NumberFormat {
+	/// Formats a floating point number, `digits` is the number of significant digits
+	/// the type can represent, which are shown with [`Precision::Auto`].
+	pub fn format_float(&self, value: f64, digits: u32) -> Result<String, Error> {
+		if !value.is_finite() {
+			return Ok(value.to_string());
+		}
+		if self.radix != 10 {
+			return match BigInt::from_f64(value) {
+				Some(integer) if value.fract() == 0.0 => self.format_integer(&integer),
+				_ => Err(Error::Runtime(
+					"Only integers can be formatted in another radix!",
+				)),
+			};
+		}
+		let (coefficient, exponent) =
+			parse_literal(&format!("{:e}", value)).ok_or(Error::Fatal("Cannot format number!"))?;
+		self.format_digits(coefficient, exponent, Some(digits))
+	}
+
+	/// Formats an integer exactly.
+	pub fn format_integer(&self, value: &BigInt) -> Result<String, Error> {
+		let prefix = match self.radix {
+			2 => "0b",
+			8 => "0o",
+			10 => return self.format_digits(value.clone(), 0, None),
+			16 => "0x",
+			_ => return Err(Error::Runtime("Radix must be 2, 8, 10 or 16!")),
+		};
+		let sign = if value.is_negative() { "-" } else { "" };
+		let digits = value.magnitude().to_str_radix(self.radix);
+		let group = if self.radix == 8 { 3 } else { 4 };
+		Ok(format!("{}{}{}", sign, prefix, self.group(&digits, group)))
+	}
+
+	/// Formats the number `coefficient * 10^exponent` exactly.
+	pub fn format_decimal(&self, coefficient: &BigInt, exponent: i64) -> Result<String, Error> {
+		if self.radix == 10 {
+			return self.format_digits(coefficient.clone(), exponent, None);
+		}
+		let integer = match exponent {
+			0.. => coefficient * pow10(exponent)?,
+			_ if coefficient.is_multiple_of(&pow10(-exponent)?) => coefficient / pow10(-exponent)?,
+			_ => {
+				return Err(Error::Runtime(
+					"Only integers can be formatted in another radix!",
+				))
+			}
+		};
+		self.format_integer(&integer)
+	}
+
+	/// Formats `coefficient * 10^exponent` in radix 10, `digits` limits the significant digits
+	/// shown with automatic precision.
+	fn format_digits(
+		&self,
+		coefficient: BigInt,
+		exponent: i64,
+		digits: Option<u32>,
+	) -> Result<String, Error> {
+		let negative = coefficient.is_negative();
+		let (mut coefficient, mut exponent) = (coefficient.abs(), exponent);
+
+		// rounding can add a digit (like `9.96` to `10.0`), which changes the shown exponent
+		let (mantissa, shown, decimals) = loop {
+			let leading = leading_exponent(&coefficient, exponent);
+			let shown = self.shown_exponent(leading);
+			let decimals = match (self.precision, digits) {
+				(Precision::Fixed(decimals), _) => decimals as i64,
+				(Precision::Significant(digits), _) => digits.max(1) as i64 - 1 - (leading - shown),
+				(Precision::Auto, Some(digits)) => digits as i64 - 1 - (leading - shown),
+				(Precision::Auto, None) => (shown - exponent).max(0),
+			};
+			let mantissa = round(&coefficient, exponent - shown + decimals)?;
+			if mantissa.is_zero()
+				|| leading_exponent(&mantissa, shown - decimals) == leading
+				|| (coefficient == mantissa && exponent == shown - decimals)
+			{
+				break (mantissa, shown, decimals);
+			}
+			(coefficient, exponent) = (mantissa, shown - decimals);
+		};
+
+		let mut digits = mantissa.to_str_radix(10);
+		if decimals < 0 {
+			digits.push_str(&"0".repeat(decimals.unsigned_abs() as usize));
+		}
+		let decimals = decimals.max(0) as usize;
+		if digits.len() <= decimals {
+			digits = format!("{}{}", "0".repeat(decimals + 1 - digits.len()), digits);
+		}
+		let (integer, fraction) = digits.split_at(digits.len() - decimals);
+		let fraction = match self.precision {
+			Precision::Auto => fraction.trim_end_matches('0'),
+			_ => fraction,
+		};
+
+		let mut result = String::new();
+		if negative && !mantissa.is_zero() {
+			result.push('-');
+		}
+		result.push_str(&self.group(integer, 3));
+		if !fraction.is_empty() {
+			result.push('.');
+			result.push_str(fraction);
+		}
+		match self.notation {
+			Notation::Plain => (),
+			Notation::Scientific | Notation::Engineering => result.push_str(&format!("e{}", shown)),
+			Notation::Si => {
+				let (_, prefix) = SI_PREFIXES
+					.iter()
+					.find(|(exp, _)| *exp == shown)
+					.unwrap_or(&(0, ""));
+				result.push_str(prefix);
+			}
+		}
+		Ok(result)
+	}
+
+	/// Returns the exponent which is written for a number whose leading digit has the given exponent.
+	fn shown_exponent(&self, leading: i64) -> i64 {
+		match self.notation {
+			Notation::Plain => 0,
+			Notation::Scientific => leading,
+			Notation::Engineering => leading.div_euclid(3) * 3,
+			Notation::Si => (leading.div_euclid(3) * 3).clamp(-24, 24),
+		}
+	}
+
+	/// Inserts the separator between groups of digits, counted from the right.
+	fn group(&self, digits: &str, size: usize) -> String {
+		let Some(separator) = self.separator else {
+			return digits.to_string();
+		};
+		let mut result = String::new();
+		for (i, digit) in digits.chars().enumerate() {
+			if i > 0 && (digits.len() - i).is_multiple_of(size) {
+				result.push(separator);
+			}
+			result.push(digit);
+		}
+		result
+	}
+}
+
+/// Returns the exponent of the leading digit of `coefficient * 10^exponent`, e.g. `2` for `470`.
+fn leading_exponent(coefficient: &BigInt, exponent: i64) -> i64 {
+	if coefficient.is_zero() {
+		return 0;
+	}
+	coefficient.magnitude().to_str_radix(10).len() as i64 - 1 + exponent
+}
+
+/// Multiplies the non-negative coefficient by `10^shift`, rounding half away from zero.
+fn round(coefficient: &BigInt, shift: i64) -> Result<BigInt, Error> {
+	if shift >= 0 {
+		return Ok(coefficient * pow10(shift)?);
+	}
+	let divisor = pow10(-shift)?;
+	let (quotient, remainder) = coefficient.div_rem(&divisor);
+	if remainder * 2 >= divisor {
+		Ok(quotient + 1)
+	} else {
+		Ok(quotient)
+	}
+}
+
+fn pow10(exponent: i64) -> Result<BigInt, Error> {
+	u32::try_from(exponent)
+		.map(|exponent| BigInt::from(10).pow(exponent))
+		.map_err(|_| Error::Runtime("Number is out of range!"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn format(value: f64, notation: Notation, precision: Precision) -> String {
+		let format = NumberFormat {
+			notation,
+			precision,
+			..Default::default()
+		};
+		format.format_float(value, f64::DIGITS).unwrap()
+	}
+
+	#[test]
+	fn test_01_precision() {
+		assert_eq!(format(0.1 + 0.2, Notation::Plain, Precision::Auto), "0.3");
+		assert_eq!(
+			format(-1.5e-7, Notation::Plain, Precision::Auto),
+			"-0.00000015"
+		);
+		assert_eq!(
+			format(1e20, Notation::Plain, Precision::Auto),
+			"100000000000000000000"
+		);
+		assert_eq!(
+			format(2.0 / 3.0, Notation::Plain, Precision::Fixed(3)),
+			"0.667"
+		);
+		assert_eq!(format(2.5, Notation::Plain, Precision::Fixed(0)), "3");
+		assert_eq!(format(-0.001, Notation::Plain, Precision::Fixed(2)), "0.00");
+		assert_eq!(
+			format(1.2, Notation::Plain, Precision::Significant(4)),
+			"1.200"
+		);
+		assert_eq!(
+			format(12345.0, Notation::Plain, Precision::Significant(2)),
+			"12000"
+		);
+		assert_eq!(
+			format(9.96, Notation::Plain, Precision::Significant(2)),
+			"10"
+		);
+		assert_eq!(
+			format(0.0, Notation::Plain, Precision::Significant(3)),
+			"0.00"
+		);
+		assert_eq!(format(f64::NAN, Notation::Plain, Precision::Auto), "NaN");
+		assert_eq!(
+			format(f64::NEG_INFINITY, Notation::Si, Precision::Auto),
+			"-inf"
+		);
+	}
+
+	#[test]
+	fn test_02_notation() {
+		assert_eq!(
+			format(4700.0, Notation::Scientific, Precision::Auto),
+			"4.7e3"
+		);
+		assert_eq!(
+			format(-0.00047, Notation::Scientific, Precision::Auto),
+			"-4.7e-4"
+		);
+		assert_eq!(format(0.0, Notation::Scientific, Precision::Auto), "0e0");
+		assert_eq!(
+			format(9.96, Notation::Scientific, Precision::Significant(2)),
+			"1.0e1"
+		);
+		assert_eq!(
+			format(47000.0, Notation::Engineering, Precision::Auto),
+			"47e3"
+		);
+		assert_eq!(
+			format(0.00047, Notation::Engineering, Precision::Fixed(1)),
+			"470.0e-6"
+		);
+		assert_eq!(
+			format(999.96, Notation::Engineering, Precision::Fixed(1)),
+			"1.0e3"
+		);
+		assert_eq!(format(4700.0, Notation::Si, Precision::Auto), "4.7k");
+		assert_eq!(format(2.2e-5, Notation::Si, Precision::Auto), "22µ");
+		assert_eq!(format(150.0, Notation::Si, Precision::Auto), "150");
+		assert_eq!(format(3e27, Notation::Si, Precision::Auto), "3000Y");
+	}
+
+	#[test]
+	fn test_03_separator_and_radix() {
+		let format = |radix: u32, separator: Option<char>| NumberFormat {
+			radix,
+			separator,
+			..Default::default()
+		};
+		assert_eq!(
+			format(10, Some(',')).format_float(-1234567.25, 15).unwrap(),
+			"-1,234,567.25"
+		);
+		assert_eq!(
+			format(10, Some('_')).format_float(123.0, 15).unwrap(),
+			"123"
+		);
+		assert_eq!(
+			format(16, None).format_integer(&255.into()).unwrap(),
+			"0xff"
+		);
+		assert_eq!(
+			format(16, None).format_integer(&(-255).into()).unwrap(),
+			"-0xff"
+		);
+		assert_eq!(
+			format(2, Some('_')).format_float(37.0, 15).unwrap(),
+			"0b10_0101"
+		);
+		assert_eq!(
+			format(8, Some(' ')).format_integer(&4095.into()).unwrap(),
+			"0o7 777"
+		);
+		assert_eq!(
+			format(16, None).format_decimal(&12.into(), 1).unwrap(),
+			"0x78"
+		);
+		assert_eq!(
+			format(16, None).format_float(1.5, 15),
+			Err(Error::Runtime(
+				"Only integers can be formatted in another radix!"
+			))
+		);
+		assert_eq!(
+			format(3, None).format_integer(&1.into()),
+			Err(Error::Runtime("Radix must be 2, 8, 10 or 16!"))
+		);
+	}
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+
+use super::{exact_factorial, parse_literal};
+use crate::{Error, Number, NumberFormat};
+
+/// Maximal number of bits a calculated power or a literal may have.
+const MAX_BITS: u64 = 1 << 26;
+
+/// Arbitrary-size integer.
+///
+/// All operations are exact, operations which would have a non-integer result
+/// (like `7 / 2` or `sin 1`) fail with a runtime error.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Integer>::default();
+///
+/// let val = calculator.calculate("2 ** 100").unwrap();
+/// assert_eq!(val.to_string(), "1267650600228229401496703205376");
+///
+/// let err = calculator.calculate("7 / 2").unwrap_err();
+/// assert_eq!(err, Error::Runtime("Result is not an integer!"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(pub BigInt);
+
+impl Integer {
+	fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+}
+
+impl fmt::Display for Integer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl From<i64> for Integer {
+	fn from(value: i64) -> Self {
+		Self(value.into())
+	}
+}
+
+impl From<BigInt> for Integer {
+	fn from(value: BigInt) -> Self {
+		Self(value)
+	}
+}
+
+impl Number for Integer {
+	type Context = ();
+
+	fn parse(src: &str, _: &()) -> Result<Self, Error> {
+		let (coefficient, exponent) =
+			parse_literal(src).ok_or(Error::Fatal("Cannot parse number!"))?;
+		// a power of 10 has less than 10/3 bits per digit
+		if exponent.unsigned_abs().saturating_mul(10) / 3 > MAX_BITS {
+			return Err(Error::Runtime("Number is out of range!"));
+		}
+		let scale = BigInt::from(10).pow(exponent.unsigned_abs() as u32);
+		if exponent >= 0 {
+			return Ok(Self(coefficient * scale));
+		}
+		if !(&coefficient % &scale).is_zero() {
+			return Err(Error::Runtime("Number is not an integer!"));
+		}
+		Ok(Self(coefficient / scale))
+	}
+
+	fn from_f64(value: f64, _: &()) -> Result<Self, Error> {
+		if value.fract() != 0.0 {
+			return Err(Error::Runtime("Result is not an integer!"));
+		}
+		BigInt::from_f64(value)
+			.map(Self)
+			.ok_or(Error::Runtime("Result is not an integer!"))
+	}
+
+	fn to_f64(&self) -> f64 {
+		self.0.to_f64().unwrap_or(f64::NAN)
+	}
+
+	fn context(&self) {}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self(self.0 + rhs.0))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self(self.0 - rhs.0))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self(self.0 * rhs.0))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		if !(&self.0 % &rhs.0).is_zero() {
+			return Err(Error::Runtime("Result is not an integer!"));
+		}
+		Ok(Self(self.0 / rhs.0))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok(Self(self.0 % rhs.0))
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		let base = self.0;
+		let exp = rhs.0;
+
+		// bases whose powers stay small are handled without limiting the exponent
+		if base.is_zero() {
+			return if exp.is_negative() {
+				Err(Error::Runtime("Division by 0!"))
+			} else if exp.is_zero() {
+				Ok(Self(BigInt::one()))
+			} else {
+				Ok(Self(base))
+			};
+		}
+		if base.is_one() {
+			return Ok(Self(base));
+		}
+		if base == -BigInt::one() {
+			return Ok(Self(if exp.is_even() { BigInt::one() } else { base }));
+		}
+
+		if exp.is_negative() {
+			return Err(Error::Runtime("Result is not an integer!"));
+		}
+		match exp.to_u32() {
+			Some(exp) if base.bits().saturating_mul(exp as u64) <= MAX_BITS => {
+				Ok(Self(base.pow(exp)))
+			}
+			_ => Err(Error::Runtime("Number is out of range!")),
+		}
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		if rhs.0.is_negative() {
+			return Self(BigInt::one()).div(self.root(Self(-rhs.0))?);
+		}
+		let n = rhs.0.to_u32().unwrap_or(u32::MAX);
+		if self.0.is_negative() && n.is_multiple_of(2) {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		let root = self.0.nth_root(n);
+		if root.pow(n) != self.0 {
+			return Err(Error::Runtime("Result is not an integer!"));
+		}
+		Ok(Self(root))
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(Self(-self.0))
+	}
+
+	fn compare(&self, other: &Self) -> Result<std::cmp::Ordering, Error> {
+		Ok(self.cmp(other))
+	}
+
+	fn to_integer(&self) -> Option<BigInt> {
+		Some(self.0.clone())
+	}
+
+	fn from_integer(value: BigInt, _: &()) -> Result<Self, Error> {
+		Ok(Self(value))
+	}
+
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		format.format_integer(&self.0)
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		Ok(Self(exact_factorial(&self.0)?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn i(src: &str) -> Integer {
+		Integer::parse(src, &()).unwrap()
+	}
+
+	#[test]
+	fn test_01_parse() {
+		assert_eq!(i("42"), Integer::from(42));
+		assert_eq!(i("42.0"), Integer::from(42));
+		assert_eq!(i("4.2e1"), Integer::from(42));
+		assert_eq!(
+			Integer::parse("4.5", &()),
+			Err(Error::Runtime("Number is not an integer!"))
+		);
+	}
+
+	#[test]
+	fn test_02_arithmetic() {
+		assert_eq!(i("4").add(i("3")).unwrap(), Integer::from(7));
+		assert_eq!(i("4").sub(i("7")).unwrap(), Integer::from(-3));
+		assert_eq!(i("4").mul(i("3")).unwrap(), Integer::from(12));
+		assert_eq!(i("12").div(i("4")).unwrap(), Integer::from(3));
+		assert_eq!(i("-7").rem(i("3")).unwrap(), Integer::from(-1));
+		assert_eq!(i("27").root(i("3")).unwrap(), Integer::from(3));
+		assert_eq!(i("-27").root(i("3")).unwrap(), Integer::from(-3));
+		assert_eq!(i("-1").pow(i("-3")).unwrap(), Integer::from(-1));
+	}
+
+	#[test]
+	fn test_03_huge_numbers() {
+		assert_eq!(
+			i("2").pow(i("100")).unwrap().to_string(),
+			"1267650600228229401496703205376"
+		);
+		assert_eq!(
+			i("2")
+				.pow(i("100"))
+				.unwrap()
+				.add(i("1"))
+				.unwrap()
+				.to_string(),
+			"1267650600228229401496703205377"
+		);
+		assert_eq!(
+			i("2").pow(i("1000")).unwrap().rem(i("1000007")).unwrap(),
+			Integer::from(783922)
+		);
+		assert_eq!(
+			i("30").factorial().unwrap().to_string(),
+			"265252859812191058636308480000000"
+		);
+		assert_eq!(
+			i("10").pow(i("9999999999")),
+			Err(Error::Runtime("Number is out of range!"))
+		);
+	}
+
+	#[test]
+	fn test_04_non_integer_operations() {
+		let not_integer = Err(Error::Runtime("Result is not an integer!"));
+		assert_eq!(i("7").div(i("2")), not_integer);
+		assert_eq!(i("2").pow(i("-1")), not_integer);
+		assert_eq!(i("2").root(i("2")), not_integer);
+		assert_eq!(i("1").sin(), not_integer);
+		assert_eq!(i("0").sin().unwrap(), Integer::from(0));
+		assert!(Integer::pi(&()).is_err());
+		assert!(i("-1").factorial().is_err());
+	}
+
+	#[test]
+	fn test_05_division_by_0() {
+		assert!(i("4").div(i("0")).is_err());
+		assert!(i("4").rem(i("0")).is_err());
+		assert!(i("4").root(i("0")).is_err());
+		assert!(i("0").pow(i("-1")).is_err());
+	}
+
+	#[test]
+	fn test_06_large_literals() {
+		let err = Err(Error::Runtime("Number is out of range!"));
+		assert_eq!(Integer::parse("1e999999999", &()), err);
+		assert_eq!(Integer::parse("1e-999999999", &()), err);
+		assert_eq!(i("1e100").to_string(), format!("1{}", "0".repeat(100)));
+	}
+
+	#[test]
+	fn test_07_factorial_limit() {
+		assert_eq!(i("20000").factorial().unwrap().to_string().len(), 77338);
+		assert_eq!(
+			i("100000").factorial(),
+			Err(Error::Runtime("Number is out of range!"))
+		);
+	}
+}
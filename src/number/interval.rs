@@ -0,0 +1,612 @@
+use std::f64::consts::{E, FRAC_PI_2, PI, TAU};
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Zero};
+
+use super::parse_literal;
+use crate::{Error, Number, NumberFormat};
+
+/// Maximal exponent of a literal which is checked for exact representability.
+const MAX_EXACT_EXPONENT: u64 = 1000;
+
+/// Closed interval `[lo, hi]` of `f64` values.
+///
+/// All operations round outwards, so the resulting interval is guaranteed to contain the exact
+/// result for all numbers of the operands. Literals which cannot be represented exactly (like `0.1`)
+/// are enclosed by their neighbouring floating point numbers.
+/// Intervals can be created with the std function `interval`, e.g. `interval 1 2`.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Interval>::default();
+/// calculator.init_std();
+///
+/// let val = calculator.calculate("0.1 + 0.2").unwrap().into_number().unwrap();
+/// assert!(val.lo < 0.3 && 0.3 < val.hi);
+///
+/// let val = calculator.calculate("interval 1 2 * 3").unwrap();
+/// assert_eq!(val, Interval::new(3.0, 6.0));
+///
+/// let err = calculator.calculate("1 / interval -1 1").unwrap_err();
+/// assert_eq!(err, Error::IntervalContainsZero { lo: -1.0, hi: 1.0 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+	pub lo: f64,
+	pub hi: f64,
+}
+
+impl Interval {
+	pub fn new(lo: f64, hi: f64) -> Self {
+		Self { lo, hi }
+	}
+
+	pub fn point(value: f64) -> Self {
+		Self::new(value, value)
+	}
+
+	pub fn is_point(&self) -> bool {
+		self.lo == self.hi
+	}
+
+	pub fn contains(&self, value: f64) -> bool {
+		self.lo <= value && value <= self.hi
+	}
+
+	pub fn width(&self) -> f64 {
+		self.hi - self.lo
+	}
+
+	/// Returns the smallest interval containing both intervals.
+	pub fn hull(&self, other: &Self) -> Self {
+		Self::new(self.lo.min(other.lo), self.hi.max(other.hi))
+	}
+
+	/// Fails if the interval contains zero and therefore cannot be used as divisor.
+	fn check_divisor(&self) -> Result<(), Error> {
+		if self.contains(0.0) {
+			return Err(Error::IntervalContainsZero {
+				lo: self.lo,
+				hi: self.hi,
+			});
+		}
+		Ok(())
+	}
+
+	/// Applies a binary operation on all bound combinations and encloses the results.
+	fn combine(&self, rhs: &Self, op: fn(f64, f64) -> (f64, f64)) -> Self {
+		let bounds = [
+			op(self.lo, rhs.lo),
+			op(self.lo, rhs.hi),
+			op(self.hi, rhs.lo),
+			op(self.hi, rhs.hi),
+		];
+		bounds
+			.iter()
+			.skip(1)
+			.fold(Self::new(bounds[0].0, bounds[0].1), |result, (lo, hi)| {
+				Self::new(result.lo.min(*lo), result.hi.max(*hi))
+			})
+	}
+
+	/// Applies a monotonically increasing library function on the bounds.
+	fn increasing(&self, fun: fn(f64) -> f64) -> Self {
+		Self::new(widen(fun(self.lo)).0, widen(fun(self.hi)).1)
+	}
+
+	/// Checks if the interval contains a point `offset + k * period` for any integer `k`.
+	///
+	/// The period is not exact, so points close to the bounds are counted as contained.
+	fn contains_periodic(&self, offset: f64, period: f64) -> bool {
+		let k = ((self.lo - offset) / period).ceil();
+		[k - 1.0, k].iter().any(|k| {
+			let point = offset + k * period;
+			let tolerance = 8.0 * f64::EPSILON * point.abs().max(1.0);
+			self.lo - tolerance <= point && point <= self.hi + tolerance
+		})
+	}
+
+	/// Encloses `sin` or `cos` whose maxima are located at `max_at + 2kπ`.
+	fn sinusoid(&self, fun: fn(f64) -> f64, max_at: f64) -> Self {
+		if self.width().is_nan() || self.width() >= TAU || self.lo.abs().max(self.hi.abs()) > 1e15 {
+			return Self::new(-1.0, 1.0);
+		}
+		let mut result = Self::new(widen(fun(self.lo)).0, widen(fun(self.lo)).1)
+			.hull(&Self::new(widen(fun(self.hi)).0, widen(fun(self.hi)).1));
+		if self.contains_periodic(max_at, TAU) {
+			result.hi = 1.0;
+		}
+		if self.contains_periodic(max_at + PI, TAU) {
+			result.lo = -1.0;
+		}
+		Self::new(result.lo.max(-1.0), result.hi.min(1.0))
+	}
+
+	/// Calculates `self ** n` for a non-negative integer `n`.
+	fn powi(&self, n: u64) -> Result<Self, Error> {
+		if n == 0 {
+			return Ok(Self::point(1.0));
+		}
+		let lower = power(self.lo, n)?;
+		let upper = power(self.hi, n)?;
+		if n % 2 == 1 {
+			return Ok(Self::new(lower.lo, upper.hi));
+		}
+		if self.contains(0.0) {
+			return Ok(Self::new(0.0, lower.hi.max(upper.hi)));
+		}
+		if self.hi < 0.0 {
+			return Ok(Self::new(upper.lo, lower.hi));
+		}
+		Ok(Self::new(lower.lo, upper.hi))
+	}
+}
+
+/// Encloses the exact value `value + err`, where `err` is the rounding error of `value`.
+fn enclose(value: f64, err: f64) -> (f64, f64) {
+	if value.is_infinite() {
+		// overflow, the exact result lies beyond the largest finite number
+		return if value > 0.0 {
+			(f64::MAX, value)
+		} else {
+			(value, f64::MIN)
+		};
+	}
+	if err > 0.0 {
+		(value, value.next_up())
+	} else if err < 0.0 {
+		(value.next_down(), value)
+	} else {
+		(value, value)
+	}
+}
+
+/// Widens the result of a library function by one ulp in both directions.
+fn widen(value: f64) -> (f64, f64) {
+	(value.next_down(), value.next_up())
+}
+
+fn add_bounds(a: f64, b: f64) -> (f64, f64) {
+	let sum = a + b;
+	let b_virtual = sum - a;
+	let err = (a - (sum - b_virtual)) + (b - b_virtual);
+	enclose(sum, err)
+}
+
+fn mul_bounds(a: f64, b: f64) -> (f64, f64) {
+	let product = a * b;
+	if product.abs() < f64::MIN_POSITIVE && a != 0.0 && b != 0.0 {
+		// the rounding error of subnormal results cannot be represented
+		return widen(product);
+	}
+	enclose(product, a.mul_add(b, -product))
+}
+
+fn div_bounds(a: f64, b: f64) -> (f64, f64) {
+	let quotient = a / b;
+	if quotient.abs() < f64::MIN_POSITIVE && a != 0.0 {
+		return widen(quotient);
+	}
+	let remainder = (-quotient).mul_add(b, a);
+	enclose(quotient, if b > 0.0 { remainder } else { -remainder })
+}
+
+/// Encloses `base ** n` by repeated squaring.
+fn power(base: f64, mut n: u64) -> Result<Interval, Error> {
+	let mut result = Interval::point(1.0);
+	let mut base = Interval::point(base);
+	while n > 0 {
+		if n % 2 == 1 {
+			result = result.mul(base)?;
+		}
+		base = base.mul(base)?;
+		n /= 2;
+	}
+	Ok(result)
+}
+
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_point() {
+			write_bound(f, self.lo)
+		} else {
+			write!(f, "[")?;
+			write_bound(f, self.lo)?;
+			write!(f, ", ")?;
+			write_bound(f, self.hi)?;
+			write!(f, "]")
+		}
+	}
+}
+
+/// Writes the shortest representation of a bound, very small or large bounds in exponent form.
+fn write_bound(f: &mut fmt::Formatter, value: f64) -> fmt::Result {
+	if value != 0.0 && (value.abs() < 1e-7 || value.abs() >= 1e21) {
+		write!(f, "{:e}", value)
+	} else {
+		write!(f, "{}", value)
+	}
+}
+
+impl From<f64> for Interval {
+	fn from(value: f64) -> Self {
+		Self::point(value)
+	}
+}
+
+impl Number for Interval {
+	type Context = ();
+
+	fn parse(src: &str, _: &()) -> Result<Self, Error> {
+		let (coefficient, exponent) =
+			parse_literal(src).ok_or(Error::Fatal("Cannot parse number!"))?;
+		let value: f64 = src
+			.parse()
+			.map_err(|_| Error::Fatal("Cannot parse number!"))?;
+		if exponent.unsigned_abs() > MAX_EXACT_EXPONENT || value.is_infinite() {
+			return Ok(Self::new(value.next_down(), value.next_up()));
+		}
+
+		// compares the rounded value with the exact value of the literal
+		let scale = BigInt::from(10).pow(exponent.unsigned_abs() as u32);
+		let exact = if exponent >= 0 {
+			BigRational::from_integer(coefficient * scale)
+		} else {
+			BigRational::new(coefficient, scale)
+		};
+		let rounded = BigRational::from_float(value).unwrap_or_else(BigRational::zero);
+		let (lo, hi) = enclose(
+			value,
+			match exact.cmp(&rounded) {
+				std::cmp::Ordering::Greater => 1.0,
+				std::cmp::Ordering::Less => -1.0,
+				std::cmp::Ordering::Equal => 0.0,
+			},
+		);
+		Ok(Self::new(lo, hi))
+	}
+
+	fn from_f64(value: f64, _: &()) -> Result<Self, Error> {
+		Ok(Self::point(value))
+	}
+
+	/// Returns the midpoint of the interval.
+	fn to_f64(&self) -> f64 {
+		if self.is_point() {
+			self.lo
+		} else {
+			self.lo / 2.0 + self.hi / 2.0
+		}
+	}
+
+	fn context(&self) {}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(Self::new(
+			add_bounds(self.lo, rhs.lo).0,
+			add_bounds(self.hi, rhs.hi).1,
+		))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		self.add(rhs.neg()?)
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.combine(&rhs, mul_bounds))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		rhs.check_divisor()?;
+		Ok(self.combine(&rhs, div_bounds))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		// x % y = x - n * y is only continuous if the truncated quotient n is the same everywhere
+		let quotient = self.div(rhs)?;
+		let n = quotient.lo.trunc();
+		if n != quotient.hi.trunc() {
+			return Err(Error::Runtime(
+				"Remainder is not continuous on the interval!",
+			));
+		}
+		self.sub(rhs.mul(Self::point(n))?)
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_point() && rhs.lo.fract() == 0.0 && rhs.lo.abs() <= u32::MAX as f64 {
+			let n = rhs.lo.abs() as u64;
+			return if rhs.lo < 0.0 {
+				Self::point(1.0).div(self.powi(n)?)
+			} else {
+				self.powi(n)
+			};
+		}
+		if self.lo < 0.0 {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		if self.hi == 0.0 && rhs.lo > 0.0 {
+			return Ok(self);
+		}
+		rhs.mul(self.ln()?)?.exp()
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		rhs.check_divisor()?;
+		if rhs == Self::point(2.0) {
+			return self.sqrt();
+		}
+		let exp = Self::point(1.0).div(rhs)?;
+
+		// odd roots are defined for negative numbers as well
+		if self.lo < 0.0 && rhs.is_point() && rhs.lo.fract() == 0.0 && rhs.lo % 2.0 != 0.0 {
+			let odd_root = |value: f64| -> Result<Self, Error> {
+				let root = Self::point(value.abs()).pow(exp)?;
+				if value < 0.0 {
+					root.neg()
+				} else {
+					Ok(root)
+				}
+			};
+			return Ok(Self::new(odd_root(self.lo)?.lo, odd_root(self.hi)?.hi));
+		}
+		self.pow(exp)
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(Self::new(-self.hi, -self.lo))
+	}
+
+	fn to_integer(&self) -> Option<BigInt> {
+		let value = self.lo;
+		(self.is_point() && value.fract() == 0.0).then(|| BigInt::from_f64(value))?
+	}
+
+	/// Intervals are only ordered if the order holds for all of their values.
+	fn compare(&self, other: &Self) -> Result<std::cmp::Ordering, Error> {
+		if self.is_point() && self == other {
+			Ok(std::cmp::Ordering::Equal)
+		} else if self.hi < other.lo {
+			Ok(std::cmp::Ordering::Less)
+		} else if self.lo > other.hi {
+			Ok(std::cmp::Ordering::Greater)
+		} else {
+			Err(Error::Runtime(
+				"Order of the overlapping intervals is not defined!",
+			))
+		}
+	}
+
+	fn pi(_: &()) -> Result<Self, Error> {
+		Ok(Self::new(PI, PI.next_up()))
+	}
+
+	fn e(_: &()) -> Result<Self, Error> {
+		Ok(Self::new(E, E.next_up()))
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		Ok(self.sinusoid(f64::sin, FRAC_PI_2))
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		if self.hi < -1.0 || self.lo > 1.0 {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		let clamped = Self::new(self.lo.max(-1.0), self.hi.min(1.0));
+		Ok(clamped.increasing(f64::asin))
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		Ok(self.sinusoid(f64::cos, 0.0))
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		if self.hi < -1.0 || self.lo > 1.0 {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		let clamped = Self::new(self.lo.max(-1.0), self.hi.min(1.0));
+		Ok(Self::new(
+			widen(clamped.hi.acos()).0.max(0.0),
+			widen(clamped.lo.acos()).1,
+		))
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		if self.width().is_nan() || self.width() >= PI || self.contains_periodic(FRAC_PI_2, PI) {
+			return Ok(Self::new(f64::NEG_INFINITY, f64::INFINITY));
+		}
+		Ok(self.increasing(f64::tan))
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		Ok(self.increasing(f64::atan))
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		self.mul(Self::point(180.0))?.div(Self::pi(&())?)
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		self.mul(Self::pi(&())?)?.div(Self::point(180.0))
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		let result = self.increasing(f64::exp);
+		Ok(Self::new(result.lo.max(0.0), result.hi))
+	}
+
+	fn ln(self) -> Result<Self, Error> {
+		if self.lo < 0.0 {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		let lo = if self.lo > 0.0 {
+			widen(self.lo.ln()).0
+		} else {
+			f64::NEG_INFINITY
+		};
+		Ok(Self::new(lo, widen(self.hi.ln()).1))
+	}
+
+	fn sqrt(self) -> Result<Self, Error> {
+		if self.hi < 0.0 {
+			return Err(Error::Runtime("Result is not a real number!"));
+		}
+		let sqrt_bounds = |value: f64| {
+			let root = value.sqrt();
+			enclose(root, (-root).mul_add(root, value))
+		};
+		Ok(Self::new(
+			sqrt_bounds(self.lo.max(0.0)).0,
+			sqrt_bounds(self.hi).1,
+		))
+	}
+
+	fn abs(self) -> Result<Self, Error> {
+		if self.contains(0.0) {
+			Ok(Self::new(0.0, self.hi.max(-self.lo)))
+		} else if self.hi < 0.0 {
+			self.neg()
+		} else {
+			Ok(self)
+		}
+	}
+
+	fn arg(self) -> Result<Self, Error> {
+		let pi = Self::pi(&())?;
+		if self.lo >= 0.0 {
+			Ok(Self::point(0.0))
+		} else if self.hi < 0.0 {
+			Ok(pi)
+		} else {
+			Ok(Self::point(0.0).hull(&pi))
+		}
+	}
+
+	fn from_polar(r: Self, phi: Self) -> Result<Self, Error> {
+		if phi == Self::point(0.0) {
+			Ok(r)
+		} else {
+			Err(Error::Runtime("Result is not a real number!"))
+		}
+	}
+
+	fn interval(lo: Self, hi: Self) -> Result<Self, Error> {
+		Ok(lo.hull(&hi))
+	}
+
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		let lo = format.format_float(self.lo, f64::DIGITS)?;
+		if self.is_point() {
+			return Ok(lo);
+		}
+		let hi = format.format_float(self.hi, f64::DIGITS)?;
+		Ok(format!("[{}, {}]", lo, hi))
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		if !self.is_point() || self.lo < 0.0 || self.lo.fract() != 0.0 {
+			return Err(Error::Runtime(
+				"Factorial is only defined for non-negative integers!",
+			));
+		}
+		let mut result = Self::point(1.0);
+		let mut i = 2.0;
+		while i <= self.lo && result.hi.is_finite() {
+			result = result.mul(Self::point(i))?;
+			i += 1.0;
+		}
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn p(value: f64) -> Interval {
+		Interval::point(value)
+	}
+
+	fn i(lo: f64, hi: f64) -> Interval {
+		Interval::new(lo, hi)
+	}
+
+	#[test]
+	fn test_01_parse_display() {
+		assert_eq!(Interval::parse("4.5", &()).unwrap(), p(4.5));
+		assert_eq!(
+			Interval::parse("0.1", &()).unwrap(),
+			i(0.1f64.next_down(), 0.1)
+		);
+		assert_eq!(
+			Interval::parse("0.3", &()).unwrap(),
+			i(0.3, 0.3f64.next_up())
+		);
+		assert_eq!(p(4.5).to_string(), "4.5");
+		assert_eq!(i(1.0, 2.0).to_string(), "[1, 2]");
+		assert_eq!(i(-5e-324, 5e-324).to_string(), "[-5e-324, 5e-324]");
+		assert_eq!(p(1e300).to_string(), "1e300");
+	}
+
+	#[test]
+	fn test_02_arithmetic() {
+		assert_eq!(p(1.0).add(p(2.0)).unwrap(), p(3.0));
+		assert_eq!(i(1.0, 2.0).sub(i(0.0, 5.0)).unwrap(), i(-4.0, 2.0));
+		assert_eq!(i(-1.0, 2.0).mul(i(-3.0, 1.0)).unwrap(), i(-6.0, 3.0));
+		assert_eq!(i(1.0, 2.0).div(i(4.0, 8.0)).unwrap(), i(0.125, 0.5));
+		assert_eq!(p(7.0).rem(p(2.0)).unwrap(), p(1.0));
+		assert_eq!(i(-2.0, 3.0).pow(p(2.0)).unwrap(), i(0.0, 9.0));
+		assert_eq!(i(-2.0, 3.0).pow(p(3.0)).unwrap(), i(-8.0, 27.0));
+		assert_eq!(i(4.0, 9.0).root(p(2.0)).unwrap(), i(2.0, 3.0));
+		assert!(p(7.0).rem(i(2.0, 3.0)).is_err());
+	}
+
+	#[test]
+	fn test_03_outward_rounding() {
+		let sum = p(0.1).add(p(0.2)).unwrap();
+		assert_eq!(
+			sum,
+			i(0.30000000000000004f64.next_down(), 0.30000000000000004)
+		);
+
+		let third = p(1.0).div(p(3.0)).unwrap();
+		assert_eq!(third, i(1.0 / 3.0, (1.0f64 / 3.0).next_up()));
+
+		let root = p(2.0).sqrt().unwrap();
+		assert!(root.lo < root.hi && root.mul(root).unwrap().contains(2.0));
+		assert!(Interval::pi(&()).unwrap().contains(PI));
+	}
+
+	#[test]
+	fn test_04_functions() {
+		assert_eq!(i(0.0, 4.0).sin().unwrap().hi, 1.0);
+		assert_eq!(i(3.0, 4.0).cos().unwrap().lo, -1.0);
+		assert_eq!(i(0.0, 10.0).sin().unwrap(), i(-1.0, 1.0));
+		assert!(i(0.1, 0.2).sin().unwrap().contains(0.15f64.sin()));
+		assert_eq!(
+			i(1.0, 2.0).tan().unwrap(),
+			i(f64::NEG_INFINITY, f64::INFINITY)
+		);
+		assert_eq!(i(-2.0, 1.0).abs().unwrap(), i(0.0, 2.0));
+		assert!(i(-2.0, -1.0).ln().is_err());
+		assert!(i(-1.0, 1.0).ln().is_err());
+		assert_eq!(i(0.0, 1.0).ln().unwrap().lo, f64::NEG_INFINITY);
+		assert_eq!(i(-1.0, 1.0).pow(p(0.0)).unwrap(), p(1.0));
+		assert!(p(5.0).factorial().unwrap().contains(120.0));
+	}
+
+	#[test]
+	fn test_05_division_by_0() {
+		let err = Err(Error::IntervalContainsZero { lo: -1.0, hi: 1.0 });
+		assert_eq!(p(4.0).div(i(-1.0, 1.0)), err);
+		assert_eq!(p(4.0).rem(i(-1.0, 1.0)), err);
+		assert_eq!(p(4.0).root(i(-1.0, 1.0)), err);
+		assert!(p(0.0).pow(p(-1.0)).is_err());
+	}
+}
@@ -0,0 +1,281 @@
+use std::{
+	cmp::Ordering,
+	fmt::{Debug, Display},
+};
+
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive};
+
+use crate::Error;
+
+mod complex;
+pub use complex::*;
+mod decimal;
+pub use decimal::*;
+mod dual;
+pub use dual::*;
+mod float;
+mod format;
+pub use format::*;
+mod integer;
+pub use integer::*;
+mod interval;
+pub use interval::*;
+mod rational;
+pub use rational::*;
+mod word;
+pub use word::*;
+
+/// Numeric backend used by the calculator.
+///
+/// The lexer, parser and environment are generic over this trait, so the same machinery can be
+/// used with different number representations, e.g. `Calculator<f64>` or `Calculator<Rational>`.
+///
+/// Only the arithmetic operations and the conversions have to be implemented,
+/// the functions of the std lib fall back to `f64` arithmetic by default.
+pub trait Number: Clone + PartialEq + Debug + Display + 'static {
+	/// Configuration shared by all numbers of a calculator instance, e.g. the precision.
+	type Context: Clone + Default + 'static;
+
+	/// Parses a numerical literal found by the lexer, e.g. `4` or `4.5`.
+	fn parse(src: &str, ctx: &Self::Context) -> Result<Self, Error>;
+
+	/// Converts a `f64` value into this number representation.
+	fn from_f64(value: f64, ctx: &Self::Context) -> Result<Self, Error>;
+
+	/// Converts this number into a `f64` value (may lose precision).
+	fn to_f64(&self) -> f64;
+
+	/// Returns the context this number was created with.
+	fn context(&self) -> Self::Context;
+
+	fn add(self, rhs: Self) -> Result<Self, Error>;
+	fn sub(self, rhs: Self) -> Result<Self, Error>;
+	fn mul(self, rhs: Self) -> Result<Self, Error>;
+	fn div(self, rhs: Self) -> Result<Self, Error>;
+	fn rem(self, rhs: Self) -> Result<Self, Error>;
+	fn pow(self, rhs: Self) -> Result<Self, Error>;
+	fn root(self, rhs: Self) -> Result<Self, Error>;
+	fn neg(self) -> Result<Self, Error>;
+
+	/// Compares two numbers, numbers without an order like complex numbers result in an error.
+	fn compare(&self, other: &Self) -> Result<Ordering, Error> {
+		self.to_f64()
+			.partial_cmp(&other.to_f64())
+			.ok_or(Error::Runtime("Numbers are not comparable!"))
+	}
+
+	/// Converts an integral number exactly into an integer, e.g. for bitwise operators.
+	/// Returns `None` if the number isn't an integer.
+	fn to_integer(&self) -> Option<BigInt> {
+		let value = self.to_f64();
+		(value.fract() == 0.0).then(|| BigInt::from_f64(value))?
+	}
+
+	/// Converts an integer into this number representation.
+	fn from_integer(value: BigInt, ctx: &Self::Context) -> Result<Self, Error> {
+		Self::parse(&value.to_string(), ctx)
+	}
+
+	/// Applies a bitwise operator on the integer values, the result has the context of the left operand.
+	fn bitwise(
+		self,
+		rhs: Self,
+		op: fn(BigInt, BigInt) -> Result<BigInt, Error>,
+	) -> Result<Self, Error> {
+		match (self.to_integer(), rhs.to_integer()) {
+			(Some(left), Some(right)) => Self::from_integer(op(left, right)?, &self.context()),
+			_ => Err(Error::Runtime(
+				"Bitwise operators are only defined for integers!",
+			)),
+		}
+	}
+
+	/// Converts an integer into the range of the fixed-width type, wrapping around like two's complement.
+	fn cast(self, ty: WordType) -> Result<Self, Error> {
+		let value = self
+			.to_integer()
+			.ok_or(Error::Runtime("Only integers can be cast!"))?;
+		Self::from_integer(ty.wrap_integer(&value).into(), &self.context())
+	}
+
+	fn pi(ctx: &Self::Context) -> Result<Self, Error> {
+		Self::from_f64(std::f64::consts::PI, ctx)
+	}
+
+	fn e(ctx: &Self::Context) -> Result<Self, Error> {
+		Self::from_f64(std::f64::consts::E, ctx)
+	}
+
+	fn sin(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().sin(), &self.context())
+	}
+
+	fn asin(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().asin(), &self.context())
+	}
+
+	fn cos(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().cos(), &self.context())
+	}
+
+	fn acos(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().acos(), &self.context())
+	}
+
+	fn tan(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().tan(), &self.context())
+	}
+
+	fn atan(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().atan(), &self.context())
+	}
+
+	fn to_degrees(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().to_degrees(), &self.context())
+	}
+
+	fn to_radians(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().to_radians(), &self.context())
+	}
+
+	fn exp(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().exp(), &self.context())
+	}
+
+	/// Calculates the natural logarithm.
+	fn ln(self) -> Result<Self, Error> {
+		Self::from_f64(self.to_f64().ln(), &self.context())
+	}
+
+	fn sqrt(self) -> Result<Self, Error> {
+		let two = Self::from_f64(2.0, &self.context())?;
+		self.root(two)
+	}
+
+	/// Returns the absolute value (the magnitude for complex numbers).
+	fn abs(self) -> Result<Self, Error> {
+		if self.to_f64() < 0.0 {
+			self.neg()
+		} else {
+			Ok(self)
+		}
+	}
+
+	/// Returns the angle of the number in the complex plane.
+	fn arg(self) -> Result<Self, Error> {
+		if self.to_f64() < 0.0 {
+			Self::pi(&self.context())
+		} else {
+			Self::from_f64(0.0, &self.context())
+		}
+	}
+
+	/// Returns the real part.
+	fn re(self) -> Result<Self, Error> {
+		Ok(self)
+	}
+
+	/// Returns the imaginary part.
+	fn im(self) -> Result<Self, Error> {
+		Self::from_f64(0.0, &self.context())
+	}
+
+	/// Returns the complex conjugate.
+	fn conj(self) -> Result<Self, Error> {
+		Ok(self)
+	}
+
+	/// Creates a number from its polar coordinates, the magnitude `r` and the angle `phi`.
+	fn from_polar(r: Self, phi: Self) -> Result<Self, Error> {
+		if phi.to_f64() == 0.0 {
+			Ok(r)
+		} else {
+			Err(Error::Runtime("Result is not a real number!"))
+		}
+	}
+
+	/// Creates the smallest interval containing both numbers, only supported by interval types.
+	fn interval(lo: Self, hi: Self) -> Result<Self, Error> {
+		if lo == hi {
+			Ok(lo)
+		} else {
+			Err(Error::Runtime(
+				"Intervals are not supported by the number type!",
+			))
+		}
+	}
+
+	/// Returns the imaginary unit, real number types don't support it.
+	fn imaginary_unit(_: &Self::Context) -> Result<Self, Error> {
+		Err(Error::Runtime("Result is not a real number!"))
+	}
+
+	/// Formats the number as configured, e.g. with a fixed number of digits or in hexadecimal.
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		format.format_float(self.to_f64(), f64::DIGITS)
+	}
+
+	/// Calculates the factorial of a non-negative integer.
+	fn factorial(self) -> Result<Self, Error> {
+		let n = self.to_f64();
+		if n < 0.0 || n.fract() != 0.0 {
+			return Err(Error::Runtime(
+				"Factorial is only defined for non-negative integers!",
+			));
+		}
+		let ctx = self.context();
+		let mut result = Self::from_f64(1.0, &ctx)?;
+		let mut i = 2.0;
+		while i <= n {
+			result = result.mul(Self::from_f64(i, &ctx)?)?;
+			i += 1.0;
+		}
+		Ok(result)
+	}
+}
+
+/// Parses a decimal literal like `-4.5e3` into its exact value `coefficient * 10^exponent`.
+fn parse_literal(src: &str) -> Option<(BigInt, i64)> {
+	let (mantissa, exponent) = match src.find(['e', 'E']) {
+		Some(pos) => (&src[..pos], src[pos + 1..].parse::<i64>().ok()?),
+		None => (src, 0),
+	};
+	let (negative, mantissa) = match mantissa.strip_prefix('-') {
+		Some(mantissa) => (true, mantissa),
+		None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+	};
+	let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	if integer.is_empty() && fraction.is_empty()
+		|| !integer
+			.chars()
+			.chain(fraction.chars())
+			.all(|c| c.is_ascii_digit())
+	{
+		return None;
+	}
+
+	let mut coefficient: BigInt = format!("{}{}", integer, fraction).parse().ok()?;
+	if negative {
+		coefficient = -coefficient;
+	}
+	let exponent = exponent.checked_sub(fraction.len() as i64)?;
+	Some((coefficient, exponent))
+}
+
+/// Maximal operand of an exactly calculated factorial, whose result has about 77000 digits.
+const MAX_EXACT_FACTORIAL: u64 = 20_000;
+
+/// Calculates the factorial of the given integer exactly.
+fn exact_factorial(n: &BigInt) -> Result<BigInt, Error> {
+	if n.is_negative() {
+		return Err(Error::Runtime(
+			"Factorial is only defined for non-negative integers!",
+		));
+	}
+	let n = n
+		.to_u64()
+		.filter(|n| *n <= MAX_EXACT_FACTORIAL)
+		.ok_or(Error::Runtime("Number is out of range!"))?;
+	Ok((2..=n).fold(BigInt::one(), |result, i| result * i))
+}
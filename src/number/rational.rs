@@ -0,0 +1,364 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+
+use super::{exact_factorial, parse_literal};
+use crate::{Error, Number, NumberFormat};
+
+/// Maximal number of bits the numerator or denominator of an exactly calculated power or a literal may have.
+const MAX_EXACT_BITS: u64 = 1 << 20;
+
+/// Exact rational number.
+///
+/// Integers and fractions stay exact for all operators, the number only falls back to a
+/// floating point approximation if an irrational operation (like `sin` or `2 // 2`) is applied.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Rational>::default();
+///
+/// let val = calculator.calculate("1/3 + 1/6").unwrap();
+/// assert_eq!(val.to_string(), "1/2");
+///
+/// if let Value::Number(Rational::Exact(fraction)) = val {
+///     assert_eq!(fraction.numer().to_string(), "1");
+///     assert_eq!(fraction.denom().to_string(), "2");
+/// }
+///
+/// let val = calculator.calculate("2 // 2").unwrap();
+/// assert_eq!(val, Rational::Approx(2f64.sqrt()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rational {
+	/// Exact fraction.
+	Exact(BigRational),
+	/// Floating point approximation.
+	Approx(f64),
+}
+
+impl Rational {
+	pub fn is_exact(&self) -> bool {
+		matches!(self, Self::Exact(_))
+	}
+
+	/// Applies `exact` if both numbers are exact, otherwise `approx` is applied on their approximations.
+	fn binary(
+		self,
+		rhs: Self,
+		exact: impl FnOnce(BigRational, BigRational) -> BigRational,
+		approx: impl FnOnce(f64, f64) -> f64,
+	) -> Self {
+		match (self, rhs) {
+			(Self::Exact(left), Self::Exact(right)) => Self::Exact(exact(left, right)),
+			(left, right) => Self::Approx(approx(left.to_f64(), right.to_f64())),
+		}
+	}
+
+	fn is_zero(&self) -> bool {
+		match self {
+			Self::Exact(value) => value.is_zero(),
+			Self::Approx(value) => *value == 0.0,
+		}
+	}
+}
+
+/// Calculates `base ** exp` if the result is rational.
+fn exact_pow(base: &BigRational, exp: &BigRational) -> Result<Option<BigRational>, Error> {
+	if base.is_zero() {
+		return if exp.is_negative() {
+			Err(Error::Runtime("Division by 0!"))
+		} else if exp.is_zero() {
+			Ok(Some(BigRational::one()))
+		} else {
+			Ok(Some(BigRational::zero()))
+		};
+	}
+
+	let Some(denom) = exp.denom().to_u32() else {
+		return Ok(None);
+	};
+	let Some(root) = exact_root(base, denom) else {
+		return Ok(None);
+	};
+	// powers of 1 and -1 stay exact for all exponents
+	if root.abs().is_one() {
+		let odd = !(exp.numer() % 2u32).is_zero();
+		return Ok(Some(if odd { root } else { BigRational::one() }));
+	}
+	let bits = root.numer().bits().max(root.denom().bits());
+	match exp.numer().to_i32() {
+		Some(numer) if bits.saturating_mul(numer.unsigned_abs() as u64) <= MAX_EXACT_BITS => {
+			Ok(Some(root.pow(numer)))
+		}
+		_ => Err(Error::Runtime("Number is out of range!")),
+	}
+}
+
+/// Calculates the `n`-th root of the given value if it is rational.
+fn exact_root(value: &BigRational, n: u32) -> Option<BigRational> {
+	if n == 1 {
+		return Some(value.clone());
+	}
+	if value.is_negative() && n.is_multiple_of(2) {
+		return None;
+	}
+	let numer = value.numer().nth_root(n);
+	let denom = value.denom().nth_root(n);
+	if numer.pow(n) == *value.numer() && denom.pow(n) == *value.denom() {
+		Some(BigRational::new(numer, denom))
+	} else {
+		None
+	}
+}
+
+impl fmt::Display for Rational {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Exact(value) if value.is_integer() => write!(f, "{}", value.numer()),
+			Self::Exact(value) => write!(f, "{}/{}", value.numer(), value.denom()),
+			Self::Approx(value) => write!(f, "{}", value),
+		}
+	}
+}
+
+impl From<i64> for Rational {
+	fn from(value: i64) -> Self {
+		Self::Exact(BigRational::from_integer(value.into()))
+	}
+}
+
+impl Number for Rational {
+	type Context = ();
+
+	fn parse(src: &str, _: &()) -> Result<Self, Error> {
+		let (coefficient, exponent) =
+			parse_literal(src).ok_or(Error::Fatal("Cannot parse number!"))?;
+		// a power of 10 has less than 10/3 bits per digit
+		if exponent.unsigned_abs().saturating_mul(10) / 3 > MAX_EXACT_BITS {
+			return Err(Error::Runtime("Number is out of range!"));
+		}
+		let scale = BigInt::from(10).pow(exponent.unsigned_abs() as u32);
+		Ok(Self::Exact(if exponent >= 0 {
+			BigRational::from_integer(coefficient * scale)
+		} else {
+			BigRational::new(coefficient, scale)
+		}))
+	}
+
+	fn from_f64(value: f64, _: &()) -> Result<Self, Error> {
+		Ok(Self::Approx(value))
+	}
+
+	fn to_f64(&self) -> f64 {
+		match self {
+			Self::Exact(value) => value.to_f64().unwrap_or(f64::NAN),
+			Self::Approx(value) => *value,
+		}
+	}
+
+	fn context(&self) {}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.binary(rhs, |a, b| a + b, |a, b| a + b))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.binary(rhs, |a, b| a - b, |a, b| a - b))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.binary(rhs, |a, b| a * b, |a, b| a * b))
+	}
+
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok(self.binary(rhs, |a, b| a / b, |a, b| a / b))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok(self.binary(rhs, |a, b| a % b, |a, b| a % b))
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		if let (Self::Exact(base), Self::Exact(exp)) = (&self, &rhs) {
+			if let Some(result) = exact_pow(base, exp)? {
+				return Ok(Self::Exact(result));
+			}
+		}
+		Ok(Self::Approx(self.to_f64().powf(rhs.to_f64())))
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_zero() {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		match rhs {
+			Self::Exact(exp) => self.pow(Self::Exact(exp.recip())),
+			Self::Approx(exp) => Ok(Self::Approx(self.to_f64().powf(1.0 / exp))),
+		}
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(match self {
+			Self::Exact(value) => Self::Exact(-value),
+			Self::Approx(value) => Self::Approx(-value),
+		})
+	}
+
+	fn compare(&self, other: &Self) -> Result<std::cmp::Ordering, Error> {
+		match (self, other) {
+			(Self::Exact(left), Self::Exact(right)) => Ok(left.cmp(right)),
+			(left, right) => left
+				.to_f64()
+				.partial_cmp(&right.to_f64())
+				.ok_or(Error::Runtime("Numbers are not comparable!")),
+		}
+	}
+
+	fn to_integer(&self) -> Option<BigInt> {
+		match self {
+			Self::Exact(value) => value.is_integer().then(|| value.to_integer()),
+			Self::Approx(value) => (value.fract() == 0.0).then(|| BigInt::from_f64(*value))?,
+		}
+	}
+
+	fn from_integer(value: BigInt, _: &()) -> Result<Self, Error> {
+		Ok(Self::Exact(BigRational::from_integer(value)))
+	}
+
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		match self {
+			Self::Exact(value) if value.is_integer() => format.format_integer(value.numer()),
+			_ => format.format_float(self.to_f64(), f64::DIGITS),
+		}
+	}
+
+	fn factorial(self) -> Result<Self, Error> {
+		match self {
+			Self::Exact(value) if value.is_integer() => Ok(Self::Exact(BigRational::from_integer(
+				exact_factorial(value.numer())?,
+			))),
+			Self::Exact(_) => Err(Error::Runtime(
+				"Factorial is only defined for non-negative integers!",
+			)),
+			Self::Approx(value) => Ok(Self::Approx(value.factorial()?)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn r(src: &str) -> Rational {
+		Rational::parse(src, &()).unwrap()
+	}
+
+	fn fraction(numer: i64, denom: i64) -> Rational {
+		Rational::Exact(BigRational::new(numer.into(), denom.into()))
+	}
+
+	#[test]
+	fn test_01_parse_display() {
+		assert_eq!(r("4"), fraction(4, 1));
+		assert_eq!(r("4.5"), fraction(9, 2));
+		assert_eq!(r("0.125"), fraction(1, 8));
+		assert_eq!(r("1.5e2"), fraction(150, 1));
+		assert_eq!(r("4.5").to_string(), "9/2");
+		assert_eq!(r("-4").neg().unwrap().to_string(), "4");
+		assert_eq!(Rational::Approx(0.5).to_string(), "0.5");
+	}
+
+	#[test]
+	fn test_02_exact_arithmetic() {
+		let third = r("1").div(r("3")).unwrap();
+		let sixth = r("1").div(r("6")).unwrap();
+		assert_eq!(third.clone().add(sixth.clone()).unwrap(), fraction(1, 2));
+		assert_eq!(third.clone().sub(sixth.clone()).unwrap(), fraction(1, 6));
+		assert_eq!(third.clone().mul(sixth).unwrap(), fraction(1, 18));
+		assert_eq!(r("0.1").add(r("0.2")).unwrap(), fraction(3, 10));
+		assert_eq!(r("7").rem(r("2")).unwrap(), fraction(1, 1));
+		assert_eq!(r("-7").rem(r("2")).unwrap(), fraction(-1, 1));
+		assert_eq!(r("4.5").rem(third).unwrap(), fraction(1, 6));
+	}
+
+	#[test]
+	fn test_03_power() {
+		assert_eq!(r("2").pow(r("10")).unwrap(), fraction(1024, 1));
+		assert_eq!(r("2").pow(r("-2")).unwrap(), fraction(1, 4));
+		assert_eq!(
+			r("2").pow(r("100")).unwrap().to_string(),
+			"1267650600228229401496703205376"
+		);
+		assert_eq!(r("8").root(r("3")).unwrap(), fraction(2, 1));
+		assert_eq!(r("-8").root(r("3")).unwrap(), fraction(-2, 1));
+		assert_eq!(
+			r("4").div(r("9")).unwrap().pow(r("0.5")).unwrap(),
+			fraction(2, 3)
+		);
+		assert_eq!(r("0").pow(r("0")).unwrap(), fraction(1, 1));
+		assert_eq!(r("2").root(r("2")).unwrap(), Rational::Approx(2f64.sqrt()));
+	}
+
+	#[test]
+	fn test_04_fallback() {
+		assert!(!r("1").sin().unwrap().is_exact());
+		assert!(!Rational::pi(&()).unwrap().is_exact());
+
+		let approx = r("2").root(r("2")).unwrap();
+		assert!(!approx.clone().add(r("1")).unwrap().is_exact());
+		assert!(!r("1").mul(approx).unwrap().is_exact());
+	}
+
+	#[test]
+	fn test_05_division_by_0() {
+		assert!(r("4").div(r("0")).is_err());
+		assert!(r("4").rem(r("0")).is_err());
+		assert!(r("4").root(r("0")).is_err());
+		assert!(r("0").pow(r("-1")).is_err());
+		assert!(r("4").div(Rational::Approx(0.0)).is_err());
+	}
+
+	#[test]
+	fn test_06_factorial() {
+		assert_eq!(r("5").factorial().unwrap(), fraction(120, 1));
+		assert_eq!(
+			r("25").factorial().unwrap().to_string(),
+			"15511210043330985984000000"
+		);
+		assert!(r("0.5").factorial().is_err());
+		assert!(r("-1").factorial().is_err());
+	}
+
+	#[test]
+	fn test_07_large_literals() {
+		let err = Err(Error::Runtime("Number is out of range!"));
+		assert_eq!(Rational::parse("1e999999999", &()), err);
+		assert_eq!(Rational::parse("1e-999999999", &()), err);
+		assert_eq!(r("1e-100").to_string(), format!("1/1{}", "0".repeat(100)));
+	}
+
+	#[test]
+	fn test_08_large_powers() {
+		let err = Err(Error::Runtime("Number is out of range!"));
+		assert_eq!(r("2").pow(r("1000000")), err);
+		assert_eq!(r("2").pow(r("1e30")), err);
+		assert_eq!(r("0.5").pow(r("1e30")), err);
+		assert_eq!(r("1").pow(r("1e30")).unwrap(), fraction(1, 1));
+		assert_eq!(r("-1").pow(r("1e30")).unwrap(), fraction(1, 1));
+		assert_eq!(
+			r("-1").pow(r("1e30").add(r("1")).unwrap()).unwrap(),
+			fraction(-1, 1)
+		);
+	}
+}
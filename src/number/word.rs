@@ -0,0 +1,432 @@
+use std::{cmp::Ordering, fmt};
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use super::Integer;
+use crate::{Error, Number, NumberFormat};
+
+/// Width and signedness of a [`Word`], e.g. `u8` or `i64`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct WordType {
+	/// Number of bits, at most 64.
+	pub bits: u32,
+	pub signed: bool,
+}
+
+impl WordType {
+	pub const U8: Self = Self::new(8, false);
+	pub const U16: Self = Self::new(16, false);
+	pub const U32: Self = Self::new(32, false);
+	pub const U64: Self = Self::new(64, false);
+	pub const I8: Self = Self::new(8, true);
+	pub const I16: Self = Self::new(16, true);
+	pub const I32: Self = Self::new(32, true);
+	pub const I64: Self = Self::new(64, true);
+
+	/// All types, which are available as cast functions like `u8(300)`.
+	pub const ALL: [Self; 8] = [
+		Self::U8,
+		Self::U16,
+		Self::U32,
+		Self::U64,
+		Self::I8,
+		Self::I16,
+		Self::I32,
+		Self::I64,
+	];
+
+	pub const fn new(bits: u32, signed: bool) -> Self {
+		Self { bits, signed }
+	}
+
+	/// Returns the name of the type, e.g. `u8`.
+	pub fn name(&self) -> &'static str {
+		match (self.signed, self.bits) {
+			(false, 8) => "u8",
+			(false, 16) => "u16",
+			(false, 32) => "u32",
+			(false, _) => "u64",
+			(true, 8) => "i8",
+			(true, 16) => "i16",
+			(true, 32) => "i32",
+			(true, _) => "i64",
+		}
+	}
+
+	/// Wraps the value around into the range of this type like two's complement does,
+	/// signed values are sign extended.
+	fn wrap(&self, value: i128) -> i128 {
+		let shift = 128 - self.bits;
+		if self.signed {
+			(value << shift) >> shift
+		} else {
+			((value << shift) as u128 >> shift) as i128
+		}
+	}
+
+	/// Wraps an integer of arbitrary size into the range of this type.
+	pub(crate) fn wrap_integer(&self, value: &BigInt) -> i128 {
+		let low = value & BigInt::from(u128::MAX);
+		self.wrap(low.to_u128().unwrap_or_default() as i128)
+	}
+}
+
+impl Default for WordType {
+	fn default() -> Self {
+		Self::I64
+	}
+}
+
+impl fmt::Display for WordType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Fixed-width integer, which wraps around on overflow like two's complement does.
+///
+/// Number literals have no type of their own and take the type of the other operand,
+/// typed values are created by casts like `u8(300)`. If both operands are typed,
+/// the result has the wider type (the unsigned one if both are equally wide).
+/// The context is the type of the literals.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::<Word>::default();
+/// calculator.init_std();
+///
+/// let val = calculator.calculate("u8(250) + 10").unwrap();
+/// assert_eq!(val.to_string(), "4");
+///
+/// let val = calculator.calculate("i8(255)").unwrap();
+/// assert_eq!(val, Value::Number(Word::new(-1, WordType::I8)));
+/// assert_eq!(format!("{:x}", val.into_number().unwrap()), "ff");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Word {
+	/// The value is always within the range of the type.
+	value: i128,
+	ty: WordType,
+	/// Literals take the type of the other operand.
+	literal: bool,
+}
+
+impl Word {
+	/// Creates a typed word, the value is wrapped around into the range of the type.
+	pub fn new(value: i128, ty: WordType) -> Self {
+		Self {
+			value: ty.wrap(value),
+			ty,
+			literal: false,
+		}
+	}
+
+	fn literal(value: i128, ty: WordType) -> Self {
+		Self {
+			literal: true,
+			..Self::new(value, ty)
+		}
+	}
+
+	pub fn value(&self) -> i128 {
+		self.value
+	}
+
+	pub fn word_type(&self) -> WordType {
+		self.ty
+	}
+
+	/// Returns the bits of the value in two's complement.
+	fn bit_pattern(&self) -> u64 {
+		WordType::U64.wrap(self.value) as u64 & (u64::MAX >> (64 - self.ty.bits))
+	}
+
+	/// Applies the operation on the values and wraps the result into the type of the result.
+	fn combine(self, rhs: Self, op: impl FnOnce(i128, i128) -> i128) -> Self {
+		let ty = match (self.literal, rhs.literal) {
+			(true, false) => rhs.ty,
+			(false, true) | (true, true) => self.ty,
+			(false, false) => match self.ty.bits.cmp(&rhs.ty.bits) {
+				Ordering::Less => rhs.ty,
+				Ordering::Greater => self.ty,
+				Ordering::Equal if self.ty.signed => rhs.ty,
+				Ordering::Equal => self.ty,
+			},
+		};
+		Self {
+			literal: self.literal && rhs.literal,
+			..Self::new(op(self.value, rhs.value), ty)
+		}
+	}
+}
+
+/// Literals adopt the type of the other operand like in operations, e.g. `u8(255) == -1`.
+impl PartialEq for Word {
+	fn eq(&self, other: &Self) -> bool {
+		self.compare(other) == Ok(Ordering::Equal)
+	}
+}
+
+impl fmt::Display for Word {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.value)
+	}
+}
+
+impl fmt::LowerHex for Word {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::LowerHex::fmt(&self.bit_pattern(), f)
+	}
+}
+
+impl fmt::UpperHex for Word {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::UpperHex::fmt(&self.bit_pattern(), f)
+	}
+}
+
+impl fmt::Octal for Word {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Octal::fmt(&self.bit_pattern(), f)
+	}
+}
+
+impl fmt::Binary for Word {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Binary::fmt(&self.bit_pattern(), f)
+	}
+}
+
+impl Number for Word {
+	type Context = WordType;
+
+	fn parse(src: &str, ctx: &WordType) -> Result<Self, Error> {
+		Self::from_integer(Integer::parse(src, &())?.0, ctx)
+	}
+
+	fn from_f64(value: f64, ctx: &WordType) -> Result<Self, Error> {
+		Self::from_integer(Integer::from_f64(value, &())?.0, ctx)
+	}
+
+	fn to_f64(&self) -> f64 {
+		self.value as f64
+	}
+
+	fn context(&self) -> WordType {
+		self.ty
+	}
+
+	fn add(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.combine(rhs, i128::wrapping_add))
+	}
+
+	fn sub(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.combine(rhs, i128::wrapping_sub))
+	}
+
+	fn mul(self, rhs: Self) -> Result<Self, Error> {
+		Ok(self.combine(rhs, i128::wrapping_mul))
+	}
+
+	/// Divides truncating towards zero like integer division in most programming languages.
+	fn div(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.value == 0 {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok(self.combine(rhs, |left, right| left / right))
+	}
+
+	fn rem(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.value == 0 {
+			return Err(Error::Runtime("Division by 0!"));
+		}
+		Ok(self.combine(rhs, |left, right| left % right))
+	}
+
+	fn pow(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.value < 0 {
+			return match self.value {
+				0 => Err(Error::Runtime("Division by 0!")),
+				1 | -1 => Ok(self.combine(rhs, |base, exp| if exp % 2 == 0 { 1 } else { base })),
+				_ => Err(Error::Runtime("Result is not an integer!")),
+			};
+		}
+		// exponentiation by squaring, wrapping modulo 2^128 keeps the lower bits correct
+		Ok(self.combine(rhs, |mut base, mut exp| {
+			let mut result: i128 = 1;
+			while exp > 0 {
+				if exp % 2 == 1 {
+					result = result.wrapping_mul(base);
+				}
+				base = base.wrapping_mul(base);
+				exp /= 2;
+			}
+			result
+		}))
+	}
+
+	fn root(self, rhs: Self) -> Result<Self, Error> {
+		let root =
+			Integer::from(BigInt::from(self.value)).root(Integer::from(BigInt::from(rhs.value)))?;
+		let root = root.0.to_i128().unwrap_or_default();
+		Ok(self.combine(rhs, |_, _| root))
+	}
+
+	fn neg(self) -> Result<Self, Error> {
+		Ok(Self {
+			value: self.ty.wrap(self.value.wrapping_neg()),
+			..self
+		})
+	}
+
+	fn compare(&self, other: &Self) -> Result<Ordering, Error> {
+		// literals adopt the type of the other operand, other values are compared by their values
+		let (left, right) = match (self.literal, other.literal) {
+			(true, false) => (other.ty.wrap(self.value), other.value),
+			(false, true) => (self.value, self.ty.wrap(other.value)),
+			_ => (self.value, other.value),
+		};
+		Ok(left.cmp(&right))
+	}
+
+	fn to_integer(&self) -> Option<BigInt> {
+		Some(self.value.into())
+	}
+
+	fn from_integer(value: BigInt, ctx: &WordType) -> Result<Self, Error> {
+		Ok(Self::literal(ctx.wrap_integer(&value), *ctx))
+	}
+
+	fn bitwise(
+		self,
+		rhs: Self,
+		op: fn(BigInt, BigInt) -> Result<BigInt, Error>,
+	) -> Result<Self, Error> {
+		let result = op(self.value.into(), rhs.value.into())?;
+		let result = WordType::I64.wrap_integer(&result);
+		Ok(self.combine(rhs, |_, _| result))
+	}
+
+	fn cast(self, ty: WordType) -> Result<Self, Error> {
+		Ok(Self::new(self.value, ty))
+	}
+
+	/// Formats the two's complement bits in other radixes than 10, e.g. `0xff` for `i8(-1)`.
+	fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		match format.radix {
+			10 => format.format_integer(&self.value.into()),
+			_ => format.format_integer(&self.bit_pattern().into()),
+		}
+	}
+
+	/// Calculates the factorial wrapping around, which is `0` for large numbers.
+	fn factorial(self) -> Result<Self, Error> {
+		if self.value < 0 {
+			return Err(Error::Runtime(
+				"Factorial is only defined for non-negative integers!",
+			));
+		}
+		let mut result = Self { value: 1, ..self };
+		let mut i = 2;
+		while i <= self.value && result.value != 0 {
+			result = result.mul(Self { value: i, ..self })?;
+			i += 1;
+		}
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn w(value: i128, ty: WordType) -> Word {
+		Word::new(value, ty)
+	}
+
+	fn l(src: &str) -> Word {
+		Word::parse(src, &WordType::default()).unwrap()
+	}
+
+	#[test]
+	fn test_01_wrapping() {
+		assert_eq!(w(300, WordType::U8).value(), 44);
+		assert_eq!(w(255, WordType::I8).value(), -1);
+		assert_eq!(w(-1, WordType::U16).value(), 65535);
+		assert_eq!(w(1 << 63, WordType::I64).value(), i64::MIN as i128);
+		assert_eq!(w(-1, WordType::U64).value(), u64::MAX as i128);
+		assert_eq!(l("18446744073709551617"), w(1, WordType::I64));
+	}
+
+	#[test]
+	fn test_02_arithmetic() {
+		let x = w(200, WordType::U8);
+		assert_eq!(x.add(l("100")).unwrap(), w(44, WordType::U8));
+		assert_eq!(l("0").sub(x).unwrap(), w(56, WordType::U8));
+		assert_eq!(x.mul(x).unwrap(), w(64, WordType::U8));
+		assert_eq!(w(-128, WordType::I8).neg().unwrap(), w(-128, WordType::I8));
+		assert_eq!(
+			w(-128, WordType::I8).div(l("-1")).unwrap(),
+			w(-128, WordType::I8)
+		);
+		assert_eq!(l("-7").div(l("2")).unwrap(), l("-3"));
+		assert_eq!(l("-7").rem(l("2")).unwrap(), l("-1"));
+		assert_eq!(
+			l("3").pow(w(5, WordType::U8)).unwrap(),
+			w(243, WordType::U8)
+		);
+		assert_eq!(l("2").pow(l("64")).unwrap(), l("0"));
+		assert_eq!(w(40, WordType::U8).factorial().unwrap(), w(0, WordType::U8));
+		assert_eq!(l("5").factorial().unwrap(), l("120"));
+		assert_eq!(
+			l("2").pow(l("-1")),
+			Err(Error::Runtime("Result is not an integer!"))
+		);
+		assert_eq!(l("2").div(l("0")), Err(Error::Runtime("Division by 0!")));
+	}
+
+	#[test]
+	fn test_03_types() {
+		// the wider type wins, the unsigned one for equal widths
+		let sum = w(1, WordType::U8).add(w(1, WordType::I16)).unwrap();
+		assert_eq!(sum.word_type(), WordType::I16);
+		let sum = w(1, WordType::I32).add(w(1, WordType::U32)).unwrap();
+		assert_eq!(sum.word_type(), WordType::U32);
+		assert_eq!(l("1").add(l("1")).unwrap().word_type(), WordType::I64);
+
+		assert_eq!(
+			w(-1, WordType::I8).cast(WordType::U16).unwrap(),
+			w(65535, WordType::U16)
+		);
+		assert_eq!(
+			w(65535, WordType::U16).cast(WordType::I8).unwrap(),
+			w(-1, WordType::I8)
+		);
+	}
+
+	#[test]
+	fn test_04_comparison() {
+		// literals adopt the type of the other operand
+		assert_eq!(w(3, WordType::U8), l("3"));
+		assert_eq!(w(255, WordType::U8), l("-1"));
+		assert_ne!(w(3, WordType::U8), l("4"));
+		assert_ne!(w(255, WordType::U8), w(-1, WordType::I8));
+		assert_eq!(w(3, WordType::U8), w(3, WordType::I16));
+		assert_eq!(w(200, WordType::U8).compare(&l("-1")), Ok(Ordering::Less));
+	}
+
+	#[test]
+	fn test_05_bit_pattern() {
+		let x = w(-2, WordType::I8);
+		assert_eq!(format!("{:x}", x), "fe");
+		assert_eq!(format!("{:#X}", x), "0xFE");
+		assert_eq!(format!("{:b}", x), "11111110");
+		assert_eq!(format!("{:o}", w(-1, WordType::I16)), "177777");
+		assert_eq!(format!("{:x}", w(-1, WordType::I64)), "ffffffffffffffff");
+		assert_eq!(format!("{:#b}", w(5, WordType::U8)), "0b101");
+	}
+}
@@ -1,29 +1,62 @@
 use crate::{
-	AddOperator, Arguments, Cursor, Environment, Error, ExpOperator, MulOperator, TokenValue,
-	Variable,
+	duration, evaluate, find_root, find_roots, golden_section, index, list_or_matrix, nelder_mead,
+	quadrature, AddOperator, ArgumentList, Arguments, BitOperator, CmpOperator, Cursor, Derivative,
+	Environment, Equation, Error, ExpOperator, Integral, Integrand, LogicOperator, MulOperator,
+	Number, Polynomial, RealFunction, Token, TokenValue, Unit, UserFunction, Value, Variable,
 };
 
-pub struct Parser<'e> {
-	tokens: Cursor,
-	env: &'e mut Environment,
+/// Maximum depth of nested user function calls, so recursive functions can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Maximum nesting depth of atomic values like brackets, signs or arguments, so deeply nested input
+/// can't overflow the stack. The atomic values of the user functions being called are included.
+const MAX_NESTING: usize = 128;
+
+pub struct Parser<'e, N: Number> {
+	tokens: Cursor<N>,
+	env: &'e mut Environment<N>,
+	/// Number of user function calls this parser is nested in.
+	depth: usize,
+	/// Number of atomic values currently being evaluated, e.g. of brackets or operands of signs.
+	nesting: usize,
 }
 
-impl<'e> Parser<'e> {
-	pub fn new(tokens: Cursor, env: &'e mut Environment) -> Self {
-		Self { tokens, env }
+impl<'e, N: Number> Parser<'e, N> {
+	pub fn new(tokens: Cursor<N>, env: &'e mut Environment<N>) -> Self {
+		Self {
+			tokens,
+			env,
+			depth: 0,
+			nesting: 0,
+		}
 	}
 
-	pub fn evaluate(&mut self) -> Result<f32, Error> {
+	pub fn evaluate(&mut self) -> Result<Value<N>, Error> {
+		let result = self.evaluate_expression()?;
+		Ok(self.env.set_last_result(result))
+	}
+
+	/// Evaluates all tokens without storing the result as last result.
+	pub fn evaluate_expression(&mut self) -> Result<Value<N>, Error> {
 		let result = self.evaluate_statement()?;
 
 		// check if all tokens are consumed
 		self.tokens.expect(&TokenValue::Eof)?;
-		self.env.set_last_result(result);
 
 		Ok(result)
 	}
 
-	fn evaluate_statement(&mut self) -> Result<f32, Error> {
+	/// Evaluates all tokens as a single expression, assignments and function definitions aren't allowed.
+	pub(crate) fn evaluate_term(&mut self) -> Result<Value<N>, Error> {
+		let result = self.evaluate_or()?;
+		self.tokens.expect(&TokenValue::Eof)?;
+		Ok(result)
+	}
+
+	fn evaluate_statement(&mut self) -> Result<Value<N>, Error> {
+		if self.is_function_definition() {
+			return self.evaluate_function_definition();
+		}
 		if let (TokenValue::Identifier(_), TokenValue::Equals) = (
 			self.tokens
 				.current()
@@ -33,16 +66,16 @@ impl<'e> Parser<'e> {
 		) {
 			self.evaluate_assignment()
 		} else {
-			self.evaluate_additive()
+			self.evaluate_or()
 		}
 	}
 
-	fn evaluate_assignment(&mut self) -> Result<f32, Error> {
+	fn evaluate_assignment(&mut self) -> Result<Value<N>, Error> {
 		let id = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
 		if let TokenValue::Identifier(id) = id.value {
 			self.tokens.expect(&TokenValue::Equals)?;
 			let value = self.evaluate_statement()?;
-			self.env.assign_var(id, value);
+			self.env.assign_var(id, value.clone());
 			Ok(value)
 		} else {
 			Err(Error::UnexpectedToken {
@@ -53,102 +86,751 @@ impl<'e> Parser<'e> {
 		}
 	}
 
-	fn evaluate_additive(&mut self) -> Result<f32, Error> {
+	/// Checks for a function definition like `f(x, y) = ...`.
+	fn is_function_definition(&self) -> bool {
+		let token = |offset| self.tokens.peek(offset).map(|token| token.value);
+		if !matches!(token(0), Some(TokenValue::Identifier(_)))
+			|| token(1) != Some(TokenValue::OpenBracket)
+		{
+			return false;
+		}
+		let mut offset = 2;
+		loop {
+			match (token(offset), token(offset + 1)) {
+				(Some(TokenValue::Identifier(_)), Some(TokenValue::Comma)) => offset += 2,
+				(Some(TokenValue::Identifier(_)), Some(TokenValue::CloseBracket)) => {
+					return token(offset + 2) == Some(TokenValue::Equals)
+				}
+				_ => return false,
+			}
+		}
+	}
+
+	/// Defines a function like `f(x, y) = x * y`, the body is stored and evaluated on every call.
+	fn evaluate_function_definition(&mut self) -> Result<Value<N>, Error> {
+		let mut identifiers = Vec::new();
+		loop {
+			let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+			match token.value {
+				TokenValue::Identifier(id) => identifiers.push(id),
+				TokenValue::Equals => break,
+				_ => (),
+			}
+		}
+		let name = identifiers.remove(0);
+
+		let start = self.tokens.position();
+		self.skip_expression(None)?;
+		let body = self.tokens.slice(start);
+		if body.is_empty() {
+			return Err(Error::UnexpectedEndOfInput);
+		}
+		let function = Value::Function(UserFunction::new(name.clone(), identifiers, body));
+		self.env.assign_var(name, function.clone());
+		Ok(function)
+	}
+
+	fn evaluate_or(&mut self) -> Result<Value<N>, Error> {
+		let mut left = self.evaluate_and()?;
+
+		while self.tokens.get_logic_op(LogicOperator::Or)? {
+			let left_bool = left.into_bool()?;
+			left = Value::Bool(self.evaluate_logic_operand(left_bool, LogicOperator::Or)?);
+		}
+
+		Ok(left)
+	}
+
+	fn evaluate_and(&mut self) -> Result<Value<N>, Error> {
+		let mut left = self.evaluate_comparison()?;
+
+		while self.tokens.get_logic_op(LogicOperator::And)? {
+			let left_bool = left.into_bool()?;
+			left = Value::Bool(self.evaluate_logic_operand(left_bool, LogicOperator::And)?);
+		}
+
+		Ok(left)
+	}
+
+	/// Evaluates the right operand of a logical operator, which is skipped if the left operand
+	/// already decides the result, e.g. `x == 0 || 1/x > 2`.
+	fn evaluate_logic_operand(&mut self, left: bool, op: LogicOperator) -> Result<bool, Error> {
+		match (left, &op) {
+			(true, LogicOperator::Or) | (false, LogicOperator::And) => {
+				self.skip_expression(Some(op))?;
+				Ok(left)
+			}
+			(_, LogicOperator::Or) => self.evaluate_and()?.into_bool(),
+			(_, LogicOperator::And) => self.evaluate_comparison()?.into_bool(),
+		}
+	}
+
+	/// Comparisons can't be chained, so `1 < x < 3` has to be written as `1 < x && x < 3`.
+	fn evaluate_comparison(&mut self) -> Result<Value<N>, Error> {
+		let left = self.evaluate_conversion()?;
+
+		let Some(op) = self.tokens.get_cmp_op()? else {
+			return Ok(left);
+		};
+		let token = self.tokens.previous().ok_or(Error::UnexpectedEndOfInput)?;
+		let right = self.evaluate_conversion()?;
+		compare(left, right, op)
+			.map(Value::Bool)
+			.map_err(|e| e.with_span(token.start, token.end))
+	}
+
+	fn evaluate_conversion(&mut self) -> Result<Value<N>, Error> {
+		let mut value = self.evaluate_bitwise()?;
+
+		// `to` and `in` are only keywords at this position, so they can still be used as variables
+		while let Some(token) = self.tokens.current().filter(
+			|token| matches!(&token.value, TokenValue::Identifier(id) if id == "to" || id == "in"),
+		) {
+			self.tokens.consume();
+			let unit = self.evaluate_unit_expression()?;
+			value = value
+				.convert(&unit)
+				.map_err(|e| e.with_span(token.start, token.end))?;
+		}
+
+		Ok(value)
+	}
+
+	/// Evaluates all bitwise operators in one loop, so they don't need a level of recursion each.
+	/// The operands are combined as soon as no operator with a higher precedence can follow.
+	fn evaluate_bitwise(&mut self) -> Result<Value<N>, Error> {
+		let mut operands = vec![self.evaluate_additive()?];
+		let mut operators: Vec<BitOperator> = Vec::new();
+
+		while let Some(op) = self.tokens.get_bit_op()? {
+			while operators
+				.last()
+				.is_some_and(|last| last.precedence() >= op.precedence())
+			{
+				reduce_bitwise(&mut operands, &mut operators)?;
+			}
+			operators.push(op);
+			operands.push(self.evaluate_additive()?);
+		}
+		while !operators.is_empty() {
+			reduce_bitwise(&mut operands, &mut operators)?;
+		}
+
+		operands.pop().ok_or(Error::UnexpectedEndOfInput)
+	}
+
+	fn evaluate_additive(&mut self) -> Result<Value<N>, Error> {
 		let mut left = self.evaluate_multiplicative()?;
 
 		while let Some(op) = self.tokens.get_add_op()? {
+			let token = self.tokens.previous().ok_or(Error::UnexpectedEndOfInput)?;
 			let right = self.evaluate_multiplicative()?;
-			match op {
-				AddOperator::Add => left += right,
-				AddOperator::Sub => left -= right,
+			left = match op {
+				AddOperator::Add => left.add(right),
+				AddOperator::Sub => left.sub(right),
 			}
+			.map_err(|e| e.with_span(token.start, token.end))?;
 		}
 
 		Ok(left)
 	}
 
-	fn evaluate_multiplicative(&mut self) -> Result<f32, Error> {
+	fn evaluate_multiplicative(&mut self) -> Result<Value<N>, Error> {
 		let mut left = self.evaluate_exponential()?;
 
 		while let Some(op) = self.tokens.get_mul_op()? {
+			let token = self.tokens.previous().ok_or(Error::UnexpectedEndOfInput)?;
 			let right = self.evaluate_exponential()?;
-			match op {
-				MulOperator::Mul => left *= right,
-				MulOperator::Div => {
-					if right == 0.0 {
-						return Err(Error::Runtime("Division by 0!"));
-					}
-					left /= right
-				}
-				MulOperator::Mod => {
-					if right == 0.0 {
-						return Err(Error::Runtime("Division by 0!"));
-					}
-					left %= right
-				}
+			left = match op {
+				MulOperator::Mul => left.mul(right),
+				MulOperator::Div => left.div(right),
+				MulOperator::Mod => left.rem(right),
+				MulOperator::LeftDiv => left.left_div(right),
 			}
+			.map_err(|e| e.with_span(token.start, token.end))?;
 		}
 
 		Ok(left)
 	}
 
-	fn evaluate_exponential(&mut self) -> Result<f32, Error> {
+	fn evaluate_exponential(&mut self) -> Result<Value<N>, Error> {
 		let mut left = self.evaluate_atomic()?;
 
 		while let Some(op) = self.tokens.get_exp_op()? {
+			let token = self.tokens.previous().ok_or(Error::UnexpectedEndOfInput)?;
 			let right = self.evaluate_atomic()?;
-			match op {
-				ExpOperator::Power => left = left.powf(right),
-				ExpOperator::Root => {
-					if right == 0.0 {
-						return Err(Error::Runtime("Division by 0!"));
-					}
-					left = left.powf(1.0 / right)
-				}
+			left = match op {
+				ExpOperator::Power => left.pow(right),
+				ExpOperator::Root => left.root(right),
 			}
+			.map_err(|e| e.with_span(token.start, token.end))?;
 		}
 
 		Ok(left)
 	}
 
-	fn evaluate_atomic(&mut self) -> Result<f32, Error> {
+	fn evaluate_atomic(&mut self) -> Result<Value<N>, Error> {
+		if self.nesting >= MAX_NESTING {
+			return Err(Error::Runtime("Maximum nesting depth exceeded!"));
+		}
+		self.nesting += 1;
+		let result = self.evaluate_token();
+		self.nesting -= 1;
+		result
+	}
+
+	fn evaluate_token(&mut self) -> Result<Value<N>, Error> {
 		let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
 		match token.value {
-			TokenValue::Number(val) => Ok(val),
-			TokenValue::Identifier(id) => {
-				if let Some(var) = self.env.get(&id) {
-					match var {
-						Variable::Var(var) => Ok(*var),
-						Variable::Fn(fun) => fun.clone_box().call_with_args(self),
-					}
-				} else {
-					Err(Error::VariableNotFound {
-						var: id,
+			TokenValue::Number(val) => self.evaluate_number(val),
+			TokenValue::Date(date) => Ok(Value::Date(date)),
+			TokenValue::Time(time) => Ok(Value::Time(time)),
+			TokenValue::DateTime(date_time) => Ok(Value::DateTime(date_time)),
+			TokenValue::Duration(seconds) => duration(seconds, self.env.context()),
+			TokenValue::Bool(val) => Ok(Value::Bool(val)),
+			TokenValue::BitNot => self.evaluate_atomic()?.bit_not(),
+			TokenValue::Identifier(id) => self.evaluate_identifier(id, token.start, token.end),
+			TokenValue::LastResult => self.evaluate_last_result(token),
+			TokenValue::AddOperator(op) => match op {
+				AddOperator::Add => self.evaluate_atomic(),
+				AddOperator::Sub => self.evaluate_atomic()?.neg(),
+			},
+			TokenValue::Not => self.evaluate_atomic()?.not(),
+			TokenValue::If => self.evaluate_conditional(),
+			TokenValue::OpenBracket | TokenValue::OpenSquareBracket => self.evaluate_bracket(token),
+			TokenValue::Eof => Err(Error::UnexpectedEndOfInput),
+			_ => Err(Error::UnexpectedToken {
+				token: token.src,
+				start: token.start,
+				end: token.end,
+			}),
+		}
+	}
+
+	// the arms of `evaluate_token` are separate methods, because its stack frame limits the recursion depth
+
+	fn evaluate_number(&mut self, number: N) -> Result<Value<N>, Error> {
+		let value = self.evaluate_units(Value::Number(number))?;
+		self.evaluate_implicit_mul(value)
+	}
+
+	fn evaluate_last_result(&mut self, token: Token<N>) -> Result<Value<N>, Error> {
+		if let Some(var) = self.env.get_last_result() {
+			self.evaluate_index(var)
+		} else {
+			Err(Error::VariableNotFound {
+				var: token.src,
+				start: token.start,
+				end: token.end,
+			})
+		}
+	}
+
+	/// Evaluates a bracketed expression or a list, which can be indexed and followed by units.
+	fn evaluate_bracket(&mut self, token: Token<N>) -> Result<Value<N>, Error> {
+		let value = if token.value == TokenValue::OpenBracket {
+			let value = self.evaluate_or()?;
+			self.tokens.expect(&TokenValue::CloseBracket)?;
+			value
+		} else {
+			self.evaluate_list()?
+		};
+		let value = self.evaluate_index(value)?;
+		self.evaluate_units(value)
+	}
+
+	/// Evaluates a variable or a function call.
+	fn evaluate_identifier(
+		&mut self,
+		id: String,
+		start: usize,
+		end: usize,
+	) -> Result<Value<N>, Error> {
+		match self.env.get(&id).cloned() {
+			Some(Variable::Var(Value::Function(_) | Value::Polynomial(_)))
+			| Some(Variable::Fn(_))
+				if self.is_argument_list_next() =>
+			{
+				let args = self.evaluate_argument_list()?;
+				self.call(&id, args).map_err(|e| e.with_span(start, end))
+			}
+			Some(Variable::Var(Value::Function(fun))) => {
+				let args = (0..fun.params().len())
+					.map(|_| self.evaluate_atomic())
+					.collect::<Result<_, _>>()?;
+				self.call_function(&fun, args)
+					.map_err(|e| e.with_span(start, end))
+			}
+			Some(Variable::Var(value)) => self.evaluate_index(value),
+			Some(Variable::Fn(fun)) => fun
+				.call_with_args(self)
+				.map_err(|e| e.with_span(start, end)),
+			None if id == "diff" && self.is_argument_list_next() => self.evaluate_diff(),
+			None if id == "integrate" && self.is_argument_list_next() => self.evaluate_integrate(),
+			None if id == "solve" && self.is_argument_list_next() => self.evaluate_solve(),
+			None if (id == "minimize" || id == "maximize") && self.is_argument_list_next() => {
+				self.evaluate_minimize(&id)
+			}
+			None if (id == "roots" || id == "poly") && self.is_argument_list_next() => {
+				self.evaluate_polynomial(&id)
+			}
+			None => Err(Error::VariableNotFound {
+				var: id,
+				start,
+				end,
+			}),
+		}
+	}
+
+	/// Evaluates the elements of a list literal after the `[` token, e.g. `[1, 2, 3]`.
+	/// Lists of equally long lists of numbers are matrices, e.g. `[[1, 2], [3, 4]]`.
+	fn evaluate_list(&mut self) -> Result<Value<N>, Error> {
+		let elements = self.evaluate_elements(TokenValue::CloseSquareBracket)?;
+		Ok(list_or_matrix(elements))
+	}
+
+	/// Checks for bracketed arguments directly following a function name, e.g. `f(1, 2)`.
+	/// With whitespace the brackets belong to the first argument, like in `sin (pi / 2)`.
+	fn is_argument_list_next(&self) -> bool {
+		match (self.tokens.previous(), self.tokens.current()) {
+			(Some(previous), Some(current)) => {
+				current.value == TokenValue::OpenBracket && current.start == previous.end + 1
+			}
+			_ => false,
+		}
+	}
+
+	fn evaluate_argument_list(&mut self) -> Result<Vec<Value<N>>, Error> {
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		self.evaluate_elements(TokenValue::CloseBracket)
+	}
+
+	/// Evaluates comma separated values up to the closing bracket.
+	fn evaluate_elements(&mut self, close: TokenValue<N>) -> Result<Vec<Value<N>>, Error> {
+		let mut elements = Vec::new();
+		if self.tokens.current().map(|token| token.value) == Some(close.clone()) {
+			self.tokens.consume();
+			return Ok(elements);
+		}
+		loop {
+			elements.push(self.evaluate_or()?);
+			let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+			match token.value {
+				TokenValue::Comma => (),
+				value if value == close => return Ok(elements),
+				TokenValue::Eof => return Err(Error::UnexpectedEndOfInput),
+				_ => {
+					return Err(Error::UnexpectedToken {
+						token: token.src,
 						start: token.start,
 						end: token.end,
 					})
 				}
 			}
-			TokenValue::LastResult => {
-				if let Some(var) = self.env.get_last_result() {
-					Ok(var)
-				} else {
-					Err(Error::VariableNotFound {
-						var: token.src,
-						start: token.start,
-						end: token.end,
-					})
+		}
+	}
+
+	/// Calls the function with the given name with already evaluated arguments.
+	pub fn call(&mut self, id: &str, args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+		match self.env.get(id).cloned() {
+			Some(Variable::Var(Value::Function(fun))) => self.call_function(&fun, args),
+			Some(Variable::Fn(fun)) => {
+				let mut args = ArgumentList::new(args);
+				let result = fun.call_with_args(&mut args)?;
+				args.finish()?;
+				Ok(result)
+			}
+			Some(Variable::Var(Value::Polynomial(polynomial))) => {
+				let mut args = ArgumentList::new(args);
+				let x = args.get_next_arg()?;
+				args.finish()?;
+				evaluate(&polynomial, x)
+			}
+			_ => Err(Error::Runtime("Expected a function!")),
+		}
+	}
+
+	/// Evaluates the body of a user function with its parameters bound to the arguments.
+	fn call_function(
+		&mut self,
+		fun: &UserFunction<N>,
+		args: Vec<Value<N>>,
+	) -> Result<Value<N>, Error> {
+		if args.len() < fun.params().len() {
+			return Err(Error::Runtime("Too few arguments!"));
+		}
+		if args.len() > fun.params().len() {
+			return Err(Error::Runtime("Too many arguments!"));
+		}
+		if self.depth >= MAX_DEPTH {
+			return Err(Error::Runtime("Maximum recursion depth exceeded!"));
+		}
+
+		let previous: Vec<_> = fun
+			.params()
+			.iter()
+			.zip(args)
+			.map(|(param, arg)| self.env.replace(param, Some(Variable::Var(arg))))
+			.collect();
+		let result = Parser {
+			tokens: Cursor::with_eof(fun.body().to_vec()),
+			env: &mut *self.env,
+			depth: self.depth + 1,
+			nesting: self.nesting,
+		}
+		.evaluate_expression();
+		for (param, previous) in fun.params().iter().zip(previous).rev() {
+			self.env.replace(param, previous);
+		}
+		result
+	}
+
+	/// Evaluates `diff(<expression>, <variable>)` or `diff(<function>, <point>)` after the `diff` token.
+	/// The expression is not evaluated here, but with dual numbers by the differentiation.
+	fn evaluate_diff(&mut self) -> Result<Value<N>, Error> {
+		let differentiate = self
+			.env
+			.differentiate()
+			.ok_or(Error::Runtime("Derivatives can't be nested!"))?;
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		let start = self.tokens.position();
+		self.skip_expression(None)?;
+		let body = self.tokens.slice(start);
+		self.tokens.expect(&TokenValue::Comma)?;
+
+		// polynomials are differentiated directly, e.g. `diff(p, 2)`
+		if let [Token {
+			value: TokenValue::Identifier(name),
+			..
+		}] = body.as_slice()
+		{
+			if let Some(Variable::Var(Value::Polynomial(polynomial))) = self.env.get(name).cloned()
+			{
+				let at = self.evaluate_or()?;
+				self.tokens.expect(&TokenValue::CloseBracket)?;
+				return evaluate(&polynomial.derivative()?, at);
+			}
+		}
+
+		let derivative = match body.as_slice() {
+			[Token {
+				value: TokenValue::Identifier(name),
+				..
+			}] if matches!(
+				self.env.get(name),
+				Some(Variable::Fn(_) | Variable::Var(Value::Function(_)))
+			) =>
+			{
+				Derivative::Function {
+					name: name.clone(),
+					at: self.evaluate_or()?,
 				}
 			}
-			TokenValue::AddOperator(op) => match op {
-				AddOperator::Add => self.evaluate_atomic(),
-				AddOperator::Sub => Ok(-(self.evaluate_atomic()?)),
+			_ => {
+				let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+				match token.value {
+					TokenValue::Identifier(var) if self.env.get(&var).is_some() => {
+						Derivative::Expression { body, var }
+					}
+					TokenValue::Identifier(var) => {
+						return Err(Error::VariableNotFound {
+							var,
+							start: token.start,
+							end: token.end,
+						})
+					}
+					TokenValue::Eof => return Err(Error::UnexpectedEndOfInput),
+					_ => {
+						return Err(Error::UnexpectedToken {
+							token: token.src,
+							start: token.start,
+							end: token.end,
+						})
+					}
+				}
+			}
+		};
+		self.tokens.expect(&TokenValue::CloseBracket)?;
+		differentiate(self.env, derivative)
+	}
+
+	/// Evaluates `integrate(<function>, <a>, <b>)` or `integrate(<expression>, <variable>, <a>, <b>)`
+	/// after the `integrate` token. The variable is bound to the points of the quadrature.
+	fn evaluate_integrate(&mut self) -> Result<Value<N>, Error> {
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		let start = self.tokens.position();
+		self.skip_expression(None)?;
+		let body = self.tokens.slice(start);
+		self.tokens.expect(&TokenValue::Comma)?;
+
+		let integrand = match body.as_slice() {
+			[Token {
+				value: TokenValue::Identifier(name),
+				..
+			}] if self.is_function(name) => Integrand::Function(name.clone()),
+			_ => {
+				let var = self.expect_identifier()?;
+				self.tokens.expect(&TokenValue::Comma)?;
+				Integrand::Expression { body, var }
+			}
+		};
+		let a = self.evaluate_or()?.into_number()?;
+		self.tokens.expect(&TokenValue::Comma)?;
+		let b = self.evaluate_or()?.into_number()?;
+		self.tokens.expect(&TokenValue::CloseBracket)?;
+		Ok(Value::Number(self.integrate(&integrand, a, b)?.value))
+	}
+
+	/// Integrates a function or expression from `a` to `b` with the tolerance of the environment.
+	pub(crate) fn integrate(
+		&mut self,
+		integrand: &Integrand<N>,
+		a: N,
+		b: N,
+	) -> Result<Integral<N>, Error> {
+		let ctx = self.env.context().clone();
+		let tolerance = self.env.tolerance();
+		let (value, error) = quadrature(
+			|x| {
+				let x = Value::Number(N::from_f64(x, &ctx)?);
+				let y = match integrand {
+					Integrand::Function(name) => self.call(name, vec![x])?,
+					Integrand::Expression { body, var } => self.evaluate_at(body, var, x)?,
+				};
+				match y {
+					Value::Number(y) => Ok(y.to_f64()),
+					_ => Err(Error::Runtime("Only numbers can be integrated!")),
+				}
 			},
-			TokenValue::OpenBracket => {
-				let value = self.evaluate_additive();
+			a.to_f64(),
+			b.to_f64(),
+			tolerance,
+		)?;
+		Ok(Integral {
+			value: N::from_f64(value, &ctx)?,
+			error: N::from_f64(error, &ctx)?,
+		})
+	}
+
+	/// Evaluates `solve(<equation>, <variable>)` or `solve(<function>, <guess>)` after the `solve` token.
+	///
+	/// The equation may omit the right side `= 0`. A single number after the variable is the initial guess
+	/// (instead of the current value of the variable or `0`), two numbers are a range to search all roots in.
+	fn evaluate_solve(&mut self) -> Result<Value<N>, Error> {
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		let start = self.tokens.position();
+		self.skip_expression(None)?;
+		let body = self.tokens.slice(start);
+		self.tokens.expect(&TokenValue::Comma)?;
+
+		let mut args = Vec::new();
+		let (equation, guess) = match body.as_slice() {
+			[Token {
+				value: TokenValue::Identifier(name),
+				..
+			}] if self.is_function(name) => {
+				args.push(self.evaluate_or()?.into_number()?);
+				(Equation::Function(name.clone()), None)
+			}
+			_ => {
+				let var = self.expect_identifier()?;
+				let guess = match self.env.get(&var) {
+					Some(Variable::Var(Value::Number(x))) => Some(x.to_f64()),
+					_ => None,
+				};
+				let mut depth = 0usize;
+				let equals = body.iter().position(|token| {
+					match token.value {
+						TokenValue::OpenBracket | TokenValue::OpenSquareBracket => depth += 1,
+						TokenValue::CloseBracket | TokenValue::CloseSquareBracket => depth -= 1,
+						_ => (),
+					}
+					depth == 0 && token.value == TokenValue::Equals
+				});
+				let (left, right) = match equals {
+					Some(i) => (body[..i].to_vec(), Some(body[i + 1..].to_vec())),
+					None => (body, None),
+				};
+				(Equation::Expression { left, right, var }, guess)
+			}
+		};
+		while self.tokens.current().map(|token| token.value) == Some(TokenValue::Comma) {
+			self.tokens.consume();
+			args.push(self.evaluate_or()?.into_number()?);
+		}
+		self.tokens.expect(&TokenValue::CloseBracket)?;
+
+		let ctx = self.env.context().clone();
+		let tolerance = self.env.tolerance();
+		let mut f = EquationFunction {
+			parser: self,
+			equation: &equation,
+		};
+		match args.as_slice() {
+			[] | [_] => {
+				let guess = args.first().map_or(guess.unwrap_or(0.0), N::to_f64);
+				let root = find_root(&mut f, guess, tolerance)?;
+				Ok(Value::Number(N::from_f64(root, &ctx)?))
+			}
+			[a, b] => {
+				let roots = find_roots(&mut f, a.to_f64(), b.to_f64(), tolerance)?
+					.into_iter()
+					.map(|root| Ok(Value::Number(N::from_f64(root, &ctx)?)))
+					.collect::<Result<_, Error>>()?;
+				Ok(Value::List(roots))
+			}
+			_ => Err(Error::Runtime("Too many arguments!")),
+		}
+	}
+
+	/// Evaluates `minimize(<function>, <a>, <b>)` or `minimize(<function>, [<x0>, <y0>, ...])` after the function name,
+	/// `maximize` negates the function. The result is a list of the argument (a list for multiple arguments) and the value.
+	///
+	/// The minimum in a range is searched by the golden section search, from a start point by the Nelder-Mead method.
+	fn evaluate_minimize(&mut self, id: &str) -> Result<Value<N>, Error> {
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		let name = self.expect_identifier()?;
+		self.tokens.expect(&TokenValue::Comma)?;
+
+		let ctx = self.env.context().clone();
+		let tolerance = self.env.tolerance();
+		let sign = if id == "maximize" { -1.0 } else { 1.0 };
+		let number = |x: f64| Ok::<_, Error>(Value::Number(N::from_f64(x, &ctx)?));
+		let f = |parser: &mut Self, args: &[f64]| {
+			let args = args.iter().map(|x| number(*x)).collect::<Result<_, _>>()?;
+			match parser.call(&name, args)? {
+				Value::Number(y) => Ok(sign * y.to_f64()),
+				_ => Err(Error::Runtime(
+					"Only functions of numbers can be minimized!",
+				)),
+			}
+		};
+		let (x, y) = match self.evaluate_or()? {
+			Value::List(start) => {
+				self.tokens.expect(&TokenValue::CloseBracket)?;
+				let start = start
+					.into_iter()
+					.map(|x| Ok(x.into_number()?.to_f64()))
+					.collect::<Result<Vec<_>, Error>>()?;
+				let (x, y) = nelder_mead(&mut |x| f(self, x), &start, tolerance)?;
+				let x = x.into_iter().map(number).collect::<Result<_, _>>()?;
+				(Value::List(x), y)
+			}
+			a => {
+				let a = a.into_number()?.to_f64();
+				self.tokens.expect(&TokenValue::Comma)?;
+				let b = self.evaluate_or()?.into_number()?.to_f64();
 				self.tokens.expect(&TokenValue::CloseBracket)?;
-				value
+				let (x, y) = golden_section(&mut |x| f(self, &[x]), a, b, tolerance)?;
+				(number(x)?, y)
 			}
+		};
+		Ok(Value::List(vec![x, number(sign * y)?]))
+	}
+
+	/// Evaluates the difference of both sides of the equation.
+	fn evaluate_equation(&mut self, equation: &Equation<N>, x: f64) -> Result<f64, Error> {
+		let x = Value::Number(N::from_f64(x, self.env.context())?);
+		let y = match equation {
+			Equation::Function(name) => self.call(name, vec![x])?,
+			Equation::Expression { left, right, var } => {
+				let y = self.evaluate_at(left, var, x.clone())?;
+				match right {
+					Some(right) => y.sub(self.evaluate_at(right, var, x)?)?,
+					None => y,
+				}
+			}
+		};
+		match y {
+			Value::Number(y) => Ok(y.to_f64()),
+			_ => Err(Error::Runtime("Only equations of numbers can be solved!")),
+		}
+	}
+
+	/// Evaluates the larger magnitude of both sides of the equation, which limits the accuracy of their difference.
+	fn equation_magnitude(&mut self, equation: &Equation<N>, x: f64) -> Result<f64, Error> {
+		let Equation::Expression {
+			left,
+			right: Some(right),
+			var,
+		} = equation
+		else {
+			return Ok(0.0);
+		};
+		let x = Value::Number(N::from_f64(x, self.env.context())?);
+		let mut magnitude = 0.0f64;
+		for side in [left, right] {
+			if let Value::Number(y) = self.evaluate_at(side, var, x.clone())? {
+				magnitude = magnitude.max(y.to_f64().abs());
+			}
+		}
+		Ok(magnitude)
+	}
+
+	/// Differentiates the difference of both sides of the equation with `diff`, if it's available.
+	fn differentiate_equation(&mut self, equation: &Equation<N>, x: f64) -> Option<f64> {
+		let differentiate = self.env.differentiate()?;
+		let x = Value::Number(N::from_f64(x, self.env.context()).ok()?);
+		let derivative = match equation {
+			Equation::Function(name) => differentiate(
+				self.env,
+				Derivative::Function {
+					name: name.clone(),
+					at: x,
+				},
+			),
+			Equation::Expression { left, right, var } => {
+				let previous = self.env.replace(var, Some(Variable::Var(x)));
+				let derivative = |env: &Environment<N>, body: &Vec<Token<N>>| {
+					let body = body.clone();
+					let var = var.clone();
+					differentiate(env, Derivative::Expression { body, var })
+				};
+				let result = match right {
+					Some(right) => derivative(self.env, left)
+						.and_then(|left| left.sub(derivative(self.env, right)?)),
+					None => derivative(self.env, left),
+				};
+				self.env.replace(var, previous);
+				result
+			}
+		};
+		let derivative = derivative.ok()?.into_number().ok()?.to_f64();
+		derivative.is_finite().then_some(derivative)
+	}
+
+	/// Evaluates an expression with the variable bound to the value, the previous binding is restored afterwards.
+	fn evaluate_at(
+		&mut self,
+		body: &[Token<N>],
+		var: &str,
+		x: Value<N>,
+	) -> Result<Value<N>, Error> {
+		let previous = self.env.replace(var, Some(Variable::Var(x)));
+		let result = Parser {
+			tokens: Cursor::with_eof(body.to_vec()),
+			env: &mut *self.env,
+			depth: self.depth,
+			nesting: self.nesting,
+		}
+		.evaluate_expression();
+		self.env.replace(var, previous);
+		result
+	}
+
+	/// Checks if the identifier can be called with one argument, e.g. by `integrate` or `solve`.
+	fn is_function(&self, id: &str) -> bool {
+		matches!(
+			self.env.get(id),
+			Some(Variable::Fn(_) | Variable::Var(Value::Function(_) | Value::Polynomial(_)))
+		)
+	}
+
+	/// Consumes an identifier, e.g. the variable of `integrate`.
+	fn expect_identifier(&mut self) -> Result<String, Error> {
+		let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+		match token.value {
+			TokenValue::Identifier(id) => Ok(id),
 			TokenValue::Eof => Err(Error::UnexpectedEndOfInput),
 			_ => Err(Error::UnexpectedToken {
 				token: token.src,
@@ -157,27 +839,349 @@ impl<'e> Parser<'e> {
 			}),
 		}
 	}
+
+	/// Evaluates `roots(<polynomial>)` or `poly(<polynomial>)` after the function name.
+	///
+	/// The polynomial is an expression in a variable, which is the only undefined identifier like `y` in `y**2 - a`
+	/// or given as second argument, or a list of coefficients in descending order like `[1, 0, -4]`.
+	/// The identifier `x` is always a variable, even if it is defined.
+	fn evaluate_polynomial(&mut self, id: &str) -> Result<Value<N>, Error> {
+		self.tokens.expect(&TokenValue::OpenBracket)?;
+		let start = self.tokens.position();
+		self.skip_expression(None)?;
+		let body = self.tokens.slice(start);
+		let var = if self.tokens.current().map(|token| token.value) == Some(TokenValue::Comma) {
+			self.tokens.consume();
+			Some(self.expect_identifier()?)
+		} else {
+			let mut vars = body.iter().filter_map(|token| match &token.value {
+				TokenValue::Identifier(id)
+					if id == "x"
+						|| (self.env.get(id).is_none() && self.env.get_unit(id).is_none()) =>
+				{
+					Some(id.clone())
+				}
+				_ => None,
+			});
+			let var = vars.next();
+			if vars.any(|other| Some(other) != var) {
+				return Err(Error::Runtime("Expected a polynomial in one variable!"));
+			}
+			var
+		};
+		self.tokens.expect(&TokenValue::CloseBracket)?;
+
+		let previous = match &var {
+			Some(var) => {
+				let x = Polynomial::variable(var.clone(), self.env.context())?;
+				Some(
+					self.env
+						.replace(var, Some(Variable::Var(Value::Polynomial(x)))),
+				)
+			}
+			None => None,
+		};
+		let value = Parser {
+			tokens: Cursor::with_eof(body),
+			env: &mut *self.env,
+			depth: self.depth,
+			nesting: self.nesting,
+		}
+		.evaluate_expression();
+		if let (Some(var), Some(previous)) = (&var, previous) {
+			self.env.replace(var, previous);
+		}
+
+		let var = var.unwrap_or_else(|| "x".to_owned());
+		let polynomial = match value? {
+			Value::Polynomial(polynomial) => polynomial,
+			Value::Number(number) => Polynomial::constant(var, number),
+			Value::List(coefficients) => Polynomial::new(
+				var,
+				coefficients
+					.into_iter()
+					.map(Value::into_number)
+					.collect::<Result<_, _>>()?,
+			),
+			_ => return Err(Error::Runtime("Expected a polynomial!")),
+		};
+		if id == "roots" {
+			let roots = polynomial.roots()?.into_iter().map(Value::Number).collect();
+			Ok(Value::List(roots))
+		} else {
+			Ok(Value::Polynomial(polynomial))
+		}
+	}
+
+	/// Applies the indices following a value, e.g. `v[0]`.
+	/// The index has to follow the value without whitespace, so `dot v [1, 2]` passes two lists.
+	fn evaluate_index(&mut self, mut value: Value<N>) -> Result<Value<N>, Error> {
+		while let (Some(previous), Some(current)) = (self.tokens.previous(), self.tokens.current())
+		{
+			if current.value != TokenValue::OpenSquareBracket || current.start != previous.end + 1 {
+				break;
+			}
+			self.tokens.consume();
+			let position = self.evaluate_or()?;
+			self.tokens.expect(&TokenValue::CloseSquareBracket)?;
+			value = index(value, position)?;
+		}
+		Ok(value)
+	}
+
+	/// Evaluates `if <condition> then <value> else <value>` after the `if` token,
+	/// the branch which is not taken is skipped without evaluating it.
+	/// The else branch extends as far as possible, like the right side of an assignment.
+	fn evaluate_conditional(&mut self) -> Result<Value<N>, Error> {
+		let condition = self.evaluate_or()?.into_bool()?;
+		self.tokens.expect(&TokenValue::Then)?;
+		if condition {
+			let value = self.evaluate_or()?;
+			self.tokens.expect(&TokenValue::Else)?;
+			self.skip_expression(None)?;
+			Ok(value)
+		} else {
+			self.skip_expression(None)?;
+			self.tokens.expect(&TokenValue::Else)?;
+			self.evaluate_or()
+		}
+	}
+
+	/// Skips the tokens of an expression up to the end of the enclosing bracket, list element or input,
+	/// up to the next `then` or `else`, or up to the next logical operator binding as weak as `until`.
+	fn skip_expression(&mut self, mut until: Option<LogicOperator>) -> Result<(), Error> {
+		let mut depth = 0usize;
+		let mut conditionals = 0usize;
+		loop {
+			let token = self.tokens.current().ok_or(Error::UnexpectedEndOfInput)?;
+			let top_level = depth == 0 && conditionals == 0;
+			match token.value {
+				TokenValue::OpenBracket | TokenValue::OpenSquareBracket => depth += 1,
+				TokenValue::CloseBracket | TokenValue::CloseSquareBracket | TokenValue::Comma
+					if depth == 0 =>
+				{
+					return Ok(())
+				}
+				TokenValue::CloseBracket | TokenValue::CloseSquareBracket => depth -= 1,
+				TokenValue::If => conditionals += 1,
+				TokenValue::Else if conditionals > 0 => {
+					conditionals -= 1;
+					// the else branch of a nested conditional extends over logical operators
+					if depth == 0 && conditionals == 0 {
+						until = None;
+					}
+				}
+				TokenValue::Then | TokenValue::Else if top_level => return Ok(()),
+				TokenValue::LogicOperator(op) if top_level => match (&until, op) {
+					(Some(LogicOperator::And), _)
+					| (Some(LogicOperator::Or), LogicOperator::Or) => return Ok(()),
+					_ => (),
+				},
+				TokenValue::Eof => return Ok(()),
+				_ => (),
+			}
+			self.tokens.consume();
+		}
+	}
+
+	/// Applies the units following a value, e.g. `5 m`, `9.81 m s**-2` or `1 km/h`.
+	/// Identifiers are only treated as units at this position, so they cannot collide with variables.
+	fn evaluate_units(&mut self, mut value: Value<N>) -> Result<Value<N>, Error> {
+		if !self.is_unit_next() {
+			return Ok(value);
+		}
+		loop {
+			if self.is_unit_next() {
+				value = value.mul_unit(&self.evaluate_unit_power()?)?;
+			} else if self.is_unit_division_next() {
+				self.tokens.consume();
+				value = value.mul_unit(&self.evaluate_unit_power()?.powi(-1)?)?;
+			} else {
+				return Ok(value);
+			}
+		}
+	}
+
+	/// Multiplies a number literal with a directly following identifier, e.g. `6x` or `2pi`.
+	fn evaluate_implicit_mul(&mut self, value: Value<N>) -> Result<Value<N>, Error> {
+		match (self.tokens.previous(), self.tokens.current()) {
+			(Some(previous), Some(current))
+				if matches!(previous.value, TokenValue::Number(_))
+					&& matches!(current.value, TokenValue::Identifier(_))
+					&& current.start == previous.end + 1 =>
+			{
+				value.mul(self.evaluate_exponential()?)
+			}
+			_ => Ok(value),
+		}
+	}
+
+	/// Evaluates the target unit of a conversion, e.g. `km/h` or `kg*m/s**2`.
+	fn evaluate_unit_expression(&mut self) -> Result<Unit<N>, Error> {
+		let mut unit = self.evaluate_unit_power()?;
+		loop {
+			let token = self.tokens.current().ok_or(Error::UnexpectedEndOfInput)?;
+			let right = match token.value {
+				TokenValue::MulOperator(MulOperator::Mul) => {
+					self.tokens.consume();
+					self.evaluate_unit_power()?
+				}
+				TokenValue::MulOperator(MulOperator::Div) => {
+					self.tokens.consume();
+					self.evaluate_unit_power()?.powi(-1)?
+				}
+				_ if self.is_unit_next() => self.evaluate_unit_power()?,
+				_ => return Ok(unit),
+			};
+			let (product, conversion) = unit.mul(&right, self.env.context())?;
+			if conversion.is_some() {
+				return Err(Error::Runtime(
+					"Units of the same dimension cannot be combined!",
+				));
+			}
+			unit = product;
+		}
+	}
+
+	/// Evaluates a single unit with an optional integer exponent, e.g. `m` or `s**-2`.
+	fn evaluate_unit_power(&mut self) -> Result<Unit<N>, Error> {
+		let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+		let mut unit = match &token.value {
+			TokenValue::Identifier(id) => {
+				self.env.get_unit(id).ok_or_else(|| Error::UnitNotFound {
+					unit: id.clone(),
+					start: token.start,
+					end: token.end,
+				})?
+			}
+			TokenValue::Eof => return Err(Error::UnexpectedEndOfInput),
+			_ => {
+				return Err(Error::UnexpectedToken {
+					token: token.src,
+					start: token.start,
+					end: token.end,
+				});
+			}
+		};
+
+		// exponents directly belong to the unit, so `5 m**2` is an area
+		let is_exponent = matches!(
+			self.tokens.current().map(|token| token.value),
+			Some(TokenValue::ExpOperator(ExpOperator::Power))
+		) && matches!(
+			self.tokens.next().map(|token| token.value),
+			Some(TokenValue::Number(_) | TokenValue::AddOperator(AddOperator::Sub))
+		);
+		if is_exponent {
+			self.tokens.consume();
+			let negative = self.tokens.get_add_op()?.is_some();
+			let token = self.tokens.consume().ok_or(Error::UnexpectedEndOfInput)?;
+			let exponent = match token.value {
+				TokenValue::Number(exponent) => exponent.to_f64(),
+				_ => f64::NAN,
+			};
+			if exponent.fract() != 0.0 || exponent.abs() > i32::MAX as f64 {
+				return Err(Error::UnexpectedToken {
+					token: token.src,
+					start: token.start,
+					end: token.end,
+				});
+			}
+			unit = unit.powi(if negative { -exponent } else { exponent } as i32)?;
+		}
+		Ok(unit)
+	}
+
+	fn is_unit_next(&self) -> bool {
+		match self.tokens.current().map(|token| token.value) {
+			Some(TokenValue::Identifier(id)) => self.env.get_unit(&id).is_some(),
+			_ => false,
+		}
+	}
+
+	/// Checks for a division by a unit, e.g. the `/h` of `1 km/h`.
+	fn is_unit_division_next(&self) -> bool {
+		matches!(
+			self.tokens.current().map(|token| token.value),
+			Some(TokenValue::MulOperator(MulOperator::Div))
+		) && match self.tokens.next().map(|token| token.value) {
+			Some(TokenValue::Identifier(id)) => self.env.get_unit(&id).is_some(),
+			_ => false,
+		}
+	}
 }
 
-impl<'e> Arguments for Parser<'e> {
-	fn get_next_arg(&mut self) -> Result<f32, Error> {
+/// Applies the comparison operator, which is kept out of the parser's methods to limit their stack frames.
+fn compare<N: Number>(left: Value<N>, right: Value<N>, op: CmpOperator) -> Result<bool, Error> {
+	match op {
+		CmpOperator::Equal => left.equals(right),
+		CmpOperator::NotEqual => left.equals(right).map(|equal| !equal),
+		CmpOperator::Less => left.compare(right).map(|ord| ord.is_lt()),
+		CmpOperator::LessEqual => left.compare(right).map(|ord| ord.is_le()),
+		CmpOperator::Greater => left.compare(right).map(|ord| ord.is_gt()),
+		CmpOperator::GreaterEqual => left.compare(right).map(|ord| ord.is_ge()),
+	}
+}
+
+/// Combines the last two operands with the last operator.
+fn reduce_bitwise<N: Number>(
+	operands: &mut Vec<Value<N>>,
+	operators: &mut Vec<BitOperator>,
+) -> Result<(), Error> {
+	let (Some(op), Some(right), Some(left)) = (operators.pop(), operands.pop(), operands.pop())
+	else {
+		return Err(Error::UnexpectedEndOfInput);
+	};
+	operands.push(match op {
+		BitOperator::And => left.bit_and(right)?,
+		BitOperator::Or => left.bit_or(right)?,
+		BitOperator::Xor => left.bit_xor(right)?,
+		BitOperator::ShiftLeft => left.shl(right)?,
+		BitOperator::ShiftRight => left.shr(right)?,
+	});
+	Ok(())
+}
+
+impl<'e, N: Number> Arguments<N> for Parser<'e, N> {
+	fn get_next_arg(&mut self) -> Result<Value<N>, Error> {
 		self.evaluate_atomic()
 	}
 }
 
+/// Equation which is evaluated by a parser for the root finding of `solve`.
+struct EquationFunction<'p, 'e, N: Number> {
+	parser: &'p mut Parser<'e, N>,
+	equation: &'p Equation<N>,
+}
+
+impl<N: Number> RealFunction for EquationFunction<'_, '_, N> {
+	fn value(&mut self, x: f64) -> Result<f64, Error> {
+		self.parser.evaluate_equation(self.equation, x)
+	}
+
+	fn derivative(&mut self, x: f64) -> Option<f64> {
+		self.parser.differentiate_equation(self.equation, x)
+	}
+
+	fn magnitude(&mut self, x: f64) -> Result<f64, Error> {
+		self.parser.equation_magnitude(self.equation, x)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::Token;
 
-	fn new_t(value: TokenValue) -> Token {
+	fn new_t(value: TokenValue<f64>) -> Token<f64> {
 		Token::new(value, "".to_owned(), 0, 0)
 	}
 
-	fn new_p<'e>(env: &'e mut Environment, tokens: Vec<Token>) -> Parser<'e> {
+	fn new_p<'e>(env: &'e mut Environment<f64>, tokens: Vec<Token<f64>>) -> Parser<'e, f64> {
 		Parser {
 			tokens: Cursor::new(tokens),
 			env,
+			depth: 0,
+			nesting: 0,
 		}
 	}
 
@@ -512,6 +1516,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// assign
 		assert_eq!(parser.evaluate().unwrap(), 34.5);
@@ -524,6 +1530,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// get
 		assert_eq!(parser.evaluate().unwrap(), 36.5);
@@ -536,6 +1544,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// reassign
 		assert_eq!(parser.evaluate().unwrap(), 5.4);
@@ -548,6 +1558,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// get
 		assert_eq!(parser.evaluate().unwrap(), 7.4);
@@ -580,6 +1592,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		assert_eq!(parser.evaluate().unwrap(), 2.0);
 
@@ -592,6 +1606,8 @@ mod tests {
 				new_t(TokenValue::Eof),
 			]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		assert_eq!(parser.evaluate().unwrap(), 6.0);
 	}
@@ -603,6 +1619,8 @@ mod tests {
 		let mut parser = Parser {
 			tokens: Cursor::new(vec![new_t(TokenValue::LastResult), new_t(TokenValue::Eof)]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 
 		// not assigned yet
@@ -614,6 +1632,8 @@ mod tests {
 		let mut parser = Parser {
 			tokens: Cursor::new(vec![new_t(TokenValue::Number(4.0)), new_t(TokenValue::Eof)]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// assign last result
 		assert_eq!(parser.evaluate().unwrap(), 4.0);
@@ -621,6 +1641,8 @@ mod tests {
 		let mut parser = Parser {
 			tokens: Cursor::new(vec![new_t(TokenValue::LastResult), new_t(TokenValue::Eof)]),
 			env: &mut env,
+			depth: 0,
+			nesting: 0,
 		};
 		// use last result
 		assert_eq!(parser.evaluate().unwrap(), 4.0);
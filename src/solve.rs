@@ -0,0 +1,319 @@
+use crate::{Error, Token};
+
+/// Maximum number of iterations for refining a root.
+const MAX_ITERATIONS: usize = 200;
+/// Maximum number of times the search interval around the initial guess is doubled.
+const MAX_EXPANSIONS: usize = 64;
+/// Number of subintervals which are checked for sign changes when searching all roots in a range.
+const SAMPLES: usize = 200;
+
+/// Equation which is solved by `solve`, a missing right side is `0`.
+pub(crate) enum Equation<N> {
+	/// Equation in a variable, e.g. `solve(x**2 = 4, x)`.
+	Expression {
+		left: Vec<Token<N>>,
+		right: Option<Vec<Token<N>>>,
+		var: String,
+	},
+	/// Function of one argument, e.g. `solve(cos, 1)`.
+	Function(String),
+}
+
+/// Real function whose roots are searched.
+pub(crate) trait RealFunction {
+	fn value(&mut self, x: f64) -> Result<f64, Error>;
+
+	/// Returns the derivative for Newton steps, `None` if it isn't available.
+	fn derivative(&mut self, x: f64) -> Option<f64>;
+
+	/// Returns the magnitude of the terms whose difference is the value, `0` if it isn't known.
+	fn magnitude(&mut self, _x: f64) -> Result<f64, Error> {
+		Ok(0.0)
+	}
+}
+
+/// Checks if the value `0` at `x` is a root and not a difference absorbed by the rounding of large terms
+/// (e.g. of `x = x + 1` for large `x`), `previous` is the value at the last point.
+fn is_root(f: &mut impl RealFunction, x: f64, previous: f64) -> Result<bool, Error> {
+	Ok(f64::EPSILON * f.magnitude(x)? <= previous.abs())
+}
+
+/// Finds a root near the guess.
+///
+/// An interval with a sign change is searched by expanding around the guess, which is then narrowed
+/// by Newton steps falling back to bisection. Roots without a sign change (e.g. of `x**2`) are approximated by Newton's method.
+pub(crate) fn find_root(
+	f: &mut impl RealFunction,
+	guess: f64,
+	tolerance: f64,
+) -> Result<f64, Error> {
+	let y = f.value(guess)?;
+	if y == 0.0 {
+		return Ok(guess);
+	}
+	let mut bounds = [(guess, y), (guess, y)];
+	let mut step = 0.01 * guess.abs().max(1.0);
+	for _ in 0..MAX_EXPANSIONS {
+		for (bound, direction) in bounds.iter_mut().zip([1.0, -1.0]) {
+			let x = guess + direction * step;
+			let y = f.value(x)?;
+			if y == 0.0 {
+				if is_root(f, x, bound.1)? {
+					return Ok(x);
+				}
+				continue;
+			}
+			if !y.is_finite() {
+				continue;
+			}
+			if y.signum() != bound.1.signum() {
+				if let Some(root) = refine(f, *bound, (x, y), tolerance)? {
+					return Ok(root);
+				}
+			}
+			*bound = (x, y);
+		}
+		step *= 2.0;
+	}
+	newton(f, guess, tolerance)
+}
+
+/// Finds all roots in the range, where the sign of the function changes or the function is exactly zero.
+pub(crate) fn find_roots(
+	f: &mut impl RealFunction,
+	a: f64,
+	b: f64,
+	tolerance: f64,
+) -> Result<Vec<f64>, Error> {
+	let (a, b) = (a.min(b), a.max(b));
+	let mut roots = Vec::new();
+	let mut previous = (a, f.value(a)?);
+	if previous.1 == 0.0 {
+		roots.push(a);
+	}
+	for i in 1..=SAMPLES {
+		let x = a + (b - a) * i as f64 / SAMPLES as f64;
+		let y = f.value(x)?;
+		if y == 0.0 {
+			if is_root(f, x, previous.1)? {
+				roots.push(x);
+			}
+		} else if previous.1 != 0.0
+			&& previous.1.is_finite()
+			&& y.is_finite()
+			&& y.signum() != previous.1.signum()
+		{
+			if let Some(root) = refine(f, previous, (x, y), tolerance)? {
+				roots.push(root);
+			}
+		}
+		previous = (x, y);
+	}
+	Ok(roots)
+}
+
+/// Narrows an interval with a sign change to a root, `None` for poles like of `1/x` at `0`.
+///
+/// Newton steps are used as long as they stay in the interval and converge fast enough, otherwise the interval is bisected.
+fn refine(
+	f: &mut impl RealFunction,
+	(mut a, mut fa): (f64, f64),
+	(mut b, fb): (f64, f64),
+	tolerance: f64,
+) -> Result<Option<f64>, Error> {
+	let bound = fa.abs().max(fb.abs());
+	let mut x = (a + b) / 2.0;
+	let mut previous_step = b - a;
+	let mut y = f.value(x)?;
+	let mut iterations = 0;
+	loop {
+		if y == 0.0 {
+			return Ok(Some(x));
+		}
+		if y.signum() == fa.signum() {
+			(a, fa) = (x, y);
+		} else {
+			b = x;
+		}
+		let newton = f.derivative(x).map(|dy| -y / dy);
+		if let Some(step) = newton.filter(|step| step.abs() <= 4.0 * f64::EPSILON * x.abs()) {
+			x += step;
+			y = f.value(x)?;
+			break;
+		}
+		let step = match newton {
+			Some(step)
+				if a.min(b) < x + step
+					&& x + step < a.max(b)
+					&& step.abs() < previous_step.abs() / 2.0 =>
+			{
+				step
+			}
+			_ => (a + b) / 2.0 - x,
+		};
+		previous_step = step;
+		x += step;
+		y = f.value(x)?;
+		iterations += 1;
+
+		if step.abs() <= 4.0 * f64::EPSILON * x.abs() || x == a || x == b {
+			break;
+		}
+		if iterations >= MAX_ITERATIONS {
+			if step.abs() <= tolerance * x.abs().max(1.0) {
+				break;
+			}
+			return Err(Error::NoConvergence {
+				iterations,
+				x: vec![x],
+				residual: y,
+			});
+		}
+	}
+	Ok((y.abs() <= bound).then_some(x))
+}
+
+/// Approximates a root with Newton's method, which converges slowly for multiple roots.
+fn newton(f: &mut impl RealFunction, mut x: f64, tolerance: f64) -> Result<f64, Error> {
+	let mut step = f64::INFINITY;
+	let mut previous = f64::INFINITY;
+	for iterations in 0..MAX_ITERATIONS {
+		let y = f.value(x)?;
+		if y == 0.0 {
+			if is_root(f, x, previous)? {
+				return Ok(x);
+			}
+			return Err(Error::NoConvergence {
+				iterations,
+				x: vec![x],
+				residual: y,
+			});
+		}
+		previous = y;
+		step = match f.derivative(x) {
+			Some(dy) if (y / dy).is_finite() => y / dy,
+			_ => {
+				return Err(Error::NoConvergence {
+					iterations,
+					x: vec![x],
+					residual: y,
+				})
+			}
+		};
+		x -= step;
+		if step.abs() <= 4.0 * f64::EPSILON * x.abs() {
+			return Ok(x);
+		}
+	}
+	let residual = f.value(x)?;
+	if step.abs() <= tolerance * x.abs().max(1.0) && residual.is_finite() {
+		return Ok(x);
+	}
+	Err(Error::NoConvergence {
+		iterations: MAX_ITERATIONS,
+		x: vec![x],
+		residual,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Polynomial(&'static [f64]);
+
+	impl RealFunction for Polynomial {
+		fn value(&mut self, x: f64) -> Result<f64, Error> {
+			Ok(self.0.iter().fold(0.0, |sum, c| sum * x + c))
+		}
+
+		fn derivative(&mut self, x: f64) -> Option<f64> {
+			let n = self.0.len() - 1;
+			let derivative = self.0[..n]
+				.iter()
+				.enumerate()
+				.fold(0.0, |sum, (i, c)| sum * x + c * (n - i) as f64);
+			Some(derivative)
+		}
+	}
+
+	struct Reciprocal;
+
+	impl RealFunction for Reciprocal {
+		fn value(&mut self, x: f64) -> Result<f64, Error> {
+			Ok(1.0 / x)
+		}
+
+		fn derivative(&mut self, _: f64) -> Option<f64> {
+			None
+		}
+	}
+
+	/// Difference of `x` and `x + 1`, which is only zero by rounding.
+	struct Absorbed;
+
+	impl RealFunction for Absorbed {
+		fn value(&mut self, x: f64) -> Result<f64, Error> {
+			Ok(x - (x + 1.0))
+		}
+
+		fn derivative(&mut self, _: f64) -> Option<f64> {
+			Some(0.0)
+		}
+
+		fn magnitude(&mut self, x: f64) -> Result<f64, Error> {
+			Ok(x.abs().max((x + 1.0).abs()))
+		}
+	}
+
+	#[test]
+	fn test_01_find_root() {
+		let mut f = Polynomial(&[1.0, 0.0, -2.0]);
+		let close = |x: f64, expected: f64| (x - expected).abs() < 1e-15;
+		assert!(close(find_root(&mut f, 1.0, 1e-10).unwrap(), 2f64.sqrt()));
+		assert!(close(
+			find_root(&mut f, -5.0, 1e-10).unwrap(),
+			-(2f64.sqrt())
+		));
+		assert!(close(
+			find_root(&mut f, 0.0, 1e-10).unwrap().abs(),
+			2f64.sqrt()
+		));
+
+		// the double root has no sign change
+		let root = find_root(&mut Polynomial(&[1.0, -2.0, 1.0]), 3.0, 1e-10).unwrap();
+		assert!((root - 1.0).abs() < 1e-7);
+
+		assert!(matches!(
+			find_root(&mut Polynomial(&[1.0, 0.0, 1.0]), 0.5, 1e-10),
+			Err(Error::NoConvergence { .. })
+		));
+		assert!(matches!(
+			find_root(&mut Reciprocal, 1.0, 1e-10),
+			Err(Error::NoConvergence { .. })
+		));
+	}
+
+	#[test]
+	fn test_02_find_roots() {
+		let mut f = Polynomial(&[1.0, -6.0, 11.0, -6.0]);
+		assert_eq!(
+			find_roots(&mut f, 0.0, 10.0, 1e-10).unwrap(),
+			vec![1.0, 2.0, 3.0]
+		);
+		assert_eq!(find_roots(&mut f, 1.5, 2.5, 1e-10).unwrap(), vec![2.0]);
+		assert_eq!(
+			find_roots(&mut Reciprocal, -1.0, 1.0, 1e-10).unwrap(),
+			vec![]
+		);
+	}
+
+	#[test]
+	fn test_03_absorbed_difference() {
+		assert!(matches!(
+			find_root(&mut Absorbed, 0.0, 1e-10),
+			Err(Error::NoConvergence { .. })
+		));
+		assert_eq!(find_roots(&mut Absorbed, 0.0, 1e17, 1e-10).unwrap(), vec![]);
+	}
+}
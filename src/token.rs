@@ -1,13 +1,15 @@
+use crate::{Date, DateTime, Error, Time};
+
 #[derive(Debug, PartialEq, Clone)]
-pub struct Token {
-	pub value: TokenValue,
+pub struct Token<N> {
+	pub value: TokenValue<N>,
 	pub src: String,
 	pub start: usize,
 	pub end: usize,
 }
 
-impl Token {
-	pub fn new(value: TokenValue, src: String, start: usize, end: usize) -> Token {
+impl<N> Token<N> {
+	pub fn new(value: TokenValue<N>, src: String, start: usize, end: usize) -> Token<N> {
 		Token {
 			value,
 			src,
@@ -15,18 +17,70 @@ impl Token {
 			end,
 		}
 	}
+
+	/// Converts the number of the token, e.g. for evaluating an expression with dual numbers.
+	pub fn map<M>(self, f: impl FnOnce(N) -> Result<M, Error>) -> Result<Token<M>, Error> {
+		let value = match self.value {
+			TokenValue::Number(number) => TokenValue::Number(f(number)?),
+			TokenValue::Date(value) => TokenValue::Date(value),
+			TokenValue::Time(value) => TokenValue::Time(value),
+			TokenValue::DateTime(value) => TokenValue::DateTime(value),
+			TokenValue::Duration(value) => TokenValue::Duration(value),
+			TokenValue::AddOperator(value) => TokenValue::AddOperator(value),
+			TokenValue::MulOperator(value) => TokenValue::MulOperator(value),
+			TokenValue::ExpOperator(value) => TokenValue::ExpOperator(value),
+			TokenValue::CmpOperator(value) => TokenValue::CmpOperator(value),
+			TokenValue::LogicOperator(value) => TokenValue::LogicOperator(value),
+			TokenValue::BitOperator(value) => TokenValue::BitOperator(value),
+			TokenValue::Not => TokenValue::Not,
+			TokenValue::BitNot => TokenValue::BitNot,
+			TokenValue::Bool(value) => TokenValue::Bool(value),
+			TokenValue::OpenBracket => TokenValue::OpenBracket,
+			TokenValue::CloseBracket => TokenValue::CloseBracket,
+			TokenValue::OpenSquareBracket => TokenValue::OpenSquareBracket,
+			TokenValue::CloseSquareBracket => TokenValue::CloseSquareBracket,
+			TokenValue::Comma => TokenValue::Comma,
+			TokenValue::Identifier(value) => TokenValue::Identifier(value),
+			TokenValue::Let => TokenValue::Let,
+			TokenValue::If => TokenValue::If,
+			TokenValue::Then => TokenValue::Then,
+			TokenValue::Else => TokenValue::Else,
+			TokenValue::Equals => TokenValue::Equals,
+			TokenValue::LastResult => TokenValue::LastResult,
+			TokenValue::Eof => TokenValue::Eof,
+		};
+		Ok(Token::new(value, self.src, self.start, self.end))
+	}
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub enum TokenValue {
-	Number(f32),
+pub enum TokenValue<N> {
+	Number(N),
+	Date(Date),
+	Time(Time),
+	DateTime(DateTime),
+	/// Compound duration like `1h30m` in seconds.
+	Duration(i64),
 	AddOperator(AddOperator),
 	MulOperator(MulOperator),
 	ExpOperator(ExpOperator),
+	CmpOperator(CmpOperator),
+	LogicOperator(LogicOperator),
+	BitOperator(BitOperator),
+	Not,
+	/// Bitwise complement `~`.
+	BitNot,
+	Bool(bool),
 	OpenBracket,
 	CloseBracket,
+	OpenSquareBracket,
+	CloseSquareBracket,
+	Comma,
 	Identifier(String),
 	Let,
+	If,
+	Then,
+	Else,
 	Equals,
 	LastResult,
 	Eof,
@@ -43,6 +97,8 @@ pub enum MulOperator {
 	Mul,
 	Div,
 	Mod,
+	/// Solves linear systems, e.g. `A \ b`.
+	LeftDiv,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -50,3 +106,41 @@ pub enum ExpOperator {
 	Power,
 	Root,
 }
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CmpOperator {
+	Equal,
+	NotEqual,
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LogicOperator {
+	And,
+	Or,
+}
+
+/// Bitwise operators on integers, which are in two's complement for negative numbers.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BitOperator {
+	And,
+	Or,
+	Xor,
+	ShiftLeft,
+	ShiftRight,
+}
+
+impl BitOperator {
+	/// Returns the precedence among the bitwise operators, shifts bind strongest and `|` weakest.
+	pub fn precedence(&self) -> u8 {
+		match self {
+			Self::ShiftLeft | Self::ShiftRight => 3,
+			Self::And => 2,
+			Self::Xor => 1,
+			Self::Or => 0,
+		}
+	}
+}
@@ -0,0 +1,379 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Dimension, Error, Number, Quantity, Unit, UnitDef, Value};
+
+const SECONDS_PER_DAY: i64 = 86400;
+const TIME: Dimension = Dimension([0, 0, 1, 0, 0, 0, 0]);
+
+/// Date of the proleptic Gregorian calendar, e.g. `2026-10-17`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+	/// Days since 1970-01-01.
+	days: i64,
+}
+
+impl Date {
+	/// Creates a date, invalid dates like `2026-02-30` result in `None`.
+	pub fn new(year: i64, month: u32, day: u32) -> Option<Self> {
+		if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+			return None;
+		}
+		// algorithm of http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+		let year = if month <= 2 { year - 1 } else { year };
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let month = month as i64;
+		let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		Some(Self {
+			days: era * 146097 + day_of_era - 719468,
+		})
+	}
+
+	/// Returns the current date in UTC.
+	pub fn today() -> Self {
+		DateTime::now().date()
+	}
+
+	/// Returns the year, month and day of the date.
+	pub fn ymd(&self) -> (i64, u32, u32) {
+		// algorithm of http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+		let days = self.days + 719468;
+		let era = days.div_euclid(146097);
+		let day_of_era = days.rem_euclid(146097);
+		let year_of_era =
+			(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month = (5 * day_of_year + 2) / 153;
+		let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+		let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+		let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+		(year, month, day)
+	}
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+	match month {
+		2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Time of the day, e.g. `14:30`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+	/// Seconds since midnight.
+	seconds: i64,
+}
+
+impl Time {
+	pub fn new(hour: u32, minute: u32, second: u32) -> Option<Self> {
+		if hour > 23 || minute > 59 || second > 59 {
+			return None;
+		}
+		Some(Self {
+			seconds: (hour * 3600 + minute * 60 + second) as i64,
+		})
+	}
+
+	/// Returns the hour, minute and second of the time.
+	pub fn hms(&self) -> (u32, u32, u32) {
+		let seconds = self.seconds as u32;
+		(seconds / 3600, seconds / 60 % 60, seconds % 60)
+	}
+}
+
+/// Point in time in UTC, e.g. `2026-10-17T14:30`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+	/// Seconds since 1970-01-01T00:00 (leap seconds are ignored).
+	seconds: i64,
+}
+
+impl DateTime {
+	pub fn new(date: Date, time: Time) -> Self {
+		Self {
+			seconds: date.days * SECONDS_PER_DAY + time.seconds,
+		}
+	}
+
+	/// Returns the current point in time.
+	pub fn now() -> Self {
+		let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+			Ok(duration) => duration.as_secs() as i64,
+			Err(err) => -(err.duration().as_secs() as i64),
+		};
+		Self { seconds }
+	}
+
+	pub fn date(&self) -> Date {
+		Date {
+			days: self.seconds.div_euclid(SECONDS_PER_DAY),
+		}
+	}
+
+	pub fn time(&self) -> Time {
+		Time {
+			seconds: self.seconds.rem_euclid(SECONDS_PER_DAY),
+		}
+	}
+}
+
+impl fmt::Display for Date {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (year, month, day) = self.ymd();
+		// the sign isn't part of the padded year, e.g. `-0001-12-31`
+		let sign = if year < 0 { "-" } else { "" };
+		write!(
+			f,
+			"{}{:04}-{:02}-{:02}",
+			sign,
+			year.unsigned_abs(),
+			month,
+			day
+		)
+	}
+}
+
+impl fmt::Display for Time {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (hour, minute, second) = self.hms();
+		write!(f, "{:02}:{:02}", hour, minute)?;
+		if second != 0 {
+			write!(f, ":{:02}", second)?;
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Display for DateTime {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}T{}", self.date(), self.time())
+	}
+}
+
+/// Creates a duration in the largest unit representing it exactly, e.g. `90 min` for 5400 seconds.
+pub(crate) fn duration<N: Number>(seconds: i64, ctx: &N::Context) -> Result<Value<N>, Error> {
+	let (symbol, factor) = [("d", SECONDS_PER_DAY), ("h", 3600), ("min", 60), ("s", 1)]
+		.into_iter()
+		.find(|(_, factor)| seconds % factor == 0)
+		.unwrap_or(("s", 1));
+	let unit = Unit::new(
+		symbol,
+		UnitDef {
+			factor: N::parse(&factor.to_string(), ctx)?,
+			dimension: TIME,
+			prefixable: false,
+			offset: None,
+		},
+	);
+	Ok(Value::Quantity(Quantity {
+		value: N::parse(&(seconds / factor).to_string(), ctx)?,
+		unit,
+	}))
+}
+
+/// Converts a duration into whole seconds.
+fn seconds<N: Number>(value: Value<N>) -> Result<i64, Error> {
+	if value.is_date_time() {
+		return Err(Error::Runtime(
+			"Operation is not defined for dates and times!",
+		));
+	}
+	let (seconds, dimension) = value.into_si()?;
+	if dimension != TIME {
+		return Err(Error::Runtime("Expected a duration!"));
+	}
+	let seconds = seconds.to_f64().round();
+	if !seconds.is_finite() || seconds.abs() > 1e15 {
+		return Err(Error::Runtime("Date is out of range!"));
+	}
+	Ok(seconds as i64)
+}
+
+/// Returns the number of calendar months of a duration in months or years, e.g. `24` for `2 years`.
+fn months<N: Number>(value: &Value<N>) -> Result<Option<i64>, Error> {
+	let Value::Quantity(quantity) = value else {
+		return Ok(None);
+	};
+	let factor = match quantity.unit.symbol() {
+		Some("month" | "months") => 1,
+		Some("year" | "years") => 12,
+		_ => return Ok(None),
+	};
+	let months = quantity.value.to_f64();
+	if months.fract() != 0.0 {
+		return Err(Error::Runtime(
+			"Months and years must be whole numbers to move a date!",
+		));
+	}
+	if months.abs() > 1e9 {
+		return Err(Error::Runtime("Date is out of range!"));
+	}
+	Ok(Some(months as i64 * factor))
+}
+
+/// Moves a date by calendar months, the day is limited to the length of the resulting month
+/// (e.g. `2026-01-31 + 1 month` is `2026-02-28`).
+fn shift_months<N>(value: Value<N>, months: i64) -> Result<Value<N>, Error> {
+	let shift_date = |date: Date| {
+		let (year, month, day) = date.ymd();
+		let total = year * 12 + month as i64 - 1 + months;
+		let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+		Date::new(year, month, day.min(days_in_month(year, month)))
+			.ok_or(Error::Runtime("Date is out of range!"))
+	};
+	Ok(match value {
+		Value::Date(date) => Value::Date(shift_date(date)?),
+		Value::DateTime(date_time) => Value::DateTime(DateTime::new(
+			shift_date(date_time.date())?,
+			date_time.time(),
+		)),
+		_ => return Err(Error::Runtime("Expected a date!")),
+	})
+}
+
+/// Moves a date or time by a duration, which is subtracted if `negate` is set.
+fn advance<N: Number>(
+	value: Value<N>,
+	duration: Value<N>,
+	negate: bool,
+) -> Result<Value<N>, Error> {
+	if let Some(months) = months(&duration)? {
+		return shift_months(value, if negate { -months } else { months });
+	}
+	let seconds = seconds(duration)?;
+	shift(value, if negate { -seconds } else { seconds })
+}
+
+/// Moves a date or time by the given number of seconds,
+/// dates are only kept if the shift consists of whole days.
+fn shift<N>(value: Value<N>, seconds: i64) -> Result<Value<N>, Error> {
+	let out_of_range = Error::Runtime("Date is out of range!");
+	Ok(match value {
+		Value::Date(date) if seconds % SECONDS_PER_DAY == 0 => Value::Date(Date {
+			days: date.days + seconds / SECONDS_PER_DAY,
+		}),
+		Value::Date(date) => Value::DateTime(DateTime {
+			seconds: (date.days * SECONDS_PER_DAY)
+				.checked_add(seconds)
+				.ok_or(out_of_range)?,
+		}),
+		Value::DateTime(date_time) => Value::DateTime(DateTime {
+			seconds: date_time.seconds.checked_add(seconds).ok_or(out_of_range)?,
+		}),
+		Value::Time(time) => Value::Time(Time {
+			seconds: (time.seconds + seconds % SECONDS_PER_DAY).rem_euclid(SECONDS_PER_DAY),
+		}),
+		_ => return Err(Error::Runtime("Expected a date!")),
+	})
+}
+
+/// Returns the seconds since 1970-01-01 for dates, which start at midnight.
+fn timestamp<N>(value: &Value<N>) -> Option<i64> {
+	match value {
+		Value::Date(date) => Some(date.days * SECONDS_PER_DAY),
+		Value::DateTime(date_time) => Some(date_time.seconds),
+		_ => None,
+	}
+}
+
+pub(crate) fn add<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	match (left, right) {
+		(Value::Date(date), Value::Time(time)) | (Value::Time(time), Value::Date(date)) => {
+			Ok(Value::DateTime(DateTime::new(date, time)))
+		}
+		(left, right) if left.is_date_time() => advance(left, right, false),
+		(left, right) => advance(right, left, false),
+	}
+}
+
+pub(crate) fn sub<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let ctx = N::Context::default();
+	if let (Some(left), Some(right)) = (timestamp(&left), timestamp(&right)) {
+		return duration(left - right, &ctx);
+	}
+	match (left, right) {
+		(Value::Time(left), Value::Time(right)) => duration(left.seconds - right.seconds, &ctx),
+		(left, right) if left.is_date_time() => advance(left, right, true),
+		_ => Err(Error::Runtime(
+			"Operation is not defined for dates and times!",
+		)),
+	}
+}
+
+pub(crate) fn compare<N>(left: &Value<N>, right: &Value<N>) -> Result<Ordering, Error> {
+	match (left, right) {
+		(Value::Time(left), Value::Time(right)) => Ok(left.cmp(right)),
+		_ => match (timestamp(left), timestamp(right)) {
+			(Some(left), Some(right)) => Ok(left.cmp(&right)),
+			_ => Err(Error::Runtime(
+				"Operation is not defined for dates and times!",
+			)),
+		},
+	}
+}
+
+pub(crate) fn today<N>() -> Value<N> {
+	Value::Date(Date::today())
+}
+
+pub(crate) fn now<N>() -> Value<N> {
+	Value::DateTime(DateTime::now())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_01_civil_date() {
+		let date = Date::new(2026, 10, 17).unwrap();
+		assert_eq!(date.ymd(), (2026, 10, 17));
+		assert_eq!(date.to_string(), "2026-10-17");
+		assert_eq!(Date::new(1970, 1, 1).unwrap().days, 0);
+		assert_eq!(Date::new(2000, 3, 1).unwrap().days, 11017);
+		assert_eq!(Date::new(1969, 12, 31).unwrap().days, -1);
+
+		assert!(Date::new(2024, 2, 29).is_some());
+		assert!(Date::new(2026, 2, 29).is_none());
+		assert!(Date::new(1900, 2, 29).is_none());
+		assert!(Date::new(2026, 13, 1).is_none());
+
+		for days in -800_000..800_000 {
+			let date = Date { days };
+			let (year, month, day) = date.ymd();
+			assert_eq!(Date::new(year, month, day), Some(date));
+		}
+	}
+
+	#[test]
+	fn test_02_time() {
+		let time = Time::new(14, 30, 0).unwrap();
+		assert_eq!(time.to_string(), "14:30");
+		assert_eq!(Time::new(8, 5, 9).unwrap().to_string(), "08:05:09");
+		assert!(Time::new(24, 0, 0).is_none());
+
+		let date_time = DateTime::new(Date::new(2026, 10, 17).unwrap(), time);
+		assert_eq!(date_time.to_string(), "2026-10-17T14:30");
+		assert_eq!(date_time.time(), time);
+	}
+
+	#[test]
+	fn test_03_negative_years() {
+		let date = Date::new(0, 1, 1).unwrap();
+		assert_eq!(date.to_string(), "0000-01-01");
+		assert_eq!(
+			Date {
+				days: date.days - 1
+			}
+			.to_string(),
+			"-0001-12-31"
+		);
+		assert_eq!(Date::new(-12345, 6, 7).unwrap().to_string(), "-12345-06-07");
+	}
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::{Error, Token};
+
+/// Function defined by an expression, e.g. `f(x) = x**2 + 1`.
+///
+/// The body is evaluated on every call with the parameters bound to the arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction<N> {
+	name: String,
+	params: Vec<String>,
+	body: Vec<Token<N>>,
+}
+
+impl<N> UserFunction<N> {
+	pub(crate) fn new(name: String, params: Vec<String>, body: Vec<Token<N>>) -> Self {
+		Self { name, params, body }
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn params(&self) -> &[String] {
+		&self.params
+	}
+
+	pub(crate) fn body(&self) -> &[Token<N>] {
+		&self.body
+	}
+
+	/// Converts the numbers of the body into another number type.
+	pub(crate) fn map_numbers<M>(
+		self,
+		f: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<UserFunction<M>, Error> {
+		let body = self
+			.body
+			.into_iter()
+			.map(|token| token.map(f))
+			.collect::<Result<_, _>>()?;
+		Ok(UserFunction::new(self.name, self.params, body))
+	}
+}
+
+impl<N> fmt::Display for UserFunction<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}({}) =", self.name, self.params.join(", "))?;
+		let mut previous: Option<&Token<N>> = None;
+		for token in &self.body {
+			// the original spacing is kept, so `v[0]` isn't changed into `v [0]`
+			match previous {
+				Some(previous) if token.start == previous.end + 1 => (),
+				_ => write!(f, " ")?,
+			}
+			write!(f, "{}", token.src)?;
+			previous = Some(token);
+		}
+		Ok(())
+	}
+}
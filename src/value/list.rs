@@ -0,0 +1,49 @@
+use crate::{Error, Number, Value};
+
+use super::sum;
+
+/// Returns the element at the given index, the first element has the index `0`.
+pub(crate) fn index<N: Number>(list: Value<N>, index: Value<N>) -> Result<Value<N>, Error> {
+	let mut elements = list.into_list()?;
+	let index = index.into_number()?.to_f64();
+	if index < 0.0 || index.fract() != 0.0 {
+		return Err(Error::Runtime("Index must be a non-negative integer!"));
+	}
+	if index >= elements.len() as f64 {
+		return Err(Error::Runtime("Index out of bounds!"));
+	}
+	Ok(elements.swap_remove(index as usize))
+}
+
+/// Creates a number of the list's number type from a count.
+pub(super) fn count<N: Number>(n: usize, list: &[Value<N>]) -> Result<N, Error> {
+	let ctx = match list.first() {
+		Some(Value::Number(number)) => number.context(),
+		Some(Value::Quantity(quantity)) => quantity.value.context(),
+		_ => N::Context::default(),
+	};
+	N::parse(&n.to_string(), &ctx)
+}
+
+pub(crate) fn len<N: Number>(list: Value<N>) -> Result<N, Error> {
+	let elements = list.into_list()?;
+	count(elements.len(), &elements)
+}
+
+/// Calculates the scalar product of two lists of the same length.
+pub(crate) fn dot<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	// lists of different lengths can't be multiplied, the caller sets the position of the error
+	let (left, right) = (left.into_list()?, right.into_list()?);
+	sum(Value::List(left).mul(Value::List(right))?.into_list()?)
+}
+
+/// Calculates the euclidean norm, complex elements contribute their magnitude.
+pub(crate) fn norm<N: Number>(list: Value<N>) -> Result<Value<N>, Error> {
+	let elements = list.into_list()?;
+	let two = count(2, &elements)?;
+	let magnitudes = Value::List(elements).map(|element| {
+		let (value, unit) = element.into_parts()?;
+		Ok(Value::from_parts(value.abs()?, unit))
+	})?;
+	dot(magnitudes.clone(), magnitudes)?.root(Value::Number(two))
+}
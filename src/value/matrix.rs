@@ -0,0 +1,440 @@
+use std::fmt;
+
+use crate::{Error, Number, NumberFormat, Value};
+
+/// Matrix of numbers stored in row-major order, e.g. `[[1, 2], [3, 4]]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<N> {
+	rows: usize,
+	cols: usize,
+	elements: Vec<N>,
+}
+
+impl<N: Number> Matrix<N> {
+	/// Creates a matrix from its elements in row-major order.
+	pub fn new(rows: usize, cols: usize, elements: Vec<N>) -> Result<Self, Error> {
+		if rows == 0 || cols == 0 || elements.len() != rows * cols {
+			return Err(Error::Runtime("Invalid matrix shape!"));
+		}
+		Ok(Self {
+			rows,
+			cols,
+			elements,
+		})
+	}
+
+	/// Creates the `n`x`n` identity matrix.
+	pub fn identity(n: usize, ctx: &N::Context) -> Result<Self, Error> {
+		let zero = N::parse("0", ctx)?;
+		let one = N::parse("1", ctx)?;
+		let elements = (0..n * n)
+			.map(|i| {
+				if i / n == i % n {
+					one.clone()
+				} else {
+					zero.clone()
+				}
+			})
+			.collect();
+		Self::new(n, n, elements)
+	}
+
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	pub fn cols(&self) -> usize {
+		self.cols
+	}
+
+	pub fn get(&self, row: usize, col: usize) -> Option<&N> {
+		if row < self.rows && col < self.cols {
+			self.elements.get(row * self.cols + col)
+		} else {
+			None
+		}
+	}
+
+	pub fn shape(&self) -> Vec<usize> {
+		vec![self.rows, self.cols]
+	}
+
+	pub fn transpose(&self) -> Self {
+		let elements = (0..self.rows * self.cols)
+			.map(|i| self.elements[(i % self.rows) * self.cols + i / self.rows].clone())
+			.collect();
+		Self {
+			rows: self.cols,
+			cols: self.rows,
+			elements,
+		}
+	}
+
+	/// Formats the elements like [`Display`](fmt::Display) does.
+	pub(crate) fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		let rows = self
+			.elements
+			.chunks(self.cols.max(1))
+			.map(|row| {
+				let row = row
+					.iter()
+					.map(|element| element.format(format))
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(format!("[{}]", row.join(", ")))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		Ok(format!("[{}]", rows.join(", ")))
+	}
+
+	/// Returns the rows of the matrix.
+	pub(crate) fn into_rows(self) -> Vec<Vec<N>> {
+		let cols = self.cols;
+		let mut elements = self.elements.into_iter();
+		(0..self.rows)
+			.map(|_| elements.by_ref().take(cols).collect())
+			.collect()
+	}
+
+	/// Applies the operation on every element.
+	pub(crate) fn map(self, op: impl Fn(N) -> Result<N, Error>) -> Result<Self, Error> {
+		Ok(Self {
+			elements: self
+				.elements
+				.into_iter()
+				.map(op)
+				.collect::<Result<_, _>>()?,
+			..self
+		})
+	}
+
+	/// Converts the elements into another number type.
+	pub(crate) fn map_numbers<M>(
+		self,
+		f: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<Matrix<M>, Error> {
+		Ok(Matrix {
+			rows: self.rows,
+			cols: self.cols,
+			elements: self.elements.into_iter().map(f).collect::<Result<_, _>>()?,
+		})
+	}
+
+	/// Applies the operation on the elements of two matrices of the same shape.
+	pub(crate) fn zip(self, rhs: Self, op: fn(N, N) -> Result<N, Error>) -> Result<Self, Error> {
+		if self.shape() != rhs.shape() {
+			return Err(self.mismatch(&rhs));
+		}
+		Ok(Self {
+			elements: self
+				.elements
+				.into_iter()
+				.zip(rhs.elements)
+				.map(|(left, right)| op(left, right))
+				.collect::<Result<_, _>>()?,
+			..self
+		})
+	}
+
+	fn mismatch(&self, rhs: &Self) -> Error {
+		Error::ShapeMismatch {
+			left: self.shape(),
+			right: rhs.shape(),
+			start: 0,
+			end: 0,
+		}
+	}
+
+	/// Calculates the matrix product, the columns of the left matrix have to match the rows of the right one.
+	pub fn matmul(&self, rhs: &Self) -> Result<Self, Error> {
+		if self.cols != rhs.rows {
+			return Err(self.mismatch(rhs));
+		}
+		let mut elements = Vec::with_capacity(self.rows * rhs.cols);
+		for row in 0..self.rows {
+			for col in 0..rhs.cols {
+				let mut sum = self.elements[row * self.cols]
+					.clone()
+					.mul(rhs.elements[col].clone())?;
+				for k in 1..self.cols {
+					let product = self.elements[row * self.cols + k]
+						.clone()
+						.mul(rhs.elements[k * rhs.cols + col].clone())?;
+					sum = sum.add(product)?;
+				}
+				elements.push(sum);
+			}
+		}
+		Self::new(self.rows, rhs.cols, elements)
+	}
+
+	/// Raises a square matrix to an integer power, negative powers use the inverse.
+	pub fn powi(&self, exponent: i32) -> Result<Self, Error> {
+		if self.rows != self.cols {
+			return Err(Error::Runtime("Matrix is not square!"));
+		}
+		let mut base = if exponent < 0 {
+			self.inverse()?
+		} else {
+			self.clone()
+		};
+		let ctx = self.elements[0].context();
+		let mut result = Self::identity(self.rows, &ctx)?;
+		let mut exponent = exponent.unsigned_abs();
+		while exponent > 0 {
+			if exponent % 2 == 1 {
+				result = result.matmul(&base)?;
+			}
+			base = base.matmul(&base)?;
+			exponent /= 2;
+		}
+		Ok(result)
+	}
+
+	/// Calculates the determinant with the fraction-free Bareiss algorithm,
+	/// so the divisions of integer matrices are exact, e.g. for the `Integer` type.
+	pub fn det(&self) -> Result<N, Error> {
+		if self.rows != self.cols {
+			return Err(Error::Runtime("Matrix is not square!"));
+		}
+		let n = self.rows;
+		let ctx = self.elements[0].context();
+		let mut rows = self.clone().into_rows();
+		let mut negative = false;
+		let mut previous = N::parse("1", &ctx)?;
+		for col in 0..n {
+			let Some(pivot) = pivot(&rows, col)? else {
+				return N::parse("0", &ctx);
+			};
+			if pivot != col {
+				rows.swap(pivot, col);
+				negative = !negative;
+			}
+
+			// a[i][j] = (a[i][j] * a[col][col] - a[i][col] * a[col][j]) / previous pivot
+			let pivot_row = rows[col].clone();
+			for elements in rows.iter_mut().skip(col + 1) {
+				let factor = elements[col].clone();
+				for (element, pivot) in elements.iter_mut().zip(&pivot_row).skip(col + 1) {
+					let product = element.clone().mul(pivot_row[col].clone())?;
+					*element = product
+						.sub(factor.clone().mul(pivot.clone())?)?
+						.div(previous.clone())?;
+				}
+			}
+			previous = pivot_row[col].clone();
+		}
+		if negative {
+			previous.neg()
+		} else {
+			Ok(previous)
+		}
+	}
+
+	pub fn inverse(&self) -> Result<Self, Error> {
+		let ctx = self.elements[0].context();
+		self.solve(&Self::identity(self.rows, &ctx)?)
+	}
+
+	/// Solves the linear system `self * x = rhs` for `x`.
+	pub fn solve(&self, rhs: &Self) -> Result<Self, Error> {
+		if self.rows != rhs.rows {
+			return Err(self.mismatch(rhs));
+		}
+		match self.reduce(rhs.clone())? {
+			Some(solution) => Ok(solution),
+			None => Err(Error::Runtime("Matrix is singular!")),
+		}
+	}
+
+	/// Applies the Gauss-Jordan elimination with partial pivoting on a square matrix,
+	/// returns the reduced right side or `None` for singular matrices.
+	fn reduce(&self, rhs: Self) -> Result<Option<Self>, Error> {
+		if self.rows != self.cols {
+			return Err(Error::Runtime("Matrix is not square!"));
+		}
+		let n = self.rows;
+		let k = rhs.cols;
+		let mut rows: Vec<Vec<N>> = self
+			.clone()
+			.into_rows()
+			.into_iter()
+			.zip(rhs.into_rows())
+			.map(|(mut left, right)| {
+				left.extend(right);
+				left
+			})
+			.collect();
+
+		for col in 0..n {
+			let Some(pivot) = pivot(&rows, col)? else {
+				return Ok(None);
+			};
+			rows.swap(pivot, col);
+
+			let value = rows[col][col].clone();
+			for element in rows[col].iter_mut() {
+				*element = element.clone().div(value.clone())?;
+			}
+			let pivot_row = rows[col].clone();
+			for (row, elements) in rows.iter_mut().enumerate() {
+				if row == col {
+					continue;
+				}
+				let factor = elements[col].clone();
+				for (element, pivot) in elements.iter_mut().zip(&pivot_row) {
+					let product = factor.clone().mul(pivot.clone())?;
+					*element = element.clone().sub(product)?;
+				}
+			}
+		}
+
+		let elements = rows
+			.into_iter()
+			.flat_map(|row| row.into_iter().skip(n))
+			.collect();
+		Ok(Some(Self::new(n, k, elements)?))
+	}
+}
+
+/// Returns the row with the largest magnitude in the column from the diagonal downwards
+/// for partial pivoting, `None` if all of them are zero.
+fn pivot<N: Number>(rows: &[Vec<N>], col: usize) -> Result<Option<usize>, Error> {
+	let mut pivot = None;
+	let mut max = 0.0;
+	for (row, elements) in rows.iter().enumerate().skip(col) {
+		let magnitude = elements[col].clone().abs()?.to_f64();
+		if magnitude > max {
+			pivot = Some(row);
+			max = magnitude;
+		}
+	}
+	Ok(pivot)
+}
+
+impl<N: Number> fmt::Display for Matrix<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "[")?;
+		for row in 0..self.rows {
+			if row > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "[")?;
+			for col in 0..self.cols {
+				if col > 0 {
+					write!(f, ", ")?;
+				}
+				write!(f, "{}", self.elements[row * self.cols + col])?;
+			}
+			write!(f, "]")?;
+		}
+		write!(f, "]")
+	}
+}
+
+/// Creates a matrix if all elements are lists of numbers of the same length, otherwise a list.
+pub(crate) fn list_or_matrix<N: Number>(elements: Vec<Value<N>>) -> Value<N> {
+	let cols = match elements.first() {
+		Some(Value::List(row)) => row.len(),
+		_ => return Value::List(elements),
+	};
+	let rows = elements
+		.iter()
+		.map(|row| match row {
+			Value::List(row) if row.len() == cols => row
+				.iter()
+				.map(|element| element.as_number().cloned())
+				.collect::<Option<Vec<_>>>(),
+			_ => None,
+		})
+		.collect::<Option<Vec<_>>>();
+	match rows.map(|rows| Matrix::new(elements.len(), cols, rows.concat())) {
+		Some(Ok(matrix)) => Value::Matrix(matrix),
+		_ => Value::List(elements),
+	}
+}
+
+/// Converts a list of numbers into a column vector.
+pub(crate) fn column<N: Number>(list: Vec<Value<N>>) -> Result<Matrix<N>, Error> {
+	let rows = list.len();
+	let elements = list
+		.into_iter()
+		.map(Value::into_number)
+		.collect::<Result<_, _>>()?;
+	Matrix::new(rows, 1, elements)
+}
+
+pub(crate) fn transpose<N: Number>(value: Value<N>) -> Result<Value<N>, Error> {
+	match value {
+		Value::Matrix(matrix) => Ok(Value::Matrix(matrix.transpose())),
+		Value::List(list) => Ok(Value::Matrix(column(list)?.transpose())),
+		_ => Err(Error::Runtime("Expected a matrix!")),
+	}
+}
+
+pub(crate) fn det<N: Number>(value: Value<N>) -> Result<N, Error> {
+	value.into_matrix()?.det()
+}
+
+pub(crate) fn inverse<N: Number>(value: Value<N>) -> Result<Value<N>, Error> {
+	Ok(Value::Matrix(value.into_matrix()?.inverse()?))
+}
+
+pub(crate) fn identity<N: Number>(n: N) -> Result<Value<N>, Error> {
+	let size = n.to_f64();
+	if size < 1.0 || size.fract() != 0.0 || size > u16::MAX as f64 {
+		return Err(Error::Runtime("Matrix size must be a positive integer!"));
+	}
+	Ok(Value::Matrix(Matrix::identity(
+		size as usize,
+		&n.context(),
+	)?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn matrix(rows: usize, cols: usize, elements: &[f64]) -> Matrix<f64> {
+		Matrix::new(rows, cols, elements.to_vec()).unwrap()
+	}
+
+	#[test]
+	fn test_01_matmul() {
+		let a = matrix(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+		let b = matrix(3, 1, &[1.0, 0.0, -1.0]);
+		assert_eq!(a.matmul(&b).unwrap(), matrix(2, 1, &[-2.0, -2.0]));
+		assert_eq!(
+			a.matmul(&a),
+			Err(Error::ShapeMismatch {
+				left: vec![2, 3],
+				right: vec![2, 3],
+				start: 0,
+				end: 0
+			})
+		);
+		assert_eq!(a.transpose(), matrix(3, 2, &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]));
+	}
+
+	#[test]
+	fn test_02_elimination() {
+		let a = matrix(2, 2, &[2.0, 1.0, 1.0, 1.0]);
+		assert_eq!(a.det().unwrap(), 1.0);
+		assert_eq!(a.inverse().unwrap(), matrix(2, 2, &[1.0, -1.0, -1.0, 2.0]));
+		assert_eq!(a.powi(2).unwrap(), matrix(2, 2, &[5.0, 3.0, 3.0, 2.0]));
+		assert_eq!(a.powi(-1).unwrap(), a.inverse().unwrap());
+
+		let a = matrix(3, 3, &[2.0, 0.0, 1.0, 1.0, 3.0, 2.0, 1.0, 1.0, 2.0]);
+		assert_eq!(a.det().unwrap(), 6.0);
+
+		let singular = matrix(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+		assert_eq!(singular.det().unwrap(), 0.0);
+		assert_eq!(
+			singular.inverse(),
+			Err(Error::Runtime("Matrix is singular!"))
+		);
+		assert_eq!(
+			matrix(1, 2, &[1.0, 2.0]).det(),
+			Err(Error::Runtime("Matrix is not square!"))
+		);
+	}
+}
@@ -0,0 +1,654 @@
+use std::{cmp::Ordering, fmt};
+
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive};
+
+use crate::{Error, Number, NumberFormat};
+
+mod datetime;
+pub(crate) use datetime::{duration, now, today};
+pub use datetime::{Date, DateTime, Time};
+mod function;
+pub use function::*;
+mod list;
+pub(crate) use list::*;
+mod statistics;
+pub(crate) use statistics::*;
+mod matrix;
+pub use matrix::*;
+mod polynomial;
+pub(crate) use polynomial::evaluate;
+pub use polynomial::Polynomial;
+mod quantity;
+pub use quantity::*;
+mod unit;
+pub use unit::*;
+
+/// Result of an evaluated expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<N> {
+	Number(N),
+	Quantity(Quantity<N>),
+	Bool(bool),
+	List(Vec<Value<N>>),
+	Matrix(Matrix<N>),
+	Date(Date),
+	Time(Time),
+	DateTime(DateTime),
+	Function(UserFunction<N>),
+	Polynomial(Polynomial<N>),
+}
+
+impl<N: Number> Value<N> {
+	/// Returns the number if the value is a plain number.
+	pub fn as_number(&self) -> Option<&N> {
+		match self {
+			Self::Number(number) => Some(number),
+			_ => None,
+		}
+	}
+
+	/// Converts the value into a plain number,
+	/// dimensionless quantities like `90 deg` are converted into their coherent SI value.
+	pub fn into_number(self) -> Result<N, Error> {
+		match self {
+			Self::Number(number) => Ok(number),
+			Self::Quantity(quantity) if quantity.unit.dimension().is_dimensionless() => {
+				let factor = quantity.unit.factor(&quantity.value.context())?;
+				quantity.value.mul(factor)
+			}
+			Self::Quantity(_) => Err(Error::Runtime("Expected a dimensionless number!")),
+			_ => Err(Error::Runtime("Expected a number!")),
+		}
+	}
+
+	/// Converts the numbers of the value into another number type,
+	/// `number` is applied on plain numbers and quantity values and `factor` on the unit factors.
+	pub(crate) fn map_numbers<M>(
+		self,
+		number: &impl Fn(N) -> Result<M, Error>,
+		factor: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<Value<M>, Error> {
+		Ok(match self {
+			Self::Number(value) => Value::Number(number(value)?),
+			Self::Quantity(quantity) => Value::Quantity(Quantity {
+				value: number(quantity.value)?,
+				unit: quantity.unit.map_numbers(factor)?,
+			}),
+			Self::Bool(value) => Value::Bool(value),
+			Self::List(elements) => Value::List(
+				elements
+					.into_iter()
+					.map(|element| element.map_numbers(number, factor))
+					.collect::<Result<_, _>>()?,
+			),
+			Self::Matrix(matrix) => Value::Matrix(matrix.map_numbers(number)?),
+			Self::Date(date) => Value::Date(date),
+			Self::Time(time) => Value::Time(time),
+			Self::DateTime(date_time) => Value::DateTime(date_time),
+			Self::Function(function) => Value::Function(function.map_numbers(number)?),
+			Self::Polynomial(polynomial) => Value::Polynomial(polynomial.map_numbers(number)?),
+		})
+	}
+
+	/// Converts the value into a boolean, numbers are not implicitly converted.
+	pub fn into_bool(self) -> Result<bool, Error> {
+		match self {
+			Self::Bool(value) => Ok(value),
+			_ => Err(Error::Runtime("Expected a boolean value!")),
+		}
+	}
+
+	fn from_parts(value: N, unit: Unit<N>) -> Self {
+		if unit.is_empty() {
+			Self::Number(value)
+		} else {
+			Self::Quantity(Quantity { value, unit })
+		}
+	}
+
+	fn into_parts(self) -> Result<(N, Unit<N>), Error> {
+		match self {
+			Self::Number(number) => Ok((number, Unit::dimensionless())),
+			Self::Quantity(quantity) => Ok((quantity.value, quantity.unit)),
+			_ => Err(Error::Runtime("Expected a number!")),
+		}
+	}
+
+	/// Converts the value into its elements, the elements of a matrix are its rows.
+	pub fn into_list(self) -> Result<Vec<Self>, Error> {
+		match self {
+			Self::List(elements) => Ok(elements),
+			Self::Matrix(matrix) => Ok(matrix
+				.into_rows()
+				.into_iter()
+				.map(|row| Self::List(row.into_iter().map(Self::Number).collect()))
+				.collect()),
+			_ => Err(Error::Runtime("Expected a list!")),
+		}
+	}
+
+	/// Converts the value into a matrix, a list of numbers is used as column vector.
+	pub fn into_matrix(self) -> Result<Matrix<N>, Error> {
+		match self {
+			Self::Matrix(matrix) => Ok(matrix),
+			Self::List(list) => column(list),
+			_ => Err(Error::Runtime("Expected a matrix!")),
+		}
+	}
+
+	/// Formats the numbers of the value as configured, other values are written as they are displayed.
+	pub fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		match self {
+			Self::Number(number) => number.format(format),
+			Self::Quantity(quantity) => quantity.format(format),
+			Self::Matrix(matrix) => matrix.format(format),
+			Self::List(elements) => {
+				let elements = elements
+					.iter()
+					.map(|element| element.format(format))
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(format!("[{}]", elements.join(", ")))
+			}
+			_ => Ok(self.to_string()),
+		}
+	}
+
+	fn is_list(&self) -> bool {
+		matches!(self, Self::List(_))
+	}
+
+	fn is_matrix(&self) -> bool {
+		matches!(self, Self::Matrix(_))
+	}
+
+	fn is_date_time(&self) -> bool {
+		matches!(self, Self::Date(_) | Self::Time(_) | Self::DateTime(_))
+	}
+
+	fn is_polynomial(&self) -> bool {
+		matches!(self, Self::Polynomial(_))
+	}
+
+	fn shape(&self) -> Vec<usize> {
+		match self {
+			Self::List(list) => vec![list.len()],
+			Self::Matrix(matrix) => matrix.shape(),
+			_ => Vec::new(),
+		}
+	}
+
+	/// Applies an element-wise operation on matrices, numbers are combined with every element.
+	fn matrix_elementwise(
+		self,
+		rhs: Self,
+		op: fn(N, N) -> Result<N, Error>,
+	) -> Result<Self, Error> {
+		let matrix = match (self, rhs) {
+			(Self::Matrix(left), Self::Matrix(right)) => left.zip(right, op)?,
+			(Self::Matrix(left), Self::Number(right)) => {
+				left.map(|left| op(left, right.clone()))?
+			}
+			(Self::Number(left), Self::Matrix(right)) => {
+				right.map(|right| op(left.clone(), right))?
+			}
+			(left, right)
+				if left.is_matrix() && right.is_list() || left.is_list() && right.is_matrix() =>
+			{
+				return Err(Error::ShapeMismatch {
+					left: left.shape(),
+					right: right.shape(),
+					start: 0,
+					end: 0,
+				})
+			}
+			_ => return Err(Error::Runtime("Expected a number!")),
+		};
+		Ok(Self::Matrix(matrix))
+	}
+
+	/// Multiplies matrices, lists are used as column vectors on the right and as row vectors on the left.
+	fn matrix_mul(self, rhs: Self) -> Result<Self, Error> {
+		match (self, rhs) {
+			(Self::Matrix(left), Self::Matrix(right)) => Ok(Self::Matrix(left.matmul(&right)?)),
+			(Self::Matrix(left), Self::List(right)) => {
+				Ok(Self::from_column(left.matmul(&column(right)?)?))
+			}
+			(Self::List(left), Self::Matrix(right)) => {
+				let row = column(left)?.transpose();
+				Ok(Self::from_column(row.matmul(&right)?.transpose()))
+			}
+			(left, right) => left.matrix_elementwise(right, N::mul),
+		}
+	}
+
+	fn from_column(matrix: Matrix<N>) -> Self {
+		Self::List(
+			matrix
+				.into_rows()
+				.into_iter()
+				.flatten()
+				.map(Self::Number)
+				.collect(),
+		)
+	}
+
+	/// Solves the linear system `self * x = rhs`, e.g. `A \ b`. For numbers this is `rhs / self`.
+	pub(crate) fn left_div(self, rhs: Self) -> Result<Self, Error> {
+		match (self, rhs) {
+			(Self::Matrix(left), Self::Matrix(right)) => Ok(Self::Matrix(left.solve(&right)?)),
+			(Self::Matrix(left), Self::List(right)) => {
+				Ok(Self::from_column(left.solve(&column(right)?)?))
+			}
+			(Self::Matrix(_), _) => Err(Error::Runtime("Expected a matrix!")),
+			(left, right) => right.div(left),
+		}
+	}
+
+	/// Applies the operation element-wise if one of the values is a list,
+	/// a scalar is combined with every element of the list.
+	fn broadcast(
+		self,
+		rhs: Self,
+		op: fn(Self, Self) -> Result<Self, Error>,
+	) -> Result<Self, Error> {
+		let elements = match (self, rhs) {
+			(Self::List(left), Self::List(right)) => {
+				if left.len() != right.len() {
+					return Err(Error::ShapeMismatch {
+						left: vec![left.len()],
+						right: vec![right.len()],
+						start: 0,
+						end: 0,
+					});
+				}
+				left.into_iter()
+					.zip(right)
+					.map(|(left, right)| op(left, right))
+					.collect::<Result<_, _>>()?
+			}
+			(Self::List(left), right) => left
+				.into_iter()
+				.map(|left| op(left, right.clone()))
+				.collect::<Result<_, _>>()?,
+			(left, Self::List(right)) => right
+				.into_iter()
+				.map(|right| op(left.clone(), right))
+				.collect::<Result<_, _>>()?,
+			(left, right) => return op(left, right),
+		};
+		Ok(Self::List(elements))
+	}
+
+	/// Applies the operation on every element of a list.
+	fn map(self, op: impl Fn(Self) -> Result<Self, Error>) -> Result<Self, Error> {
+		let elements = self.into_list()?;
+		Ok(Self::List(
+			elements.into_iter().map(op).collect::<Result<_, _>>()?,
+		))
+	}
+
+	/// Converts both values into the unit of the left one,
+	/// so they can be combined by operations requiring the same dimension.
+	fn unify(self, rhs: Self) -> Result<(N, N, Unit<N>), Error> {
+		let (left, left_unit) = self.into_parts()?;
+		let (right, right_unit) = rhs.into_parts()?;
+		if left_unit.dimension() != right_unit.dimension() {
+			return Err(Error::DimensionMismatch {
+				left: left_unit.to_string(),
+				right: right_unit.to_string(),
+				start: 0,
+				end: 0,
+			});
+		}
+
+		let right = if left_unit == right_unit {
+			right
+		} else {
+			let ctx = left.context();
+			right
+				.mul(right_unit.factor(&ctx)?)?
+				.div(left_unit.factor(&ctx)?)?
+		};
+		Ok((left, right, left_unit))
+	}
+
+	/// Applies an operation on values of the same dimension, the right value is converted into the left unit.
+	fn additive(self, rhs: Self, op: fn(N, N) -> Result<N, Error>) -> Result<Self, Error> {
+		let (left, right, unit) = self.unify(rhs)?;
+		Ok(Self::from_parts(op(left, right)?, unit))
+	}
+
+	/// Checks the values for equality, quantities are compared in the same unit.
+	pub(crate) fn equals(self, rhs: Self) -> Result<bool, Error> {
+		match (self, rhs) {
+			(Self::Bool(left), Self::Bool(right)) => Ok(left == right),
+			(Self::List(left), Self::List(right)) => {
+				if left.len() != right.len() {
+					return Ok(false);
+				}
+				for (left, right) in left.into_iter().zip(right) {
+					if !left.equals(right)? {
+						return Ok(false);
+					}
+				}
+				Ok(true)
+			}
+			(Self::Matrix(left), Self::Matrix(right)) => Ok(left == right),
+			(Self::Polynomial(left), Self::Polynomial(right)) => Ok(left == right),
+			(Self::List(_) | Self::Matrix(_), _) | (_, Self::List(_) | Self::Matrix(_)) => {
+				Err(Error::Runtime("Lists can only be compared with lists!"))
+			}
+			(Self::Bool(_), _) | (_, Self::Bool(_)) => Err(Error::Runtime(
+				"Booleans can only be compared with booleans!",
+			)),
+			(left, right) if left.is_date_time() || right.is_date_time() => {
+				Ok(datetime::compare(&left, &right)? == Ordering::Equal)
+			}
+			(left, right) => {
+				let (left, right, _) = left.unify(right)?;
+				Ok(left == right)
+			}
+		}
+	}
+
+	/// Orders the values, only numbers and quantities of the same dimension as well as dates can be ordered.
+	pub(crate) fn compare(self, rhs: Self) -> Result<Ordering, Error> {
+		if self.is_date_time() || rhs.is_date_time() {
+			return datetime::compare(&self, &rhs);
+		}
+		let (left, right, _) = self.unify(rhs)?;
+		left.compare(&right)
+	}
+
+	/// Multiplies the value with the given unit.
+	pub(crate) fn mul_unit(self, unit: &Unit<N>) -> Result<Self, Error> {
+		if self.is_list() {
+			return self.map(|element| element.mul_unit(unit));
+		}
+		let (value, left_unit) = self.into_parts()?;
+		let (unit, conversion) = left_unit.mul(unit, &value.context())?;
+		let value = match conversion {
+			Some(conversion) => value.mul(conversion)?,
+			None => value,
+		};
+		Ok(Self::from_parts(value, unit))
+	}
+
+	/// Returns the value in coherent SI units together with its dimension, offsets are applied as well.
+	pub(crate) fn into_si(self) -> Result<(N, Dimension), Error> {
+		let (value, unit) = self.into_parts()?;
+		let mut value = value.clone().mul(unit.factor(&value.context())?)?;
+		if let Some(offset) = unit.offset() {
+			value = value.add(offset.clone())?;
+		}
+		Ok((value, unit.dimension()))
+	}
+
+	/// Converts the value into the given unit, e.g. `60 mph to km/h`.
+	///
+	/// Absolute temperatures like `100 degF in degC` are converted affinely,
+	/// while derived units like `degC/s` only use the factors.
+	pub(crate) fn convert(self, unit: &Unit<N>) -> Result<Self, Error> {
+		let from = match &self {
+			Self::Number(_) => Unit::dimensionless(),
+			Self::Quantity(quantity) => quantity.unit.clone(),
+			Self::List(_) => return self.map(|element| element.convert(unit)),
+			_ => return Err(Error::Runtime("Expected a number!")),
+		};
+		if from.dimension() != unit.dimension() {
+			return Err(Error::DimensionMismatch {
+				left: from.to_string(),
+				right: unit.to_string(),
+				start: 0,
+				end: 0,
+			});
+		}
+		if from == *unit {
+			return Ok(self);
+		}
+
+		let (mut value, _) = self.into_si()?;
+		if let Some(offset) = unit.offset() {
+			value = value.sub(offset.clone())?;
+		}
+		let value = value.clone().div(unit.factor(&value.context())?)?;
+		Ok(Self::from_parts(value, unit.clone()))
+	}
+
+	/// Converts a dimensionless exponent into an integer for raising units to a power.
+	fn unit_exponent(exponent: &N) -> Option<i32> {
+		let exponent = exponent.to_f64();
+		if exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64 {
+			Some(exponent as i32)
+		} else {
+			None
+		}
+	}
+
+	pub(crate) fn add(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_matrix() || rhs.is_matrix() {
+			return self.matrix_elementwise(rhs, N::add);
+		}
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::add);
+		}
+		if self.is_polynomial() || rhs.is_polynomial() {
+			return polynomial::add(self, rhs);
+		}
+		if self.is_date_time() || rhs.is_date_time() {
+			return datetime::add(self, rhs);
+		}
+		self.additive(rhs, N::add)
+	}
+
+	pub(crate) fn sub(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_matrix() || rhs.is_matrix() {
+			return self.matrix_elementwise(rhs, N::sub);
+		}
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::sub);
+		}
+		if self.is_polynomial() || rhs.is_polynomial() {
+			return polynomial::sub(self, rhs);
+		}
+		if self.is_date_time() || rhs.is_date_time() {
+			return datetime::sub(self, rhs);
+		}
+		self.additive(rhs, N::sub)
+	}
+
+	pub(crate) fn mul(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_matrix() || rhs.is_matrix() {
+			return self.matrix_mul(rhs);
+		}
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::mul);
+		}
+		if self.is_polynomial() || rhs.is_polynomial() {
+			return polynomial::mul(self, rhs);
+		}
+		let (right, right_unit) = rhs.into_parts()?;
+		let (value, unit) = self.mul_unit(&right_unit)?.into_parts()?;
+		Ok(Self::from_parts(value.mul(right)?, unit))
+	}
+
+	pub(crate) fn div(self, rhs: Self) -> Result<Self, Error> {
+		if rhs.is_matrix() {
+			return Err(Error::Runtime(
+				"Division by a matrix is not defined, use `\\` for solving linear systems!",
+			));
+		}
+		if self.is_matrix() {
+			return self.matrix_elementwise(rhs, N::div);
+		}
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::div);
+		}
+		if self.is_polynomial() || rhs.is_polynomial() {
+			return polynomial::div(self, rhs);
+		}
+		let (right, right_unit) = rhs.into_parts()?;
+		let (value, unit) = self.mul_unit(&right_unit.powi(-1)?)?.into_parts()?;
+		Ok(Self::from_parts(value.div(right)?, unit))
+	}
+
+	pub(crate) fn rem(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::rem);
+		}
+		if self.is_polynomial() || rhs.is_polynomial() {
+			return polynomial::rem(self, rhs);
+		}
+		self.additive(rhs, N::rem)
+	}
+
+	pub(crate) fn pow(self, rhs: Self) -> Result<Self, Error> {
+		if let Self::Matrix(matrix) = self {
+			let n = Self::unit_exponent(&rhs.into_number()?).ok_or(Error::Runtime(
+				"Matrices can only be raised to integer powers!",
+			))?;
+			return Ok(Self::Matrix(matrix.powi(n)?));
+		}
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::pow);
+		}
+		if self.is_polynomial() {
+			return polynomial::pow(self, rhs);
+		}
+		let exponent = rhs.into_number()?;
+		let (value, unit) = self.into_parts()?;
+		if unit.is_empty() {
+			return Ok(Self::Number(value.pow(exponent)?));
+		}
+		let n = Self::unit_exponent(&exponent).ok_or(Error::Runtime(
+			"Quantities can only be raised to integer powers!",
+		))?;
+		Ok(Self::from_parts(value.pow(exponent)?, unit.powi(n)?))
+	}
+
+	pub(crate) fn root(self, rhs: Self) -> Result<Self, Error> {
+		if self.is_list() || rhs.is_list() {
+			return self.broadcast(rhs, Self::root);
+		}
+		let exponent = rhs.into_number()?;
+		let (value, unit) = self.into_parts()?;
+		if unit.is_empty() {
+			return Ok(Self::Number(value.root(exponent)?));
+		}
+		let unit = Self::unit_exponent(&exponent)
+			.filter(|n| *n != 0)
+			.and_then(|n| unit.root(n))
+			.ok_or(Error::Runtime("Root of the unit is not defined!"))?;
+		Ok(Self::from_parts(value.root(exponent)?, unit))
+	}
+
+	pub(crate) fn neg(self) -> Result<Self, Error> {
+		if let Self::Matrix(matrix) = self {
+			return Ok(Self::Matrix(matrix.map(N::neg)?));
+		}
+		if self.is_list() {
+			return self.map(Self::neg);
+		}
+		if let Self::Polynomial(polynomial) = self {
+			return Ok(Self::Polynomial(polynomial.neg()?));
+		}
+		let (value, unit) = self.into_parts()?;
+		Ok(Self::from_parts(value.neg()?, unit))
+	}
+
+	pub(crate) fn not(self) -> Result<Self, Error> {
+		Ok(Self::Bool(!self.into_bool()?))
+	}
+
+	pub(crate) fn bit_and(self, rhs: Self) -> Result<Self, Error> {
+		self.bitwise(rhs, |left, right| Ok(left & right))
+	}
+
+	pub(crate) fn bit_or(self, rhs: Self) -> Result<Self, Error> {
+		self.bitwise(rhs, |left, right| Ok(left | right))
+	}
+
+	pub(crate) fn bit_xor(self, rhs: Self) -> Result<Self, Error> {
+		self.bitwise(rhs, |left, right| Ok(left ^ right))
+	}
+
+	/// Shifts to the left, which multiplies by a power of two.
+	pub(crate) fn shl(self, rhs: Self) -> Result<Self, Error> {
+		self.bitwise(rhs, |left, right| Ok(left << shift_amount(&right)?))
+	}
+
+	/// Shifts to the right, which divides by a power of two rounding towards negative infinity.
+	pub(crate) fn shr(self, rhs: Self) -> Result<Self, Error> {
+		self.bitwise(rhs, |left, right| Ok(left >> shift_amount(&right)?))
+	}
+
+	/// Inverts all bits, which is `-x - 1` in two's complement.
+	pub(crate) fn bit_not(self) -> Result<Self, Error> {
+		self.clone().bitwise(self, |value, _| Ok(!value))
+	}
+
+	/// Applies the operator on integers, see [`Number::bitwise`].
+	fn bitwise(
+		self,
+		rhs: Self,
+		op: fn(BigInt, BigInt) -> Result<BigInt, Error>,
+	) -> Result<Self, Error> {
+		match (self, rhs) {
+			(Self::Number(left), Self::Number(right)) => Ok(Self::Number(left.bitwise(right, op)?)),
+			_ => Err(Error::Runtime(
+				"Bitwise operators are only defined for integers!",
+			)),
+		}
+	}
+}
+
+/// Converts the right operand of a shift, which is limited so the result can't exhaust the memory.
+fn shift_amount(amount: &BigInt) -> Result<usize, Error> {
+	const MAX_SHIFT: usize = 1 << 16;
+	if amount.is_negative() {
+		return Err(Error::Runtime("Shift amount must not be negative!"));
+	}
+	amount
+		.to_usize()
+		.filter(|amount| *amount <= MAX_SHIFT)
+		.ok_or(Error::Runtime("Shift amount is too large!"))
+}
+
+impl<N> From<N> for Value<N> {
+	fn from(value: N) -> Self {
+		Self::Number(value)
+	}
+}
+
+impl<N: PartialEq> PartialEq<N> for Value<N> {
+	fn eq(&self, other: &N) -> bool {
+		matches!(self, Self::Number(number) if number == other)
+	}
+}
+
+impl<N: Number> fmt::Display for Value<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Number(number) => write!(f, "{}", number),
+			Self::Quantity(quantity) => write!(f, "{}", quantity),
+			Self::Bool(value) => write!(f, "{}", value),
+			Self::Matrix(matrix) => write!(f, "{}", matrix),
+			Self::Date(date) => write!(f, "{}", date),
+			Self::Time(time) => write!(f, "{}", time),
+			Self::DateTime(date_time) => write!(f, "{}", date_time),
+			Self::Function(function) => write!(f, "{}", function),
+			Self::Polynomial(polynomial) => write!(f, "{}", polynomial),
+			Self::List(elements) => {
+				write!(f, "[")?;
+				for (i, element) in elements.iter().enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{}", element)?;
+				}
+				write!(f, "]")
+			}
+		}
+	}
+}
@@ -0,0 +1,576 @@
+use std::fmt;
+
+use num_complex::Complex64;
+
+use crate::{Error, Number, Value};
+
+/// Maximum number of Durand-Kerner iterations for finding the roots.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Polynomial in one variable, e.g. `x**3 - 6*x**2 + 11*x - 6`.
+///
+/// # Example
+///
+/// ```
+/// use calculator::*;
+///
+/// let mut calculator = Calculator::new();
+/// calculator.init_std();
+///
+/// let val = calculator.calculate("roots(x**3 - 6x**2 + 11x - 6)").unwrap();
+/// assert_eq!(val.to_string(), "[1, 2, 3]");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<N> {
+	var: String,
+	/// Coefficients in ascending order of the powers without trailing zeros.
+	coefficients: Vec<N>,
+}
+
+impl<N: Number> Polynomial<N> {
+	/// Creates a polynomial from its coefficients in descending order, e.g. `[1, 0, -4]` for `x**2 - 4`.
+	pub fn new(var: impl Into<String>, coefficients: Vec<N>) -> Self {
+		let mut coefficients = coefficients;
+		coefficients.reverse();
+		Self::from_ascending(var.into(), coefficients)
+	}
+
+	/// Creates the polynomial `x` of the variable.
+	pub fn variable(var: impl Into<String>, ctx: &N::Context) -> Result<Self, Error> {
+		let coefficients = vec![N::parse("1", ctx)?, N::parse("0", ctx)?];
+		Ok(Self::new(var, coefficients))
+	}
+
+	pub fn constant(var: impl Into<String>, value: N) -> Self {
+		Self::new(var, vec![value])
+	}
+
+	fn from_ascending(var: String, mut coefficients: Vec<N>) -> Self {
+		while coefficients.last().is_some_and(is_zero) {
+			coefficients.pop();
+		}
+		Self { var, coefficients }
+	}
+
+	pub fn var(&self) -> &str {
+		&self.var
+	}
+
+	/// Returns the coefficients in descending order, the zero polynomial has no coefficients.
+	pub fn coefficients(&self) -> Vec<N> {
+		self.coefficients.iter().rev().cloned().collect()
+	}
+
+	/// Returns the highest power, constants including the zero polynomial have the degree 0.
+	pub fn degree(&self) -> usize {
+		self.coefficients.len().saturating_sub(1)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.coefficients.is_empty()
+	}
+
+	/// Returns the common variable, constants can be combined with any polynomial.
+	fn common_var(&self, rhs: &Self) -> Result<String, Error> {
+		if self.degree() == 0 {
+			Ok(rhs.var.clone())
+		} else if rhs.degree() == 0 || self.var == rhs.var {
+			Ok(self.var.clone())
+		} else {
+			Err(Error::Runtime("Polynomials must have the same variable!"))
+		}
+	}
+
+	pub(crate) fn add(self, rhs: Self) -> Result<Self, Error> {
+		let var = self.common_var(&rhs)?;
+		let (mut long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+			(self.coefficients, rhs.coefficients)
+		} else {
+			(rhs.coefficients, self.coefficients)
+		};
+		for (i, coefficient) in short.into_iter().enumerate() {
+			long[i] = long[i].clone().add(coefficient)?;
+		}
+		Ok(Self::from_ascending(var, long))
+	}
+
+	pub(crate) fn sub(self, rhs: Self) -> Result<Self, Error> {
+		self.add(rhs.neg()?)
+	}
+
+	pub(crate) fn neg(self) -> Result<Self, Error> {
+		let coefficients = self
+			.coefficients
+			.into_iter()
+			.map(N::neg)
+			.collect::<Result<_, _>>()?;
+		Ok(Self::from_ascending(self.var, coefficients))
+	}
+
+	pub(crate) fn mul(self, rhs: Self) -> Result<Self, Error> {
+		let var = self.common_var(&rhs)?;
+		if self.is_zero() || rhs.is_zero() {
+			return Ok(Self::from_ascending(var, Vec::new()));
+		}
+		let mut coefficients: Vec<Option<N>> =
+			vec![None; self.coefficients.len() + rhs.coefficients.len() - 1];
+		for (i, left) in self.coefficients.iter().enumerate() {
+			for (j, right) in rhs.coefficients.iter().enumerate() {
+				let product = left.clone().mul(right.clone())?;
+				coefficients[i + j] = Some(match coefficients[i + j].take() {
+					Some(sum) => sum.add(product)?,
+					None => product,
+				});
+			}
+		}
+		Ok(Self::from_ascending(
+			var,
+			coefficients.into_iter().flatten().collect(),
+		))
+	}
+
+	/// Raises the polynomial to a non-negative integer power by repeated squaring.
+	pub(crate) fn powi(self, mut n: u32) -> Result<Self, Error> {
+		let one = match self.coefficients.first() {
+			Some(coefficient) => N::parse("1", &coefficient.context())?,
+			None if n == 0 => return Err(Error::Runtime("0 ** 0 is not defined!")),
+			None => return Ok(self),
+		};
+		let mut result = Self::constant(self.var.clone(), one);
+		let mut base = self;
+		while n > 0 {
+			if n % 2 == 1 {
+				result = result.mul(base.clone())?;
+			}
+			base = base.clone().mul(base)?;
+			n /= 2;
+		}
+		Ok(result)
+	}
+
+	/// Divides by another polynomial, returning the quotient and the remainder.
+	pub(crate) fn div_rem(self, rhs: Self) -> Result<(Self, Self), Error> {
+		let var = self.common_var(&rhs)?;
+		let divisor = rhs
+			.coefficients
+			.last()
+			.ok_or(Error::Runtime("Division by 0!"))?;
+		if self.coefficients.len() < rhs.coefficients.len() {
+			return Ok((
+				Self::from_ascending(var.clone(), Vec::new()),
+				Self::from_ascending(var, self.coefficients),
+			));
+		}
+		let mut remainder = self.coefficients;
+		let mut quotient = Vec::new();
+		for i in (0..=remainder.len() - rhs.coefficients.len()).rev() {
+			let factor = remainder[i + rhs.coefficients.len() - 1]
+				.clone()
+				.div(divisor.clone())?;
+			for (j, coefficient) in rhs.coefficients.iter().enumerate() {
+				remainder[i + j] = remainder[i + j]
+					.clone()
+					.sub(factor.clone().mul(coefficient.clone())?)?;
+			}
+			quotient.push(factor);
+		}
+		quotient.reverse();
+		remainder.truncate(rhs.coefficients.len() - 1);
+		Ok((
+			Self::from_ascending(var.clone(), quotient),
+			Self::from_ascending(var, remainder),
+		))
+	}
+
+	/// Evaluates the polynomial at the given point with Horner's method.
+	pub fn eval(&self, x: N) -> Result<N, Error> {
+		let mut result = N::parse("0", &x.context())?;
+		for coefficient in self.coefficients.iter().rev() {
+			result = result.mul(x.clone())?.add(coefficient.clone())?;
+		}
+		Ok(result)
+	}
+
+	pub fn derivative(&self) -> Result<Self, Error> {
+		let coefficients = self
+			.coefficients
+			.iter()
+			.enumerate()
+			.skip(1)
+			.map(|(i, coefficient)| {
+				let power = N::parse(&i.to_string(), &coefficient.context())?;
+				coefficient.clone().mul(power)
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(Self::from_ascending(self.var.clone(), coefficients))
+	}
+
+	/// Calculates all roots with their multiplicity, real roots in ascending order followed by complex roots.
+	///
+	/// The roots are approximated with `f64` precision by the Durand-Kerner method,
+	/// complex roots require a number type supporting them like [`Complex`](crate::Complex).
+	pub fn roots(&self) -> Result<Vec<N>, Error> {
+		let ctx = match self.coefficients.first() {
+			Some(coefficient) => coefficient.context(),
+			None => {
+				return Err(Error::Runtime(
+					"The zero polynomial has infinitely many roots!",
+				))
+			}
+		};
+		// zero roots are split off, so they are exact
+		let zeros = self.coefficients.iter().take_while(|c| is_zero(*c)).count();
+		let coefficients = self.coefficients[zeros..]
+			.iter()
+			.map(|c| {
+				Ok(Complex64::new(
+					c.clone().re()?.to_f64(),
+					c.clone().im()?.to_f64(),
+				))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let mut roots = vec![Complex64::new(0.0, 0.0); zeros];
+		roots.extend(durand_kerner(&coefficients));
+
+		let (mut real, mut complex): (Vec<_>, Vec<_>) =
+			roots.into_iter().partition(|root| root.im == 0.0);
+		real.sort_by(|a, b| a.re.total_cmp(&b.re));
+		complex.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+		let i = N::imaginary_unit(&ctx).ok();
+		if i.is_none() && !complex.is_empty() {
+			return Err(Error::Runtime(
+				"The polynomial has complex roots, which require complex numbers!",
+			));
+		}
+		real.into_iter()
+			.chain(complex)
+			.map(|root| self.exact_root(root, i.as_ref(), &ctx))
+			.collect()
+	}
+
+	/// Converts an approximated root, which is rounded to an integer if that's an exact root.
+	fn exact_root(&self, root: Complex64, i: Option<&N>, ctx: &N::Context) -> Result<N, Error> {
+		let to_number = |root: Complex64| match i {
+			Some(i) if root.im != 0.0 => {
+				let im = N::from_f64(root.im, ctx)?.mul(i.clone())?;
+				N::from_f64(root.re, ctx)?.add(im)
+			}
+			_ => N::from_f64(root.re, ctx),
+		};
+		let round = |x: f64| {
+			if (x - x.round()).abs() < 1e-9 * x.abs().max(1.0) {
+				x.round()
+			} else {
+				x
+			}
+		};
+		let rounded = Complex64::new(round(root.re), round(root.im));
+		if rounded != root {
+			let candidate = to_number(rounded)?;
+			if is_zero(&self.eval(candidate.clone())?) {
+				return Ok(candidate);
+			}
+		}
+		to_number(root)
+	}
+
+	/// Converts the coefficients into another number type.
+	pub(crate) fn map_numbers<M>(
+		self,
+		f: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<Polynomial<M>, Error> {
+		let coefficients = self
+			.coefficients
+			.into_iter()
+			.map(f)
+			.collect::<Result<_, _>>()?;
+		Ok(Polynomial {
+			var: self.var,
+			coefficients,
+		})
+	}
+}
+
+fn is_zero<N: Number>(number: &N) -> bool {
+	N::parse("0", &number.context()).is_ok_and(|zero| *number == zero)
+}
+
+/// Approximates the roots of a polynomial with coefficients in ascending order and a non-zero constant.
+fn durand_kerner(coefficients: &[Complex64]) -> Vec<Complex64> {
+	let degree = coefficients.len() - 1;
+	let leading = coefficients[degree];
+	let monic: Vec<_> = coefficients.iter().map(|c| c / leading).collect();
+	let eval = |x: Complex64| eval(&monic, x);
+
+	// the initial guesses are spread on a circle containing all roots (Cauchy's bound)
+	let radius = 1.0 + monic[..degree].iter().map(|c| c.norm()).fold(0.0, f64::max);
+	let mut roots: Vec<_> = (0..degree)
+		.map(|k| {
+			Complex64::from_polar(
+				radius,
+				0.4 + std::f64::consts::TAU * k as f64 / degree as f64,
+			)
+		})
+		.collect();
+	for _ in 0..MAX_ITERATIONS {
+		let mut change: f64 = 0.0;
+		for k in 0..degree {
+			let mut denominator = Complex64::new(1.0, 0.0);
+			for j in 0..degree {
+				if j != k {
+					denominator *= roots[k] - roots[j];
+				}
+			}
+			let delta = eval(roots[k]) / denominator;
+			if delta.is_finite() {
+				roots[k] -= delta;
+				change = change.max(delta.norm() / (1.0 + roots[k].norm()));
+			}
+		}
+		if change < 1e-15 {
+			break;
+		}
+	}
+
+	// multiple roots converge slowly to a cluster, whose mean is much more accurate
+	let tolerance = 1e-5;
+	let mut clusters: Vec<Vec<Complex64>> = Vec::new();
+	for root in roots {
+		match clusters
+			.iter_mut()
+			.find(|cluster| (cluster[0] - root).norm() < tolerance * (1.0 + root.norm()))
+		{
+			Some(cluster) => cluster.push(root),
+			None => clusters.push(vec![root]),
+		}
+	}
+	let mut result = Vec::new();
+	for cluster in clusters {
+		// a root with multiplicity m is a simple root of the (m - 1)th derivative
+		let mut derivative = monic.clone();
+		for _ in 1..cluster.len() {
+			derivative = derive(&derivative);
+		}
+		let mean = cluster.iter().sum::<Complex64>() / cluster.len() as f64;
+		let mut root = newton(&derivative, mean);
+		if root.im.abs() < 1e-10 * (1.0 + root.re.abs()) {
+			root.im = 0.0;
+		}
+		result.extend(std::iter::repeat_n(root, cluster.len()));
+	}
+	result
+}
+
+fn eval(coefficients: &[Complex64], x: Complex64) -> Complex64 {
+	coefficients
+		.iter()
+		.rev()
+		.fold(Complex64::new(0.0, 0.0), |sum, c| sum * x + c)
+}
+
+fn derive(coefficients: &[Complex64]) -> Vec<Complex64> {
+	coefficients
+		.iter()
+		.enumerate()
+		.skip(1)
+		.map(|(i, c)| c * i as f64)
+		.collect()
+}
+
+/// Improves an approximated simple root with a few Newton steps, as long as they reduce the error.
+fn newton(coefficients: &[Complex64], mut x: Complex64) -> Complex64 {
+	let derivative = derive(coefficients);
+	for _ in 0..3 {
+		let step = eval(coefficients, x) / eval(&derivative, x);
+		if !step.is_finite() || eval(coefficients, x - step).norm() >= eval(coefficients, x).norm()
+		{
+			break;
+		}
+		x -= step;
+	}
+	x
+}
+
+/// Converts a value into a polynomial, numbers are constants.
+fn into_polynomial<N: Number>(value: Value<N>) -> Result<Polynomial<N>, Error> {
+	match value {
+		Value::Polynomial(polynomial) => Ok(polynomial),
+		Value::Number(number) => Ok(Polynomial::constant("x", number)),
+		_ => Err(Error::Runtime("Expected a polynomial!")),
+	}
+}
+
+pub(crate) fn add<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let result = into_polynomial(left)?.add(into_polynomial(right)?)?;
+	Ok(Value::Polynomial(result))
+}
+
+pub(crate) fn sub<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let result = into_polynomial(left)?.sub(into_polynomial(right)?)?;
+	Ok(Value::Polynomial(result))
+}
+
+pub(crate) fn mul<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let result = into_polynomial(left)?.mul(into_polynomial(right)?)?;
+	Ok(Value::Polynomial(result))
+}
+
+/// Divides polynomials, which is only defined if there is no remainder.
+pub(crate) fn div<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let (quotient, remainder) = into_polynomial(left)?.div_rem(into_polynomial(right)?)?;
+	if !remainder.is_zero() {
+		return Err(Error::Runtime(
+			"Division of polynomials has a remainder, use `%` for it!",
+		));
+	}
+	Ok(Value::Polynomial(quotient))
+}
+
+pub(crate) fn rem<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let (_, remainder) = into_polynomial(left)?.div_rem(into_polynomial(right)?)?;
+	Ok(Value::Polynomial(remainder))
+}
+
+pub(crate) fn pow<N: Number>(left: Value<N>, right: Value<N>) -> Result<Value<N>, Error> {
+	let exponent = right.into_number()?.to_f64();
+	if exponent.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&exponent) {
+		return Err(Error::Runtime(
+			"Polynomials can only be raised to non-negative integer powers!",
+		));
+	}
+	let result = into_polynomial(left)?.powi(exponent as u32)?;
+	Ok(Value::Polynomial(result))
+}
+
+/// Evaluates the polynomial at a value, e.g. a number, a quantity or another polynomial.
+pub(crate) fn evaluate<N: Number>(
+	polynomial: &Polynomial<N>,
+	x: Value<N>,
+) -> Result<Value<N>, Error> {
+	let mut coefficients = polynomial.coefficients.iter().rev().cloned();
+	let mut result = match coefficients.next() {
+		Some(coefficient) => Value::Number(coefficient),
+		None => Value::Number(N::parse("0", &N::Context::default())?),
+	};
+	for coefficient in coefficients {
+		result = result.mul(x.clone())?.add(Value::Number(coefficient))?;
+	}
+	Ok(result)
+}
+
+impl<N: Number> fmt::Display for Polynomial<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		let mut first = true;
+		for (power, coefficient) in self.coefficients.iter().enumerate().rev() {
+			if is_zero(coefficient) {
+				continue;
+			}
+			let mut coefficient = coefficient.to_string();
+			// complex coefficients like `1+2i` are put in brackets
+			if coefficient[1..].contains(['+', '-']) {
+				coefficient = format!("({})", coefficient);
+			}
+			match coefficient.strip_prefix('-') {
+				Some(abs) if first => {
+					write!(f, "-")?;
+					coefficient = abs.to_owned();
+				}
+				Some(abs) => {
+					write!(f, " - ")?;
+					coefficient = abs.to_owned();
+				}
+				None if first => (),
+				None => write!(f, " + ")?,
+			}
+			first = false;
+			match power {
+				0 => write!(f, "{}", coefficient)?,
+				_ if coefficient != "1" => write!(f, "{}*{}", coefficient, self.var)?,
+				_ => write!(f, "{}", self.var)?,
+			}
+			if power > 1 {
+				write!(f, "**{}", power)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Complex;
+
+	fn p(coefficients: &[f64]) -> Polynomial<f64> {
+		Polynomial::new("x", coefficients.to_vec())
+	}
+
+	#[test]
+	fn test_01_arithmetic() {
+		assert_eq!(
+			p(&[1.0, -6.0, 11.0, -6.0]).to_string(),
+			"x**3 - 6*x**2 + 11*x - 6"
+		);
+		assert_eq!(p(&[-1.0, 0.0, 0.5]).to_string(), "-x**2 + 0.5");
+		assert_eq!(p(&[1.0, 1.0]).add(p(&[-1.0, 1.0])).unwrap(), p(&[2.0]));
+		assert_eq!(p(&[1.0, 1.0]).sub(p(&[1.0, 1.0])).unwrap().to_string(), "0");
+		assert_eq!(
+			p(&[1.0, 1.0]).mul(p(&[1.0, -1.0])).unwrap(),
+			p(&[1.0, 0.0, -1.0])
+		);
+		assert_eq!(p(&[1.0, 1.0]).powi(3).unwrap(), p(&[1.0, 3.0, 3.0, 1.0]));
+
+		let (quotient, remainder) = p(&[1.0, -6.0, 11.0, -5.0])
+			.div_rem(p(&[1.0, -1.0]))
+			.unwrap();
+		assert_eq!(quotient, p(&[1.0, -5.0, 6.0]));
+		assert_eq!(remainder, p(&[1.0]));
+		assert_eq!(
+			p(&[1.0]).div_rem(p(&[])),
+			Err(Error::Runtime("Division by 0!"))
+		);
+
+		assert_eq!(p(&[1.0, 0.0, -4.0]).eval(3.0).unwrap(), 5.0);
+		assert_eq!(
+			p(&[1.0, -6.0, 11.0, -6.0]).derivative().unwrap(),
+			p(&[3.0, -12.0, 11.0])
+		);
+		assert_eq!(
+			Polynomial::new("y", vec![1.0, 0.0]).add(p(&[1.0, 0.0])),
+			Err(Error::Runtime("Polynomials must have the same variable!"))
+		);
+	}
+
+	#[test]
+	fn test_02_roots() {
+		assert_eq!(
+			p(&[1.0, -6.0, 11.0, -6.0]).roots().unwrap(),
+			vec![1.0, 2.0, 3.0]
+		);
+		assert_eq!(p(&[1.0, -2.0, 1.0]).roots().unwrap(), vec![1.0, 1.0]);
+		assert_eq!(p(&[1.0, 0.0, 0.0]).roots().unwrap(), vec![0.0, 0.0]);
+		assert_eq!(p(&[2.0, 0.0, -8.0]).roots().unwrap(), vec![-2.0, 2.0]);
+		// complex roots require a complex number type
+		assert_eq!(
+			p(&[1.0, 0.0, 1.0]).roots(),
+			Err(Error::Runtime(
+				"The polynomial has complex roots, which require complex numbers!"
+			))
+		);
+		assert_eq!(
+			Polynomial::<Complex>::new("x", vec![1.0.into(), 0.0.into(), 1.0.into()])
+				.roots()
+				.unwrap(),
+			vec![Complex::new(0.0, -1.0), Complex::new(0.0, 1.0)]
+		);
+		assert_eq!(
+			p(&[]).roots(),
+			Err(Error::Runtime(
+				"The zero polynomial has infinitely many roots!"
+			))
+		);
+	}
+}
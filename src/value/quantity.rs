@@ -0,0 +1,22 @@
+use std::fmt;
+
+use crate::{Error, Number, NumberFormat, Unit};
+
+/// Number with a physical unit, e.g. `2.5 m/s`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity<N> {
+	pub value: N,
+	pub unit: Unit<N>,
+}
+
+impl<N: Number> Quantity<N> {
+	pub(crate) fn format(&self, format: &NumberFormat) -> Result<String, Error> {
+		Ok(format!("{} {}", self.value.format(format)?, self.unit))
+	}
+}
+
+impl<N: Number> fmt::Display for Quantity<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.value, self.unit)
+	}
+}
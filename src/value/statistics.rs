@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+
+use crate::{Error, Number, Value};
+
+use super::list::count;
+
+/// Returns the values of a variadic call, lists and matrices are flattened into one sample
+/// (e.g. `mean([1, 2], 3)` is the mean of `1`, `2` and `3`).
+fn values<N: Number>(args: Vec<Value<N>>) -> Result<Vec<Value<N>>, Error> {
+	let mut values = Vec::with_capacity(args.len());
+	for arg in args {
+		match arg {
+			Value::List(_) | Value::Matrix(_) => values.extend(self::values(arg.into_list()?)?),
+			_ => values.push(arg),
+		}
+	}
+	Ok(values)
+}
+
+/// Sorts the values in ascending order, which fails for values without an order.
+fn sorted<N: Number>(mut values: Vec<Value<N>>) -> Result<Vec<Value<N>>, Error> {
+	let mut error = None;
+	values.sort_by(|left, right| {
+		left.clone().compare(right.clone()).unwrap_or_else(|e| {
+			error.get_or_insert(e);
+			Ordering::Equal
+		})
+	});
+	match error {
+		Some(e) => Err(e),
+		None => Ok(values),
+	}
+}
+
+/// Adds all values, the sum of no values is `0`.
+pub(crate) fn sum<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	let zero = count(0, &values)?;
+	let mut values = values.into_iter();
+	match values.next() {
+		Some(first) => values.try_fold(first, Value::add),
+		None => Ok(Value::Number(zero)),
+	}
+}
+
+/// Multiplies all values, the product of no values is `1`.
+pub(crate) fn product<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	let one = count(1, &values)?;
+	let mut values = values.into_iter();
+	match values.next() {
+		Some(first) => values.try_fold(first, Value::mul),
+		None => Ok(Value::Number(one)),
+	}
+}
+
+pub(crate) fn mean<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	if values.is_empty() {
+		return Err(Error::Runtime("Mean of an empty list is not defined!"));
+	}
+	let n = count(values.len(), &values)?;
+	sum(values)?.div(Value::Number(n))
+}
+
+/// Calculates the sample variance, where the squared deviations are divided by `n - 1`.
+pub(crate) fn variance<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	if values.len() < 2 {
+		return Err(Error::Runtime("Variance needs at least two values!"));
+	}
+	let n = count(values.len() - 1, &values)?;
+	let mean = mean(values.clone())?;
+	let squares = values
+		.into_iter()
+		.map(|value| {
+			let deviation = value.sub(mean.clone())?;
+			deviation.clone().mul(deviation)
+		})
+		.collect::<Result<_, _>>()?;
+	sum(squares)?.div(Value::Number(n))
+}
+
+/// Calculates the sample standard deviation, the square root of the sample variance.
+pub(crate) fn stdev<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	let two = count(2, &values)?;
+	variance(values)?.root(Value::Number(two))
+}
+
+pub(crate) fn min<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	extremum(args, Ordering::Less)?
+		.ok_or(Error::Runtime("Minimum of an empty list is not defined!"))
+}
+
+pub(crate) fn max<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	extremum(args, Ordering::Greater)?
+		.ok_or(Error::Runtime("Maximum of an empty list is not defined!"))
+}
+
+/// Returns the first value which is ordered before all others as given by the ordering.
+fn extremum<N: Number>(args: Vec<Value<N>>, ordering: Ordering) -> Result<Option<Value<N>>, Error> {
+	let mut values = values(args)?.into_iter();
+	let Some(mut extremum) = values.next() else {
+		return Ok(None);
+	};
+	for value in values {
+		if value.clone().compare(extremum.clone())? == ordering {
+			extremum = value;
+		}
+	}
+	Ok(Some(extremum))
+}
+
+pub(crate) fn median<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let values = values(args)?;
+	if values.is_empty() {
+		return Err(Error::Runtime("Median of an empty list is not defined!"));
+	}
+	let rank = count(values.len() - 1, &values)?.div(count(2, &values)?)?;
+	interpolate(sorted(values)?, rank)
+}
+
+/// Returns the most frequent value, the smallest one if multiple values are equally frequent.
+pub(crate) fn mode<N: Number>(args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let mut values = sorted(values(args)?)?.into_iter();
+	let Some(first) = values.next() else {
+		return Err(Error::Runtime("Mode of an empty list is not defined!"));
+	};
+	let (mut mode, mut frequency) = (first.clone(), 1);
+	let (mut current, mut run) = (first, 1);
+	for value in values {
+		if value.clone().equals(current.clone())? {
+			run += 1;
+		} else {
+			(current, run) = (value, 1);
+		}
+		if run > frequency {
+			(mode, frequency) = (current.clone(), run);
+		}
+	}
+	Ok(mode)
+}
+
+/// Calculates the percentile by linear interpolation between the closest ranks,
+/// the percentile between 0 and 100 is the last argument (e.g. `percentile(v, 90)` or `percentile(1, 2, 3, 90)`).
+pub(crate) fn percentile<N: Number>(mut args: Vec<Value<N>>) -> Result<Value<N>, Error> {
+	let p = args
+		.pop()
+		.ok_or(Error::Runtime("Too few arguments!"))?
+		.into_number()?;
+	let values = values(args)?;
+	if values.is_empty() {
+		return Err(Error::Runtime(
+			"Percentile of an empty list is not defined!",
+		));
+	}
+	if !(0.0..=100.0).contains(&p.to_f64()) {
+		return Err(Error::Runtime("Percentile must be between 0 and 100!"));
+	}
+	let rank = p
+		.mul(count(values.len() - 1, &values)?)?
+		.div(count(100, &values)?)?;
+	interpolate(sorted(values)?, rank)
+}
+
+/// Interpolates linearly between the sorted values at the fractional rank, the first value has the rank `0`.
+fn interpolate<N: Number>(mut sorted: Vec<Value<N>>, rank: N) -> Result<Value<N>, Error> {
+	let index = (rank.to_f64().floor() as usize).min(sorted.len() - 1);
+	let fraction = rank.sub(count(index, &sorted)?)?;
+	if index + 1 == sorted.len() || fraction.to_f64() == 0.0 {
+		return Ok(sorted.swap_remove(index));
+	}
+	let lower = sorted[index].clone();
+	let difference = sorted.swap_remove(index + 1).sub(lower.clone())?;
+	lower.add(difference.mul(Value::Number(fraction))?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Matrix;
+
+	fn list(values: &[f64]) -> Vec<Value<f64>> {
+		values.iter().map(|&value| Value::Number(value)).collect()
+	}
+
+	#[test]
+	fn test_01_statistics() {
+		let values = list(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+		assert_eq!(sum(values.clone()).unwrap(), 40.0);
+		assert_eq!(mean(values.clone()).unwrap(), 5.0);
+		assert_eq!(median(values.clone()).unwrap(), 4.5);
+		assert_eq!(mode(values.clone()).unwrap(), 4.0);
+		assert_eq!(variance(values.clone()).unwrap(), 32.0 / 7.0);
+		assert_eq!(min(values.clone()).unwrap(), 2.0);
+		assert_eq!(max(values).unwrap(), 9.0);
+
+		// a single list is used as the values
+		assert_eq!(
+			product(vec![Value::List(list(&[2.0, 3.0, 4.0]))]).unwrap(),
+			24.0
+		);
+		assert_eq!(product(vec![]).unwrap(), 1.0);
+		// lists and matrices are flattened into one sample
+		assert_eq!(
+			mean(vec![Value::List(list(&[1.0, 2.0])), Value::Number(3.0)]).unwrap(),
+			2.0
+		);
+		assert_eq!(
+			sum(vec![
+				Value::Number(1.0),
+				Value::Matrix(Matrix::new(2, 2, vec![2.0, 3.0, 4.0, 5.0]).unwrap())
+			])
+			.unwrap(),
+			15.0
+		);
+		assert_eq!(mode(list(&[3.0, 1.0, 3.0, 1.0])).unwrap(), 1.0);
+	}
+
+	#[test]
+	fn test_02_percentile() {
+		let values = list(&[15.0, 20.0, 35.0, 40.0, 50.0]);
+		let percentile = |p: f64| {
+			let mut args = values.clone();
+			args.push(Value::Number(p));
+			percentile(args)
+		};
+		assert_eq!(percentile(0.0).unwrap(), 15.0);
+		assert_eq!(percentile(25.0).unwrap(), 20.0);
+		assert_eq!(percentile(62.5).unwrap(), 37.5);
+		assert_eq!(percentile(100.0).unwrap(), 50.0);
+		assert_eq!(
+			percentile(101.0),
+			Err(Error::Runtime("Percentile must be between 0 and 100!"))
+		);
+		assert_eq!(
+			median::<f64>(vec![]),
+			Err(Error::Runtime("Median of an empty list is not defined!"))
+		);
+	}
+}
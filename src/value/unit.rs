@@ -0,0 +1,402 @@
+use std::fmt;
+
+use crate::{Error, Number};
+
+/// Exponents of the SI base dimensions in the order:
+/// length, mass, time, electric current, temperature, amount of substance, luminous intensity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dimension(pub [i32; 7]);
+
+impl Dimension {
+	pub fn is_dimensionless(&self) -> bool {
+		self.0.iter().all(|exponent| *exponent == 0)
+	}
+}
+
+/// SI prefixes with their factors, `da` has to be checked before `d` and micro is written as `u`.
+pub(crate) const PREFIXES: [(&str, &str); 20] = [
+	("da", "1e1"),
+	("Y", "1e24"),
+	("Z", "1e21"),
+	("E", "1e18"),
+	("P", "1e15"),
+	("T", "1e12"),
+	("G", "1e9"),
+	("M", "1e6"),
+	("k", "1e3"),
+	("h", "1e2"),
+	("d", "1e-1"),
+	("c", "1e-2"),
+	("m", "1e-3"),
+	("u", "1e-6"),
+	("n", "1e-9"),
+	("p", "1e-12"),
+	("f", "1e-15"),
+	("a", "1e-18"),
+	("z", "1e-21"),
+	("y", "1e-24"),
+];
+
+/// Units of the std lib as `(symbol, factor, dimension, prefixable)`,
+/// the factor converts a value of the unit into coherent SI units.
+#[rustfmt::skip]
+pub(crate) const STD_UNITS: [(&str, &str, [i32; 7], bool); 48] = [
+	// SI base units, the kilogram is defined by its prefix
+	("m", "1", [1, 0, 0, 0, 0, 0, 0], true),
+	("g", "1e-3", [0, 1, 0, 0, 0, 0, 0], true),
+	("s", "1", [0, 0, 1, 0, 0, 0, 0], true),
+	("A", "1", [0, 0, 0, 1, 0, 0, 0], true),
+	("K", "1", [0, 0, 0, 0, 1, 0, 0], true),
+	("mol", "1", [0, 0, 0, 0, 0, 1, 0], true),
+	("cd", "1", [0, 0, 0, 0, 0, 0, 1], true),
+	// derived SI units
+	("rad", "1", [0, 0, 0, 0, 0, 0, 0], true),
+	("Hz", "1", [0, 0, -1, 0, 0, 0, 0], true),
+	("N", "1", [1, 1, -2, 0, 0, 0, 0], true),
+	("Pa", "1", [-1, 1, -2, 0, 0, 0, 0], true),
+	("J", "1", [2, 1, -2, 0, 0, 0, 0], true),
+	("W", "1", [2, 1, -3, 0, 0, 0, 0], true),
+	("C", "1", [0, 0, 1, 1, 0, 0, 0], true),
+	("V", "1", [2, 1, -3, -1, 0, 0, 0], true),
+	("F", "1", [-2, -1, 4, 2, 0, 0, 0], true),
+	("ohm", "1", [2, 1, -3, -2, 0, 0, 0], true),
+	("S", "1", [-2, -1, 3, 2, 0, 0, 0], true),
+	("Wb", "1", [2, 1, -2, -1, 0, 0, 0], true),
+	("T", "1", [0, 1, -2, -1, 0, 0, 0], true),
+	("H", "1", [2, 1, -2, -2, 0, 0, 0], true),
+	// non-SI units accepted for use with the SI
+	("min", "60", [0, 0, 1, 0, 0, 0, 0], false),
+	("h", "3600", [0, 0, 1, 0, 0, 0, 0], false),
+	("d", "86400", [0, 0, 1, 0, 0, 0, 0], false),
+	("day", "86400", [0, 0, 1, 0, 0, 0, 0], false),
+	("days", "86400", [0, 0, 1, 0, 0, 0, 0], false),
+	("week", "604800", [0, 0, 1, 0, 0, 0, 0], false),
+	("weeks", "604800", [0, 0, 1, 0, 0, 0, 0], false),
+	// calendar units with their mean Gregorian length, dates are moved by calendar months instead
+	("month", "2629746", [0, 0, 1, 0, 0, 0, 0], false),
+	("months", "2629746", [0, 0, 1, 0, 0, 0, 0], false),
+	("year", "31556952", [0, 0, 1, 0, 0, 0, 0], false),
+	("years", "31556952", [0, 0, 1, 0, 0, 0, 0], false),
+	("L", "1e-3", [3, 0, 0, 0, 0, 0, 0], true),
+	("t", "1e3", [0, 1, 0, 0, 0, 0, 0], false),
+	("bar", "1e5", [-1, 1, -2, 0, 0, 0, 0], true),
+	("ha", "1e4", [2, 0, 0, 0, 0, 0, 0], false),
+	("Wh", "3600", [2, 1, -2, 0, 0, 0, 0], true),
+	("cal", "4.184", [2, 1, -2, 0, 0, 0, 0], true),
+	("atm", "101325", [-1, 1, -2, 0, 0, 0, 0], false),
+	("kn", "1852/3600", [1, 0, -1, 0, 0, 0, 0], false),
+	// imperial units
+	("inch", "0.0254", [1, 0, 0, 0, 0, 0, 0], false),
+	("ft", "0.3048", [1, 0, 0, 0, 0, 0, 0], false),
+	("yd", "0.9144", [1, 0, 0, 0, 0, 0, 0], false),
+	("mi", "1609.344", [1, 0, 0, 0, 0, 0, 0], false),
+	("mph", "0.44704", [1, 0, -1, 0, 0, 0, 0], false),
+	("lb", "0.45359237", [0, 1, 0, 0, 0, 0, 0], false),
+	("oz", "0.028349523125", [0, 1, 0, 0, 0, 0, 0], false),
+	("gal", "0.003785411784", [3, 0, 0, 0, 0, 0, 0], false),
+];
+
+/// Temperature units of the std lib with an offset as `(symbol, factor, offset)`,
+/// a value is converted into kelvin by `value * factor + offset`.
+pub(crate) const STD_TEMPERATURE_UNITS: [(&str, &str, &str); 2] =
+	[("degC", "1", "273.15"), ("degF", "5/9", "2298.35/9")];
+
+/// Parses the factor of a unit definition, which may be a fraction like `5/9`.
+pub(crate) fn parse_factor<N: Number>(src: &str, ctx: &N::Context) -> Result<N, Error> {
+	match src.split_once('/') {
+		Some((numerator, denominator)) => {
+			N::parse(numerator, ctx)?.div(N::parse(denominator, ctx)?)
+		}
+		None => N::parse(src, ctx),
+	}
+}
+
+/// Definition of a unit stored in the environment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnitDef<N> {
+	/// Factor converting a value of this unit into coherent SI units.
+	pub factor: N,
+	pub dimension: Dimension,
+	/// Whether SI prefixes can be applied, e.g. `km`.
+	pub prefixable: bool,
+	/// Offset added after applying the factor, used for temperature scales like `degC`.
+	pub offset: Option<N>,
+}
+
+/// Unit symbol raised to an integer power, e.g. `s**-2`.
+#[derive(Debug, Clone, PartialEq)]
+struct UnitPower<N> {
+	symbol: String,
+	factor: N,
+	dimension: Dimension,
+	offset: Option<N>,
+	exponent: i32,
+}
+
+/// Product of unit symbols, e.g. `m/s**2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit<N> {
+	powers: Vec<UnitPower<N>>,
+}
+
+/// Converts an integer into a number without losing exactness.
+fn integer<N: Number>(value: i32, ctx: &N::Context) -> Result<N, Error> {
+	N::parse(&value.to_string(), ctx)
+}
+
+impl<N: Number> Unit<N> {
+	pub(crate) fn new(symbol: impl Into<String>, def: UnitDef<N>) -> Self {
+		Self {
+			powers: vec![UnitPower {
+				symbol: symbol.into(),
+				factor: def.factor,
+				dimension: def.dimension,
+				offset: def.offset,
+				exponent: 1,
+			}],
+		}
+	}
+
+	/// Converts the factors and offsets into another number type.
+	pub(crate) fn map_numbers<M>(
+		self,
+		f: &impl Fn(N) -> Result<M, Error>,
+	) -> Result<Unit<M>, Error> {
+		let powers = self
+			.powers
+			.into_iter()
+			.map(|power| {
+				Ok(UnitPower {
+					symbol: power.symbol,
+					factor: f(power.factor)?,
+					dimension: power.dimension,
+					offset: power.offset.map(f).transpose()?,
+					exponent: power.exponent,
+				})
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(Unit { powers })
+	}
+
+	/// Creates the unit of plain numbers.
+	pub fn dimensionless() -> Self {
+		Self { powers: Vec::new() }
+	}
+
+	/// Checks if the unit has no symbols, which is not the same as being dimensionless (e.g. `rad`).
+	pub fn is_empty(&self) -> bool {
+		self.powers.is_empty()
+	}
+
+	pub fn dimension(&self) -> Dimension {
+		// the exponents are checked when units are combined, so the dimension is in range
+		self.checked_dimension().unwrap_or_default()
+	}
+
+	fn checked_dimension(&self) -> Option<Dimension> {
+		let mut dimension = Dimension::default();
+		for power in &self.powers {
+			for (total, exponent) in dimension.0.iter_mut().zip(power.dimension.0) {
+				*total = total.checked_add(exponent.checked_mul(power.exponent)?)?;
+			}
+		}
+		Some(dimension)
+	}
+
+	/// Fails if an exponent of the unit or its dimension doesn't fit into an `i32`.
+	fn checked(self) -> Result<Self, Error> {
+		match self.checked_dimension() {
+			Some(_) => Ok(self),
+			None => Err(Error::Runtime("Unit exponent is out of range!")),
+		}
+	}
+
+	/// Returns the factor converting a value of this unit into coherent SI units.
+	pub fn factor(&self, ctx: &N::Context) -> Result<N, Error> {
+		let mut result: N = integer(1, ctx)?;
+		for power in &self.powers {
+			let factor = power.factor.clone().pow(integer(power.exponent, ctx)?)?;
+			result = result.mul(factor)?;
+		}
+		Ok(result)
+	}
+
+	/// Returns the offset of absolute temperature units, e.g. `degC` but not `degC**2` or `degC/s`.
+	pub fn offset(&self) -> Option<&N> {
+		match self.powers.as_slice() {
+			[power] if power.exponent == 1 => power.offset.as_ref(),
+			_ => None,
+		}
+	}
+
+	/// Returns the symbol of a unit consisting of a single symbol, e.g. `km` but not `km**2` or `m/s`.
+	pub(crate) fn symbol(&self) -> Option<&str> {
+		match self.powers.as_slice() {
+			[power] if power.exponent == 1 => Some(&power.symbol),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn powi(&self, n: i32) -> Result<Self, Error> {
+		let mut result = self.clone();
+		for power in &mut result.powers {
+			power.exponent = power
+				.exponent
+				.checked_mul(n)
+				.ok_or(Error::Runtime("Unit exponent is out of range!"))?;
+		}
+		result.powers.retain(|power| power.exponent != 0);
+		result.checked()
+	}
+
+	/// Calculates the `n`-th root if all exponents are divisible by `n`.
+	pub(crate) fn root(&self, n: i32) -> Option<Self> {
+		let mut result = self.clone();
+		for power in &mut result.powers {
+			if power.exponent.checked_rem(n)? != 0 {
+				return None;
+			}
+			power.exponent = power.exponent.checked_div(n)?;
+		}
+		Some(result)
+	}
+
+	/// Multiplies the units, symbols with the same dimension as an existing symbol are converted
+	/// into it (e.g. `km * m` results in `km**2`) and the returned factor has to be applied on the value.
+	pub(crate) fn mul(&self, rhs: &Self, ctx: &N::Context) -> Result<(Self, Option<N>), Error> {
+		let mut powers = self.powers.clone();
+		let mut conversion: Option<N> = None;
+		let add = |left: i32, right: i32| {
+			left.checked_add(right)
+				.ok_or(Error::Runtime("Unit exponent is out of range!"))
+		};
+		for power in &rhs.powers {
+			if let Some(existing) = powers.iter_mut().find(|p| p.symbol == power.symbol) {
+				existing.exponent = add(existing.exponent, power.exponent)?;
+			} else if let Some(existing) =
+				powers.iter_mut().find(|p| p.dimension == power.dimension)
+			{
+				let factor = power
+					.factor
+					.clone()
+					.div(existing.factor.clone())?
+					.pow(integer(power.exponent, ctx)?)?;
+				conversion = Some(match conversion {
+					Some(conversion) => conversion.mul(factor)?,
+					None => factor,
+				});
+				existing.exponent = add(existing.exponent, power.exponent)?;
+			} else {
+				powers.push(power.clone());
+			}
+		}
+		powers.retain(|power| power.exponent != 0);
+		Ok((Self { powers }.checked()?, conversion))
+	}
+}
+
+impl<N> fmt::Display for Unit<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let format = |negative: bool| {
+			self.powers
+				.iter()
+				.filter(|power| (power.exponent < 0) == negative)
+				.map(|power| match power.exponent.abs() {
+					1 => power.symbol.clone(),
+					exponent => format!("{}**{}", power.symbol, exponent),
+				})
+				.collect::<Vec<_>>()
+		};
+		let numerator = format(false);
+		let denominator = format(true);
+
+		let numerator = match numerator.is_empty() {
+			true => "1".to_owned(),
+			false => numerator.join("*"),
+		};
+		match denominator.len() {
+			0 => write!(f, "{}", numerator),
+			1 => write!(f, "{}/{}", numerator, denominator[0]),
+			_ => write!(f, "{}/({})", numerator, denominator.join("*")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn unit(symbol: &str, factor: f64, dimension: [i32; 7]) -> Unit<f64> {
+		Unit::new(
+			symbol,
+			UnitDef {
+				factor,
+				dimension: Dimension(dimension),
+				prefixable: true,
+				offset: None,
+			},
+		)
+	}
+
+	#[test]
+	fn test_01_display() {
+		let m = unit("m", 1.0, [1, 0, 0, 0, 0, 0, 0]);
+		let s = unit("s", 1.0, [0, 0, 1, 0, 0, 0, 0]);
+		let kg = unit("kg", 1.0, [0, 1, 0, 0, 0, 0, 0]);
+
+		assert_eq!(m.to_string(), "m");
+		assert_eq!(
+			m.mul(&s.powi(-1).unwrap(), &()).unwrap().0.to_string(),
+			"m/s"
+		);
+		assert_eq!(s.powi(-2).unwrap().to_string(), "1/s**2");
+
+		let (newton, _) = kg.mul(&m, &()).unwrap();
+		let (newton, _) = newton.mul(&s.powi(-2).unwrap(), &()).unwrap();
+		assert_eq!(newton.to_string(), "kg*m/s**2");
+		assert_eq!(
+			newton
+				.mul(&kg.powi(-2).unwrap(), &())
+				.unwrap()
+				.0
+				.to_string(),
+			"m/(kg*s**2)"
+		);
+	}
+
+	#[test]
+	fn test_02_dimension() {
+		let m = unit("m", 1.0, [1, 0, 0, 0, 0, 0, 0]);
+		let s = unit("s", 1.0, [0, 0, 1, 0, 0, 0, 0]);
+
+		let (speed, _) = m.mul(&s.powi(-1).unwrap(), &()).unwrap();
+		assert_eq!(speed.dimension(), Dimension([1, 0, -1, 0, 0, 0, 0]));
+		assert!(m.mul(&m.powi(-1).unwrap(), &()).unwrap().0.is_empty());
+		assert_eq!(m.powi(2).unwrap().root(2), Some(m.clone()));
+		assert_eq!(m.root(2), None);
+	}
+
+	#[test]
+	fn test_03_conversion() {
+		let km = unit("km", 1000.0, [1, 0, 0, 0, 0, 0, 0]);
+		let m = unit("m", 1.0, [1, 0, 0, 0, 0, 0, 0]);
+
+		let (unit, conversion) = km.mul(&m, &()).unwrap();
+		assert_eq!(unit, km.powi(2).unwrap());
+		assert_eq!(conversion, Some(0.001));
+		assert_eq!(unit.factor(&()).unwrap(), 1e6);
+	}
+
+	#[test]
+	fn test_04_exponent_overflow() {
+		let err = Err(Error::Runtime("Unit exponent is out of range!"));
+		let m = unit("m", 1.0, [1, 0, 0, 0, 0, 0, 0]);
+		let n = unit("N", 1.0, [1, 1, -2, 0, 0, 0, 0]);
+
+		let large = m.powi(2_000_000_000).unwrap();
+		assert_eq!(large.powi(2), err);
+		assert_eq!(large.mul(&large, &()).map(|(unit, _)| unit), err);
+		// the exponent fits, but not the exponent of the dimension
+		assert_eq!(n.powi(1_500_000_000), err);
+	}
+}